@@ -0,0 +1,57 @@
+//! Plays again, headless, a battle stored on a battle log, like the `godot/last_battle_log.json` that the game writes
+//! after every battle. Prints every event of every step, and fails on the first step that doesn't end up as it was logged.
+//!
+//! Usage: `cargo run --bin battle_replay -- <path/to/battle_log.json>`
+
+use std::{env, fs, process};
+
+use pokemon_gallaecia::game::battle::battle_log::{BattleLog, BattleLogEntry, BattleReplay};
+
+fn main() {
+    let log_path = match env::args().nth(1) {
+        Some(log_path) => log_path,
+        None => {
+            eprintln!("Usage: battle_replay <path/to/battle_log.json>");
+            process::exit(2);
+        }
+    };
+    let battle_log = fs::read_to_string(&log_path)
+        .map_err(|err| format!("Can't open {}: {}", log_path, err))
+        .and_then(|json| BattleLog::from_json(&json));
+    let battle_log = match battle_log {
+        Ok(battle_log) => battle_log,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+
+    println!("{:?} {:?} battle, {} vs {}, seed {}, weather {:?}", battle_log.format, battle_log.kind,
+        battle_log.player.name, battle_log.opponent.name, battle_log.seed, battle_log.weather);
+    let steps: Vec<BattleLogEntry> = battle_log.entries.clone();
+    let mut replay = match BattleReplay::new(battle_log) {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+    for step in steps.iter() {
+        match step {
+            BattleLogEntry::Start { .. } => println!("-- Start"),
+            BattleLogEntry::Turn { turn, actions, .. } => println!("-- Turn {}: {:?}", turn, actions),
+            BattleLogEntry::Replacement { position, party_index, .. } =>
+                println!("-- Replacement of {:?} by the Pokémon at index {}", position, party_index)
+        }
+        match replay.play_next() {
+            Some(Ok(events)) => events.iter().for_each(|event| println!("   {:?}", event)),
+            Some(Err(err)) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            },
+            None => break
+        }
+    }
+
+    println!("Outcome: {:?} after {} turns", replay.engine().outcome(), replay.engine().turn());
+}
@@ -0,0 +1,38 @@
+//! Loads every data file of the game and cross-checks them, so the content can be validated before launching Godot:
+//! the species, the moves, the items, the wild encounter tables, the trainers with their dialogues, the maps with their scenes
+//! and the sprites of every specie.
+//! Prints every problem found, and fails when there's any of them. The missing content that the game can work without,
+//! like the sprites that have a placeholder, it's printed as warnings that don't fail.
+//!
+//! Usage: `cargo run --bin validate_game_data -- [path/to/the/godot/project]`. By default, the project where this crate lives
+
+use std::path::PathBuf;
+use std::{env, process};
+
+use pokemon_gallaecia::utils::data_validation;
+
+fn main() {
+    let project_root = env::args().nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    if !project_root.join("project.godot").is_file() {
+        eprintln!("{} isn't a Godot project", project_root.display());
+        eprintln!("Usage: validate_game_data [path/to/the/godot/project]");
+        process::exit(2);
+    }
+
+    let sections = data_validation::validate_game_data(&project_root);
+    for section in sections.iter() {
+        println!("{}: {} checked, {} problems, {} warnings",
+            section.name, section.checked, section.problems.len(), section.warnings.len());
+        section.problems.iter().for_each(|problem| println!("   {}", problem));
+        section.warnings.iter().for_each(|warning| println!("   Warning: {}", warning));
+    }
+
+    let total_problems: usize = sections.iter().map(|section| section.problems.len()).sum();
+    if total_problems > 0 {
+        eprintln!("The game data has {} problems", total_problems);
+        process::exit(1);
+    }
+    println!("The game data is valid");
+}
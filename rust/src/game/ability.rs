@@ -0,0 +1,113 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+/// The passive power of a Pokémon. Every specie can have a few of them, and each individual gets one when it's created.
+///
+/// The battle engine asks the ability what it does through the hooks of `BattleHooks`
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum Ability {
+    // Boosts the moves of his type while the Pokémon is in a pinch
+    Overgrow,
+    Blaze,
+    Torrent,
+    // Change how the Pokémon fights under some weather
+    Chlorophyll,
+    SwiftSwim,
+    SolarPower,
+    RainDish,
+    IceBody,
+    // Change the weather when the Pokémon enters the battlefield
+    Drizzle,
+    Drought,
+    // Modify the damage dealt or received
+    Guts,
+    ThickFat,
+    // Hurt the Pokémon that hit this one
+    Static,
+    PoisonPoint,
+    // Protect the Pokémon against the status conditions
+    ShieldDust,
+    ShedSkin,
+    Immunity,
+    Insomnia,
+    Limber,
+    // Work outside the damage formula
+    RunAway,
+}
+
+impl Ability {
+    // Returns a Vec<Ability> with all the Variants
+    pub fn values() -> Vec<Ability> {
+        vec![
+            Self::Overgrow, Self::Blaze, Self::Torrent,
+            Self::Chlorophyll, Self::SwiftSwim, Self::SolarPower, Self::RainDish, Self::IceBody,
+            Self::Drizzle, Self::Drought,
+            Self::Guts, Self::ThickFat,
+            Self::Static, Self::PoisonPoint,
+            Self::ShieldDust, Self::ShedSkin, Self::Immunity, Self::Insomnia, Self::Limber,
+            Self::RunAway,
+        ]
+    }
+
+    /// Picks one of the abilities of a specie, like the wild Pokémon do. `None` if the specie doesn't have any
+    pub fn random_from<R: Rng>(abilities: &[Ability], rng: &mut R) -> Option<Ability> {
+        if abilities.is_empty() {
+            return None;
+        }
+        Some(abilities[rng.gen_range(0..abilities.len())])
+    }
+
+    // Returns the ability's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Overgrow => "Espesura",
+            Self::Blaze => "Mar Llamas",
+            Self::Torrent => "Torrente",
+            Self::Chlorophyll => "Clorofila",
+            Self::SwiftSwim => "Nado Rápido",
+            Self::SolarPower => "Poder Solar",
+            Self::RainDish => "Cura Lluvia",
+            Self::IceBody => "Gélido",
+            Self::Drizzle => "Llovizna",
+            Self::Drought => "Sequía",
+            Self::Guts => "Agallas",
+            Self::ThickFat => "Sebo",
+            Self::Static => "Elec. Estática",
+            Self::PoisonPoint => "Punto Tóxico",
+            Self::ShieldDust => "Polvo Escudo",
+            Self::ShedSkin => "Mudar",
+            Self::Immunity => "Inmunidad",
+            Self::Insomnia => "Insomnio",
+            Self::Limber => "Flexibilidad",
+            Self::RunAway => "Fuga",
+        }
+    }
+
+    /// What the ability does, as the summary screens shows it
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Overgrow => "Potencia los movimientos de tipo Planta en un apuro.",
+            Self::Blaze => "Potencia los movimientos de tipo Fuego en un apuro.",
+            Self::Torrent => "Potencia los movimientos de tipo Agua en un apuro.",
+            Self::Chlorophyll => "Sube la Velocidad cuando hace sol.",
+            Self::SwiftSwim => "Sube la Velocidad cuando llueve.",
+            Self::SolarPower => "Potencia el Ataque Especial cuando hace sol, pero resta PS cada turno.",
+            Self::RainDish => "Recupera PS poco a poco cuando llueve.",
+            Self::IceBody => "Recupera PS poco a poco cuando graniza, y el granizo no le daña.",
+            Self::Drizzle => "Hace que llueva al entrar en combate.",
+            Self::Drought => "Hace que salga el sol al entrar en combate.",
+            Self::Guts => "Sube el Ataque si sufre un problema de estado.",
+            Self::ThickFat => "Reduce el daño de los movimientos de tipo Fuego y Hielo.",
+            Self::Static => "Puede paralizar al atacante si le golpea con un movimiento físico.",
+            Self::PoisonPoint => "Puede envenenar al atacante si le golpea con un movimiento físico.",
+            Self::ShieldDust => "Bloquea los efectos secundarios de los movimientos que recibe.",
+            Self::ShedSkin => "Puede curar sus problemas de estado al final de cada turno.",
+            Self::Immunity => "Impide que sea envenenado.",
+            Self::Insomnia => "Impide que se quede dormido.",
+            Self::Limber => "Impide que sea paralizado.",
+            Self::RunAway => "Permite huir siempre de los Pokémon salvajes.",
+        }
+    }
+}
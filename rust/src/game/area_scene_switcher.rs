@@ -60,12 +60,7 @@ impl AreaSceneSwitcher {
     }
 
     fn set_path_to_scene_to_switch(&mut self) {
-        if self.parent_name == "Exit" {
-            self.scene_to_switch = "res://godot/Game/Map.tscn".to_string();
-        } else {
-            self.scene_to_switch = "res://godot/Game/WorldElements/".to_string() + 
-                &self.owner_node + &"/Scenes".to_string() + &"/Interior".to_string() + &self.parent_name + &".tscn".to_string();
-        } 
+        self.scene_to_switch = scene_to_switch_path(&self.owner_node, &self.parent_name);
     }
 
     #[method]
@@ -81,4 +76,16 @@ impl AreaSceneSwitcher {
         base.connect("scene_change", game, "change_world_scene",
             VariantArray::new_shared(), 0).unwrap();
     }
-}
\ No newline at end of file
+}
+
+/// The scene that a switcher loads, given the name of the root node of the scene where it's placed and the name of his parent.
+///
+/// The switchers placed under an "Exit" node go back to the `Map`, and the rest go into the interior named after his parent
+pub fn scene_to_switch_path(owner_node: &str, parent_name: &str) -> String {
+    if parent_name == "Exit" {
+        "res://godot/Game/Map.tscn".to_string()
+    } else {
+        "res://godot/Game/WorldElements/".to_string() +
+            owner_node + "/Scenes" + "/Interior" + parent_name + ".tscn"
+    }
+}
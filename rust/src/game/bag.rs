@@ -0,0 +1,114 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::game_item::{BagPocket, GameItem};
+use super::pokemon::Pokemon;
+
+use crate::utils::all_game_items::game_item_by_name;
+
+/// The most units of the same item that the bag can hold
+pub const MAX_ITEM_QUANTITY: i32 = 999;
+
+/// An item stored on the bag, with how many units of it the player has
+#[derive(PartialEq, Clone, Debug, Default, ToVariant, Serialize, Deserialize)]
+pub struct BagSlot {
+    pub item_name: String,
+    pub quantity: i32,
+}
+
+/// The inventory of the player. It's saved alongside the rest of the `PlayerData`
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Bag {
+    slots: Vec<BagSlot>,
+}
+
+impl Bag {
+    pub fn new() -> Self {
+        Self { slots: Vec::new() }
+    }
+
+    /// How many units of the given item are on the bag
+    pub fn quantity_of(&self, item_name: &str) -> i32 {
+        self.slots.iter()
+            .find(|slot| slot.item_name == item_name)
+            .map_or(0, |slot| slot.quantity)
+    }
+
+    pub fn has(&self, item_name: &str) -> bool {
+        self.quantity_of(item_name) > 0
+    }
+
+    /// Stores the units of the item, never going above `MAX_ITEM_QUANTITY`
+    pub fn add(&mut self, item_name: &str, quantity: i32) -> Result<(), String> {
+        if game_item_by_name(item_name).is_none() {
+            return Err(format!("{} isn't an item of the game", item_name));
+        }
+        if quantity <= 0 {
+            return Err(format!("Can't add {} units of {}", quantity, item_name));
+        }
+        if self.quantity_of(item_name) + quantity > MAX_ITEM_QUANTITY {
+            return Err(format!("There's no room left for more {}", item_name));
+        }
+
+        match self.slots.iter_mut().find(|slot| slot.item_name == item_name) {
+            Some(slot) => slot.quantity += quantity,
+            None => self.slots.push(BagSlot { item_name: item_name.to_string(), quantity })
+        }
+        Ok(())
+    }
+
+    /// Takes out units of the item. The item leaves the bag when there's none left
+    pub fn remove(&mut self, item_name: &str, quantity: i32) -> Result<(), String> {
+        if quantity <= 0 {
+            return Err(format!("Can't remove {} units of {}", quantity, item_name));
+        }
+        let index = match self.slots.iter().position(|slot| slot.item_name == item_name) {
+            Some(index) => index,
+            None => return Err(format!("There's no {} on the bag", item_name))
+        };
+        if self.slots[index].quantity < quantity {
+            return Err(format!("There's not enough {} on the bag", item_name));
+        }
+
+        self.slots[index].quantity -= quantity;
+        if self.slots[index].quantity == 0 {
+            self.slots.remove(index);
+        }
+        Ok(())
+    }
+
+    /// Uses one unit of the item over the given Pokémon, spending it if the item it's consumable
+    pub fn use_item(&mut self, item_name: &str, pokemon: &mut Pokemon) -> Result<(), String> {
+        if !self.has(item_name) {
+            return Err(format!("There's no {} on the bag", item_name));
+        }
+        let game_item = match game_item_by_name(item_name) {
+            Some(game_item) => game_item,
+            None => return Err(format!("{} isn't an item of the game", item_name))
+        };
+
+        game_item.apply_to(pokemon)?;
+        if game_item.is_consumable() {
+            self.remove(item_name, 1)?;
+        }
+        Ok(())
+    }
+
+    /// The items stored on the given pocket, in the order that they were obtained
+    pub fn pocket(&self, pocket: BagPocket) -> Vec<BagSlot> {
+        self.slots.iter()
+            .filter(|slot| game_item_by_name(&slot.item_name)
+                .map_or(false, |game_item: GameItem| game_item.pocket == pocket))
+            .cloned()
+            .collect()
+    }
+
+    /// The items that can be given to a Pokémon to hold, in the order that they were obtained
+    pub fn holdable_items(&self) -> Vec<BagSlot> {
+        self.slots.iter()
+            .filter(|slot| game_item_by_name(&slot.item_name)
+                .map_or(false, |game_item: GameItem| game_item.can_be_held()))
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,358 @@
+use std::fmt::Debug;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::pokemon_move::{MoveCategory, MoveEffect};
+use crate::game::pokemon_type::PokemonType;
+use crate::game::status_condition::{MajorStatus, VolatileStatus};
+
+use super::battle_engine::{self, BattleAction, BattleEngine};
+use super::battle_hooks;
+use super::battle_weather::BattleWeather;
+use super::battler::{BattleMove, Battler, BattlerPosition};
+
+/// The average of the random factor of the damage formula, that goes from 0.85 to 1.0
+const AVERAGE_RANDOM_FACTOR: f64 = 0.925;
+/// How much better, as a fraction of the HP, the matchup of another Pokémon must be for the smart AI to switch to it
+const SWITCH_MATCHUP_MARGIN: f64 = 0.5;
+/// The smart AI only spends a turn on a status move while the target has more than this fraction of his HP
+const STATUS_MOVE_MIN_TARGET_HP: f64 = 0.5;
+
+/// The decision layer of the opponent of a battle. Chooses what every Pokémon of the side does on every turn, and
+/// which Pokémon it sends out when one of them faints.
+///
+/// Every implementation owns his own seeded random generator, so the same seed and the same battle always
+/// lead to the same decisions
+pub trait BattleAi: Debug {
+    /// Picks the action of the Pokémon at the position for the next turn. It must be a valid action for the current
+    /// battle state
+    fn choose_action(&mut self, engine: &BattleEngine, position: BattlerPosition) -> BattleAction;
+
+    /// Picks the Pokémon that replaces the fainted one at the position. By default, the first one that can still fight
+    fn choose_replacement(&mut self, engine: &BattleEngine, position: BattlerPosition) -> Option<usize> {
+        engine.side(position.side).next_healthy_index()
+    }
+}
+
+/// The strategies that the opponents can use, as they are set on the trainers' data
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum AiDifficulty {
+    /// Uses any move with PP left. The wild Pokémon always fight like this
+    Random,
+    /// Always uses the move that deals more damage
+    Greedy,
+    /// Also uses status moves and switches out of bad matchups
+    Smart,
+}
+
+impl Default for AiDifficulty {
+    fn default() -> Self { AiDifficulty::Random }
+}
+
+impl AiDifficulty {
+    pub fn values() -> Vec<AiDifficulty> {
+        vec![AiDifficulty::Random, AiDifficulty::Greedy, AiDifficulty::Smart]
+    }
+
+    /// Creates the AI of this difficulty, whose decisions only depends on the given seed
+    pub fn build(&self, seed: u64) -> Box<dyn BattleAi> {
+        match self {
+            AiDifficulty::Random => Box::new(RandomAi::new(seed)),
+            AiDifficulty::Greedy => Box::new(GreedyAi::new(seed)),
+            AiDifficulty::Smart => Box::new(SmartAi::new(seed))
+        }
+    }
+
+    // Returns the difficulty's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            AiDifficulty::Random => "Aleatoria",
+            AiDifficulty::Greedy => "Agresiva",
+            AiDifficulty::Smart => "Estratega"
+        }
+    }
+}
+
+/// Picks a random move, with PP left, of the active Pokémon, and a random foe to use it on
+#[derive(Debug)]
+pub struct RandomAi {
+    rng: ChaCha8Rng,
+}
+
+impl RandomAi {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: ChaCha8Rng::seed_from_u64(seed) }
+    }
+}
+
+impl BattleAi for RandomAi {
+    fn choose_action(&mut self, engine: &BattleEngine, position: BattlerPosition) -> BattleAction {
+        let foes = standing_foes(engine, position);
+        let target = if foes.len() > 1 { Some(foes[self.rng.gen_range(0..foes.len())]) } else { None };
+        let usable_moves = usable_moves(engine.battler(position));
+        // Without PP left, any index makes the Pokémon struggle
+        if usable_moves.is_empty() {
+            return BattleAction::UseMove { move_index: 0, target };
+        }
+        BattleAction::UseMove { move_index: usable_moves[self.rng.gen_range(0..usable_moves.len())], target }
+    }
+}
+
+/// Always uses the move with the highest expected damage, against the foe that it hurts the most, using the type chart.
+/// Ties are decided randomly
+#[derive(Debug)]
+pub struct GreedyAi {
+    rng: ChaCha8Rng,
+}
+
+impl GreedyAi {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: ChaCha8Rng::seed_from_u64(seed) }
+    }
+}
+
+impl BattleAi for GreedyAi {
+    fn choose_action(&mut self, engine: &BattleEngine, position: BattlerPosition) -> BattleAction {
+        let (move_index, target) = strongest_attack(engine, position, &mut self.rng);
+        BattleAction::UseMove { move_index, target }
+    }
+}
+
+/// Finishes the target when it can, switches out of the matchups where the active Pokémon it's clearly worse than
+/// another one of the team, and uses status moves on healthy targets that don't have any status condition yet.
+/// Otherwise, it fights like the `GreedyAi`
+#[derive(Debug)]
+pub struct SmartAi {
+    rng: ChaCha8Rng,
+    // The turn when the AI switched for the last time, so it doesn't switch on every turn
+    last_switch_turn: Option<i32>,
+}
+
+impl SmartAi {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: ChaCha8Rng::seed_from_u64(seed), last_switch_turn: None }
+    }
+
+    /// The Pokémon of the team that would do better than the active one against the target, if any
+    fn better_switch(&self, engine: &BattleEngine, position: BattlerPosition) -> Option<usize> {
+        if self.last_switch_turn.map_or(false, |turn| turn + 1 >= engine.turn()) {
+            return None;
+        }
+        let battle_side = engine.side(position.side);
+        let defender = engine.battler(engine.foe_in_front(position)?);
+        let active_matchup = matchup(engine.battler(position), defender, engine.weather());
+
+        best_matchup(engine, position)
+            .filter(|(party_index, bench_matchup)| battle_side.can_switch_to(*party_index) &&
+                *bench_matchup > active_matchup + SWITCH_MATCHUP_MARGIN)
+            .map(|(party_index, _)| party_index)
+    }
+
+    /// A status move, with PP left, that would inflict a new condition to the target
+    fn useful_status_move(&self, attacker: &Battler, defender: &Battler) -> Option<usize> {
+        if hp_fraction(defender) <= STATUS_MOVE_MIN_TARGET_HP {
+            return None;
+        }
+        usable_moves(attacker).into_iter()
+            .filter(|move_index| attacker.moves[*move_index].category == MoveCategory::Status)
+            .find(|move_index| would_inflict_status(&attacker.moves[*move_index], defender))
+    }
+}
+
+impl BattleAi for SmartAi {
+    fn choose_action(&mut self, engine: &BattleEngine, position: BattlerPosition) -> BattleAction {
+        let attacker = engine.battler(position);
+
+        // 1º -> A move that knocks out one of the foes right now
+        let finishing_move = standing_foes(engine, position).into_iter()
+            .flat_map(|target| usable_moves(attacker).into_iter().map(move |move_index| (move_index, target)))
+            .filter(|(move_index, target)| {
+                let defender = engine.battler(*target);
+                expected_damage(attacker, defender, &attacker.moves[*move_index], engine.weather()) >= defender.current_hp as f64
+            })
+            .max_by_key(|(move_index, _)| (attacker.moves[*move_index].priority, attacker.moves[*move_index].accuracy.unwrap_or(101)));
+        if let Some((move_index, target)) = finishing_move {
+            return BattleAction::UseMove { move_index, target: Some(target) };
+        }
+
+        // 2º -> Leaves the battlefield when another Pokémon of the team has a much better matchup
+        if let Some(party_index) = self.better_switch(engine, position) {
+            self.last_switch_turn = Some(engine.turn() + 1);
+            return BattleAction::Switch { party_index };
+        }
+
+        // 3º -> Weakens a healthy foe with a status condition
+        if let Some(target) = engine.foe_in_front(position) {
+            if let Some(move_index) = self.useful_status_move(attacker, engine.battler(target)) {
+                return BattleAction::UseMove { move_index, target: Some(target) };
+            }
+        }
+
+        let (move_index, target) = strongest_attack(engine, position, &mut self.rng);
+        BattleAction::UseMove { move_index, target }
+    }
+
+    fn choose_replacement(&mut self, engine: &BattleEngine, position: BattlerPosition) -> Option<usize> {
+        best_matchup(engine, position).map(|(party_index, _)| party_index)
+    }
+}
+
+/// The indexes of the moves of the Pokémon that still have PP left
+fn usable_moves(battler: &Battler) -> Vec<usize> {
+    battler.moves.iter()
+        .enumerate()
+        .filter(|(_, battle_move)| battle_move.current_pp > 0)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// The damage that the move would deal on average under the given weather, without critical hits, weighted by his accuracy
+pub fn expected_damage(attacker: &Battler, defender: &Battler, battle_move: &BattleMove, weather: BattleWeather) -> f64 {
+    if battle_move.category == MoveCategory::Status {
+        return 0.0;
+    }
+    let effectiveness = defender.effectiveness_of(battle_move.move_type);
+    if effectiveness == 0.0 {
+        return 0.0;
+    }
+
+    let damage = match battle_move.effect {
+        Some(MoveEffect::FixedDamage { damage }) => damage as f64,
+        _ => {
+            let mut modifier = AVERAGE_RANDOM_FACTOR * effectiveness * weather.type_multiplier(battle_move.move_type) *
+                battle_hooks::damage_multiplier(attacker, defender, battle_move, weather);
+            if attacker.has_stab(battle_move) {
+                modifier *= battle_engine::STAB_MULTIPLIER;
+            }
+            battle_engine::base_damage(attacker, defender, battle_move) as f64 * modifier
+        }
+    };
+    damage * battle_move.accuracy.map_or(1.0, |accuracy| accuracy as f64 / 100.0)
+}
+
+/// The foes of the Pokémon at the position that are still able to fight
+fn standing_foes(engine: &BattleEngine, position: BattlerPosition) -> Vec<BattlerPosition> {
+    engine.positions_of(position.side.opposite()).into_iter()
+        .filter(|foe| engine.is_standing(*foe))
+        .collect()
+}
+
+/// The usable move with the highest expected damage, and the foe that it hurts the most. Ties are decided randomly
+fn strongest_attack<R: Rng>(engine: &BattleEngine, position: BattlerPosition, rng: &mut R) -> (usize, Option<BattlerPosition>) {
+    let attacker = engine.battler(position);
+    let best_against = |target: &BattlerPosition| {
+        let defender = engine.battler(*target);
+        usable_moves(attacker).iter()
+            .map(|move_index| expected_damage(attacker, defender, &attacker.moves[*move_index], engine.weather()))
+            .fold(0.0, f64::max)
+    };
+    let foes = standing_foes(engine, position);
+    let best_damage = foes.iter().map(best_against).fold(0.0, f64::max);
+    let best_foes: Vec<BattlerPosition> = foes.iter()
+        .filter(|target| best_against(target) >= best_damage)
+        .copied()
+        .collect();
+
+    let target = match best_foes.len() {
+        0 => return (0, None),
+        1 => best_foes[0],
+        _ => best_foes[rng.gen_range(0..best_foes.len())]
+    };
+    (strongest_move(attacker, engine.battler(target), engine.weather(), rng), Some(target))
+}
+
+/// The usable move with the highest expected damage. Ties are decided randomly, and without PP left the
+/// Pokémon struggles
+fn strongest_move<R: Rng>(attacker: &Battler, defender: &Battler, weather: BattleWeather, rng: &mut R) -> usize {
+    let usable_moves = usable_moves(attacker);
+    let best_damage = usable_moves.iter()
+        .map(|move_index| expected_damage(attacker, defender, &attacker.moves[*move_index], weather))
+        .fold(0.0, f64::max);
+    let best_moves: Vec<usize> = usable_moves.into_iter()
+        .filter(|move_index| expected_damage(attacker, defender, &attacker.moves[*move_index], weather) >= best_damage)
+        .collect();
+
+    if best_moves.is_empty() {
+        return 0;
+    }
+    best_moves[rng.gen_range(0..best_moves.len())]
+}
+
+/// How good is the Pokémon against the target: the fraction of the target's HP that it takes with his best move,
+/// minus the fraction of his own HP that the target takes back
+fn matchup(battler: &Battler, target: &Battler, weather: BattleWeather) -> f64 {
+    let best_damage = |attacker: &Battler, defender: &Battler| attacker.moves.iter()
+        .filter(|battle_move| battle_move.current_pp > 0)
+        .map(|battle_move| expected_damage(attacker, defender, battle_move, weather))
+        .fold(0.0, f64::max);
+
+    best_damage(battler, target) / target.current_hp.max(1) as f64 -
+        best_damage(target, battler) / battler.current_hp.max(1) as f64
+}
+
+/// The healthy Pokémon of the team, out of the battlefield, with the best matchup against the foe in front of the position
+fn best_matchup(engine: &BattleEngine, position: BattlerPosition) -> Option<(usize, f64)> {
+    let battle_side = engine.side(position.side);
+    let target = engine.battler(engine.foe_in_front(position)?);
+    battle_side.battlers.iter()
+        .enumerate()
+        .filter(|(party_index, battler)| !battle_side.is_active(*party_index) && !battler.is_fainted())
+        .map(|(party_index, battler)| (party_index, matchup(battler, target, engine.weather())))
+        .fold(None, |best: Option<(usize, f64)>, candidate| match best {
+            Some(best) if best.1 >= candidate.1 => Some(best),
+            _ => Some(candidate)
+        })
+}
+
+fn hp_fraction(battler: &Battler) -> f64 {
+    battler.current_hp as f64 / battler.stats.max_hp.max(1) as f64
+}
+
+/// True when the status move would give a new condition to the target, that it isn't immune to
+fn would_inflict_status(battle_move: &BattleMove, target: &Battler) -> bool {
+    let major_status = match battle_move.effect {
+        Some(MoveEffect::Burn { .. }) => MajorStatus::Burn,
+        Some(MoveEffect::Poison { .. }) => MajorStatus::Poison,
+        Some(MoveEffect::BadPoison { .. }) => MajorStatus::BadPoison,
+        Some(MoveEffect::Paralysis { .. }) => MajorStatus::Paralysis,
+        Some(MoveEffect::Sleep { .. }) => MajorStatus::Sleep { turns_left: 0 },
+        Some(MoveEffect::Freeze { .. }) => MajorStatus::Freeze,
+        Some(MoveEffect::Confusion { .. }) => return !target.has_volatile(&VolatileStatus::Confusion { turns_left: 0 }),
+        Some(MoveEffect::LeechSeed) => {
+            let grass_type = target.pokemon.type1() == PokemonType::Grass || target.pokemon.type2() == Some(PokemonType::Grass);
+            return !grass_type && !target.has_volatile(&VolatileStatus::LeechSeed);
+        },
+        _ => return false
+    };
+    target.pokemon.status.is_none() && !major_status.is_immune(target.pokemon.type1(), target.pokemon.type2())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::battle_event::{BattleFormat, BattleKind};
+    use super::super::battler::BattleSide;
+    use super::super::test_support::{play_with_ais, team};
+
+    /// Plays a double battle where both sides are driven by AIs of the given difficulty, and returns every
+    /// decision that they took, on the order they took them
+    fn decisions(difficulty: AiDifficulty, seed: u64) -> Vec<(BattlerPosition, BattleAction)> {
+        let player = BattleSide::new("Player", team(&[(4, 15), (16, 14), (25, 13)]));
+        let trainer = BattleSide::new("Trainer", team(&[(1, 15), (7, 14), (19, 13)]));
+        let mut engine = BattleEngine::with_seed(BattleKind::Trainer, player, trainer, 99).unwrap()
+            .with_format(BattleFormat::Double);
+        engine.start();
+        play_with_ais(&mut engine, difficulty.build(seed), difficulty.build(seed + 1), 30)
+    }
+
+    #[test]
+    fn the_same_seed_leads_to_the_same_decisions() {
+        for difficulty in AiDifficulty::values() {
+            let first_run = decisions(difficulty, 31);
+            assert!(!first_run.is_empty());
+            assert_eq!(first_run, decisions(difficulty, 31), "The {:?} AI isn't deterministic", difficulty);
+        }
+    }
+}
@@ -0,0 +1,1253 @@
+use std::cmp::Reverse;
+
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::capture;
+use crate::game::experience;
+use crate::game::pokemon_move::{MoveCategory, MoveEffect, MoveTarget};
+use crate::game::pokemon_type::PokemonType;
+use crate::game::status_condition::{self, MajorStatus, StatusCondition, VolatileStatus};
+use crate::game::species_registry::pokemon_specie_by_id;
+
+use super::battle_event::{BattleEvent, BattleFormat, BattleKind, BattleOutcome};
+use super::battle_hooks::{self, BattleHooks, HookEffect, SwitchInEffect};
+use super::battle_log::{BattleLog, BattleLogEntry};
+use super::battle_weather::BattleWeather;
+use super::battler::{BattleMove, BattleSide, BattleSideId, Battler, BattlerPosition};
+
+/// The chance, expressed as 1 / N, of landing a critical hit
+const CRITICAL_HIT_RATIO: u32 = 16;
+/// The chance, expressed as 1 / N, of landing a critical hit with the moves that have a high ratio
+const HIGH_CRITICAL_HIT_RATIO: u32 = 8;
+/// The damage multiplier of the moves that shares a type with the Pokémon that uses them
+pub const STAB_MULTIPLIER: f64 = 1.5;
+/// The damage multiplier of the moves that hit more than one Pokémon at the same time
+pub const SPREAD_MULTIPLIER: f64 = 0.75;
+
+/// What a Pokémon on the battlefield has decided to do on the current turn
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum BattleAction {
+    /// `target` it's the Pokémon chosen for the moves that hit a single one. With `None`, or when the chosen one
+    /// already fainted, the move hits the foe in front
+    UseMove { move_index: usize, target: Option<BattlerPosition> },
+    Switch { party_index: usize },
+    /// Throws a Poké Ball to the wild Pokémon. The ball it's taken out of the bag by the caller once the turn is played
+    ThrowBall { ball_name: String, multiplier: f64 },
+    Flee
+}
+
+/// A pure Rust, turn based Pokémon battle simulator.
+///
+/// The engine doesn't know anything about Godot. It receives the actions chosen for every Pokémon on the battlefield,
+/// resolves the whole turn and returns a `Vec<BattleEvent>` describing what happened, so the battle scene only has to
+/// animate them, and whole battles can be simulated headless.
+///
+/// The same engine plays the single and the double battles. The `BattleFormat` only sets how many slots of the
+/// battlefield every side fills.
+///
+/// Every random decision comes from a RNG seeded with a known value, and the engine writes down everything that
+/// happens on a `BattleLog`, so any battle can be replayed later with exactly the same outcome
+#[derive(Clone, Debug)]
+pub struct BattleEngine {
+    kind: BattleKind,
+    format: BattleFormat,
+    player: BattleSide,
+    opponent: BattleSide,
+    turn: i32,
+    flee_attempts: i32,
+    outcome: BattleOutcome,
+    weather: BattleWeather,
+    seed: u64,
+    // Unlike `StdRng`, ChaCha8 gives the same numbers for a seed on every platform and version of `rand`, so the logs
+    // of the old battles can still be replayed
+    rng: ChaCha8Rng,
+    log: Option<BattleLog>,
+}
+
+impl BattleEngine {
+    /// Creates a new battle with a random seed. The seed it's still stored on the `BattleLog`, so the battle can be replayed.
+    ///
+    /// Returns an error when any of the sides doesn't have a single Pokémon
+    pub fn new(kind: BattleKind, player: BattleSide, opponent: BattleSide) -> Result<Self, String> {
+        Self::with_seed(kind, player, opponent, rand::random())
+    }
+
+    /// Creates a new battle where every random decision depends only on the provided seed.
+    ///
+    /// Returns an error when any of the sides doesn't have a single Pokémon
+    pub fn with_seed(kind: BattleKind, player: BattleSide, opponent: BattleSide, seed: u64) -> Result<Self, String> {
+        if let Some(empty_side) = [&player, &opponent].into_iter().find(|battle_side| battle_side.battlers.is_empty()) {
+            return Err(format!("{} doesn't have any Pokémon to battle with", empty_side.name));
+        }
+        Ok(Self {
+            kind,
+            format: BattleFormat::Single,
+            player,
+            opponent,
+            turn: 0,
+            flee_attempts: 0,
+            outcome: BattleOutcome::Ongoing,
+            weather: BattleWeather::Clear,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            log: None,
+        })
+    }
+
+    /// Sets the weather of the battlefield, that lasts for the whole battle. Must be called before `start()`
+    pub fn with_weather(mut self, weather: BattleWeather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    /// Sets how many Pokémon of every side fight at the same time, sending out the first healthy ones of every team.
+    /// Must be called before `start()`
+    pub fn with_format(mut self, format: BattleFormat) -> Self {
+        self.format = format;
+        self.player.send_out_first(format.active_slots());
+        self.opponent.send_out_first(format.active_slots());
+        self
+    }
+
+    // Getters
+    pub fn kind(&self) -> BattleKind {
+        self.kind
+    }
+
+    pub fn format(&self) -> BattleFormat {
+        self.format
+    }
+
+    pub fn turn(&self) -> i32 {
+        self.turn
+    }
+
+    pub fn outcome(&self) -> BattleOutcome {
+        self.outcome
+    }
+
+    pub fn weather(&self) -> BattleWeather {
+        self.weather
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Everything that happened on the battle, from the teams that started it. `None` until the battle it's started
+    pub fn log(&self) -> Option<&BattleLog> {
+        self.log.as_ref()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome != BattleOutcome::Ongoing
+    }
+
+    pub fn side(&self, side: BattleSideId) -> &BattleSide {
+        match side {
+            BattleSideId::Player => &self.player,
+            BattleSideId::Opponent => &self.opponent
+        }
+    }
+
+    fn side_mut(&mut self, side: BattleSideId) -> &mut BattleSide {
+        match side {
+            BattleSideId::Player => &mut self.player,
+            BattleSideId::Opponent => &mut self.opponent
+        }
+    }
+
+    /// The Pokémon that it's on the given position of the battlefield
+    pub fn battler(&self, position: BattlerPosition) -> &Battler {
+        self.side(position.side).active_battler_at(position.slot)
+    }
+
+    fn battler_mut(&mut self, position: BattlerPosition) -> &mut Battler {
+        self.side_mut(position.side).active_battler_at_mut(position.slot)
+    }
+
+    /// Every slot of the battlefield that the side fills, even when his Pokémon fainted
+    pub fn positions_of(&self, side: BattleSideId) -> Vec<BattlerPosition> {
+        (0..self.side(side).slots())
+            .map(|slot| BattlerPosition::new(side, slot))
+            .collect()
+    }
+
+    /// Every slot of the battlefield, first the player's ones and then the opponent's ones
+    pub fn positions(&self) -> Vec<BattlerPosition> {
+        let mut positions = self.positions_of(BattleSideId::Player);
+        positions.append(&mut self.positions_of(BattleSideId::Opponent));
+        positions
+    }
+
+    /// True when there's a Pokémon able to fight on the given position
+    pub fn is_standing(&self, position: BattlerPosition) -> bool {
+        position.slot < self.side(position.side).slots() && !self.battler(position).is_fainted()
+    }
+
+    /// The positions of the Pokémon that are still able to fight, on the order of `positions()`
+    pub fn standing_positions(&self) -> Vec<BattlerPosition> {
+        self.positions().into_iter()
+            .filter(|position| self.is_standing(*position))
+            .collect()
+    }
+
+    /// The foe that it's in front of the Pokémon, or the other foe when that one already fainted
+    pub fn foe_in_front(&self, position: BattlerPosition) -> Option<BattlerPosition> {
+        let facing = position.facing();
+        if self.is_standing(facing) {
+            return Some(facing);
+        }
+        self.positions_of(position.side.opposite()).into_iter()
+            .find(|foe| self.is_standing(*foe))
+    }
+
+    /// The wild Pokémon that a Poké Ball would catch: the only one still standing on the battlefield. Balls can't
+    /// be thrown while two wild Pokémon are able to fight
+    pub fn catchable_position(&self) -> Option<BattlerPosition> {
+        let wild_positions: Vec<BattlerPosition> = self.positions_of(BattleSideId::Opponent).into_iter()
+            .filter(|position| self.is_standing(*position))
+            .collect();
+        match wild_positions.as_slice() {
+            [position] => Some(*position),
+            _ => None
+        }
+    }
+
+    /// Returns the events that opens the battle, sending out the first Pokémon of every side
+    pub fn start(&mut self) -> Vec<BattleEvent> {
+        self.log = Some(BattleLog::new(self.seed, self.kind, self.format, self.weather, &self.player, &self.opponent));
+        let mut events = vec![BattleEvent::BattleStarted { kind: self.kind, format: self.format }];
+        if self.weather != BattleWeather::Clear {
+            events.push(BattleEvent::WeatherActive { weather: self.weather });
+        }
+        let mut positions = self.positions_of(BattleSideId::Opponent);
+        positions.append(&mut self.positions_of(BattleSideId::Player));
+        for position in positions.iter() {
+            self.announce_switch_in(*position, &mut events);
+        }
+        for position in positions {
+            self.apply_switch_in_hooks(position, &mut events);
+        }
+        self.record(BattleLogEntry::Start { events: events.clone() });
+        events
+    }
+
+    /// The slots of the side whose Pokémon fainted, while the side still has another one to send out
+    pub fn positions_to_replace(&self, side: BattleSideId) -> Vec<BattlerPosition> {
+        if self.is_over() || self.side(side).next_healthy_index().is_none() {
+            return Vec::new();
+        }
+        self.positions_of(side).into_iter()
+            .filter(|position| self.battler(*position).is_fainted())
+            .collect()
+    }
+
+    /// True when a Pokémon of the given side fainted and the side still have another one to send out.
+    ///
+    /// While this is true, no new turns can be played until `replace_fainted(..)` gets called for that side
+    pub fn needs_replacement(&self, side: BattleSideId) -> bool {
+        !self.positions_to_replace(side).is_empty()
+    }
+
+    /// Sends out a new Pokémon to the position of one that fainted
+    pub fn replace_fainted(&mut self, position: BattlerPosition, party_index: usize) -> Result<Vec<BattleEvent>, String> {
+        if !self.positions_to_replace(position.side).contains(&position) {
+            return Err(format!("The {:?} side doesn't need to replace a fainted Pokémon on the slot {}", position.side, position.slot));
+        }
+        if !self.side(position.side).can_switch_to(party_index) {
+            return Err(format!("The Pokémon at index {} can't be sent out", party_index));
+        }
+        self.switch_active(position, party_index);
+        let mut events = Vec::new();
+        self.announce_switch_in(position, &mut events);
+        self.apply_switch_in_hooks(position, &mut events);
+        self.record(BattleLogEntry::Replacement { position, party_index, events: events.clone() });
+        Ok(events)
+    }
+
+    /// Checks that an action can be performed by the Pokémon at the given position on the current battle state
+    pub fn validate_action(&self, position: BattlerPosition, action: &BattleAction) -> Result<(), String> {
+        if position.slot >= self.side(position.side).slots() {
+            return Err(format!("The {:?} side doesn't have a Pokémon on the slot {}", position.side, position.slot));
+        }
+        let battle_side = self.side(position.side);
+        match action {
+            BattleAction::UseMove { move_index, target } => {
+                let battler = self.battler(position);
+                if let Some(target) = target {
+                    if *target == position || target.slot >= self.side(target.side).slots() {
+                        return Err(format!("{} can't target the slot {} of the {:?} side", battler.name(), target.slot, target.side));
+                    }
+                }
+                // A Pokémon without PP left on any move will struggle, whatever move was chosen
+                if !battler.has_usable_moves() {
+                    return Ok(());
+                }
+                match battler.moves.get(*move_index) {
+                    Some(battle_move) if battle_move.current_pp <= 0 =>
+                        return Err(format!("{} doesn't have PP left", battle_move.name)),
+                    Some(_) => (),
+                    None => return Err(format!("{} doesn't know a move at index {}", battler.name(), move_index))
+                }
+            },
+            BattleAction::Switch { party_index } => {
+                if !battle_side.can_switch_to(*party_index) {
+                    return Err(format!("The Pokémon at index {} can't be sent out", party_index));
+                }
+            },
+            BattleAction::ThrowBall { .. } => {
+                if self.kind == BattleKind::Trainer || position.side == BattleSideId::Opponent {
+                    return Err("Poké Balls can only be thrown to wild Pokémon".to_string());
+                }
+                if self.catchable_position().is_none() {
+                    return Err("Poké Balls can't be thrown while two wild Pokémon are on the battlefield".to_string());
+                }
+            },
+            BattleAction::Flee => {
+                if self.kind == BattleKind::Trainer || position.side == BattleSideId::Opponent {
+                    return Err("There's no running from a trainer battle!".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves a complete turn of a single battle, with the action chosen by the player and the one chosen by the opponent
+    pub fn play_turn(&mut self, player_action: BattleAction, opponent_action: BattleAction) -> Result<Vec<BattleEvent>, String> {
+        self.play_turn_with(vec![
+            (BattlerPosition::new(BattleSideId::Player, 0), player_action),
+            (BattlerPosition::new(BattleSideId::Opponent, 0), opponent_action)
+        ])
+    }
+
+    /// Resolves a complete turn with the actions chosen for every Pokémon that can fight on the battlefield, one each.
+    ///
+    /// Fleeing and switching always happens before any move. Moves are ordered by their priority, then by the
+    /// speed of the Pokémon that uses them, and speed ties are decided randomly.
+    pub fn play_turn_with(&mut self, actions: Vec<(BattlerPosition, BattleAction)>) -> Result<Vec<BattleEvent>, String> {
+        if self.is_over() {
+            return Err("The battle is already over".to_string());
+        }
+        if self.needs_replacement(BattleSideId::Player) || self.needs_replacement(BattleSideId::Opponent) {
+            return Err("A fainted Pokémon must be replaced before playing the next turn".to_string());
+        }
+        for position in self.standing_positions() {
+            match actions.iter().filter(|(action_position, _)| *action_position == position).count() {
+                1 => (),
+                0 => return Err(format!("{} doesn't have an action for this turn", self.battler(position).name())),
+                _ => return Err(format!("{} can only do one action on every turn", self.battler(position).name()))
+            }
+        }
+        for (position, action) in actions.iter() {
+            if !self.is_standing(*position) {
+                return Err(format!("There's no Pokémon able to fight on the slot {} of the {:?} side", position.slot, position.side));
+            }
+            self.validate_action(*position, action)?;
+        }
+        // Two Pokémon of the same side can't be switched for the same one
+        let switches: Vec<(BattleSideId, usize)> = actions.iter()
+            .filter_map(|(position, action)| match action {
+                BattleAction::Switch { party_index } => Some((position.side, *party_index)),
+                _ => None
+            })
+            .collect();
+        if switches.iter().enumerate().any(|(idx, switch)| switches[..idx].contains(switch)) {
+            return Err("Two Pokémon can't be switched for the same one".to_string());
+        }
+
+        self.turn += 1;
+        let mut events = vec![BattleEvent::TurnStarted { turn: self.turn }];
+        let logged_actions = actions.clone();
+
+        for (position, action) in self.order_actions(actions) {
+            // A Pokémon that fainted earlier in this turn loses its action
+            if self.is_over() || !self.is_standing(position) {
+                continue;
+            }
+            match action {
+                BattleAction::Flee => self.try_to_flee(position, &mut events),
+                BattleAction::ThrowBall { ball_name, multiplier } => self.throw_ball(&ball_name, multiplier, &mut events),
+                BattleAction::Switch { party_index } => {
+                    self.switch_active(position, party_index);
+                    self.announce_switch_in(position, &mut events);
+                    self.apply_switch_in_hooks(position, &mut events);
+                },
+                BattleAction::UseMove { move_index, target } => self.use_move(position, move_index, target, &mut events)
+            }
+        }
+
+        if !self.is_over() {
+            self.end_of_turn(&mut events);
+        }
+
+        self.record(BattleLogEntry::Turn { turn: self.turn, actions: logged_actions, events: events.clone() });
+        Ok(events)
+    }
+
+    /// Writes down on the log something that happened on the battle
+    fn record(&mut self, entry: BattleLogEntry) {
+        if let Some(log) = self.log.as_mut() {
+            log.entries.push(entry);
+        }
+    }
+
+    /// Sends out another Pokémon. The one that leaves the battlefield loses his volatile status conditions
+    fn switch_active(&mut self, position: BattlerPosition, party_index: usize) {
+        let battle_side = self.side_mut(position.side);
+        battle_side.active_battler_at_mut(position.slot).clear_volatile_statuses();
+        battle_side.active[position.slot] = party_index;
+    }
+
+    /// Applies the damage of the weather and of the status conditions to the Pokémon that are still on the battlefield,
+    /// and then the end of turn hooks
+    fn end_of_turn(&mut self, events: &mut Vec<BattleEvent>) {
+        for position in self.positions() {
+            // Flinching only lasts for the current turn
+            self.battler_mut(position).remove_volatile(&VolatileStatus::Flinch);
+
+            if self.is_over() || self.battler(position).is_fainted() {
+                continue;
+            }
+
+            // Hail
+            let battler = self.battler(position);
+            let weather_immune = battler.hooks().iter().any(|hooks| hooks.ignores_weather_damage(self.weather));
+            let damage = self.weather.end_of_turn_damage(battler.pokemon.type1(), battler.pokemon.type2(), battler.stats.max_hp);
+            if damage > 0 && !weather_immune {
+                events.push(BattleEvent::HurtByWeather { position, weather: self.weather });
+                self.hurt(position, damage, events);
+                if self.is_over() || self.battler(position).is_fainted() {
+                    continue;
+                }
+            }
+
+            // Poison and burn
+            let battler = self.battler_mut(position);
+            if let Some(status) = battler.pokemon.status {
+                if status == MajorStatus::BadPoison {
+                    battler.bad_poison_turns += 1;
+                }
+                let damage = status_condition::end_of_turn_damage(&status, battler.stats.max_hp, battler.bad_poison_turns);
+                if damage > 0 {
+                    events.push(BattleEvent::HurtByStatus { position, condition: StatusCondition::Major(status) });
+                    self.hurt(position, damage, events);
+                }
+            }
+
+            // Leech Seed. The HP goes to the foe in front
+            let battler = self.battler(position);
+            if !battler.is_fainted() && battler.has_volatile(&VolatileStatus::LeechSeed) {
+                let damage = (battler.stats.max_hp / 8).max(1);
+                events.push(BattleEvent::HurtByStatus { position, condition: StatusCondition::Volatile(VolatileStatus::LeechSeed) });
+                let hp_lost = self.hurt(position, damage, events);
+                if let Some(seeder) = self.foe_in_front(position) {
+                    self.heal(seeder, hp_lost, events);
+                }
+            }
+
+            self.apply_end_of_turn_hooks(position, events);
+        }
+    }
+
+    /// Lets the hooks of the Pokémon that just entered the battlefield act, like the abilities that change the weather
+    fn apply_switch_in_hooks(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position);
+        let effects: Vec<(BattleEvent, SwitchInEffect)> = battler.hooks().iter()
+            .filter_map(|hooks| hooks.on_switch_in(battler, self.weather)
+                .map(|effect| (hooks.activated_event(position, battler.name()), effect)))
+            .collect();
+
+        for (activated_event, effect) in effects {
+            events.push(activated_event);
+            match effect {
+                SwitchInEffect::SetWeather { weather } => {
+                    self.weather = weather;
+                    events.push(BattleEvent::WeatherActive { weather });
+                }
+            }
+        }
+    }
+
+    /// Lets the hooks of the Pokémon at the position act at the end of the turn, like healing or curing his status
+    fn apply_end_of_turn_hooks(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position).clone();
+        for hooks in battler.hooks() {
+            if self.is_over() || self.battler(position).is_fainted() {
+                return;
+            }
+            // The previous hooks could have changed the Pokémon
+            let current_battler = self.battler(position).clone();
+            if let Some(effect) = hooks.on_end_of_turn(&current_battler, self.weather, &mut self.rng) {
+                self.apply_hook_effect(position, hooks, effect, events);
+            }
+        }
+    }
+
+    /// Lets the hooks of the Pokémon that just lost HP react, like the berries that heal at half HP
+    fn apply_damaged_hooks(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position).clone();
+        for hooks in battler.hooks() {
+            if self.is_over() || self.battler(position).is_fainted() {
+                return;
+            }
+            let current_battler = self.battler(position).clone();
+            if let Some(effect) = hooks.on_damaged(&current_battler) {
+                self.apply_hook_effect(position, hooks, effect, events);
+            }
+        }
+    }
+
+    /// Lets the hooks of the Pokémon that just got a persistent status condition react, like the berries that cure it
+    fn apply_status_inflicted_hooks(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position).clone();
+        for hooks in battler.hooks() {
+            let current_battler = self.battler(position).clone();
+            if let Some(effect) = hooks.on_status_inflicted(&current_battler) {
+                self.apply_hook_effect(position, hooks, effect, events);
+            }
+        }
+    }
+
+    /// Announces the hook that reacted and applies what it does to the Pokémon at the position.
+    /// The hooks that are consumed on use, like the berries, are lost after this
+    fn apply_hook_effect(&mut self, position: BattlerPosition, hooks: &dyn BattleHooks, effect: HookEffect, events: &mut Vec<BattleEvent>) {
+        let pokemon_name = self.battler(position).name().to_string();
+        events.push(hooks.activated_event(position, &pokemon_name));
+        if hooks.is_consumed_on_use() {
+            self.battler_mut(position).consume_held_item();
+        }
+
+        match effect {
+            HookEffect::Heal { amount } => self.heal(position, amount, events),
+            HookEffect::Hurt { amount } => {
+                self.hurt(position, amount, events);
+            },
+            HookEffect::CureStatus => {
+                let battler = self.battler_mut(position);
+                if let Some(status) = battler.pokemon.status {
+                    battler.pokemon.cure_status();
+                    events.push(BattleEvent::StatusCured { position, condition: StatusCondition::Major(status) });
+                }
+            }
+        }
+    }
+
+    /// Lets the hooks of the Pokémon that got hit punish the attacker, like the abilities that paralyze on contact
+    fn apply_on_hit_hooks(&mut self, position: BattlerPosition, target: BattlerPosition, battle_move: &BattleMove, events: &mut Vec<BattleEvent>) {
+        let defender = self.battler(target).clone();
+        for hooks in defender.hooks() {
+            if self.is_over() || self.battler(position).is_fainted() {
+                return;
+            }
+            let (status, chance) = match hooks.on_hit(battle_move) {
+                Some(on_hit) => on_hit,
+                None => continue
+            };
+            if self.rng.gen_range(0..100) >= chance {
+                continue;
+            }
+
+            // The ability it's only announced when the attacker really gets the status condition
+            let mut status_events = Vec::new();
+            if self.inflict_status(position, status, &mut status_events) {
+                events.push(hooks.activated_event(target, defender.name()));
+                events.append(&mut status_events);
+            }
+        }
+    }
+
+    /// Substracts HP from the Pokémon at the position, announcing his faint if it happens, or letting his hooks
+    /// react if it doesn't. Returns the real HP lost
+    fn hurt(&mut self, position: BattlerPosition, damage: i32, events: &mut Vec<BattleEvent>) -> i32 {
+        let battler = self.battler_mut(position);
+        let hp_lost = battler.take_damage(damage);
+        events.push(BattleEvent::Damaged {
+            position,
+            damage: hp_lost,
+            remaining_hp: battler.current_hp,
+            max_hp: battler.stats.max_hp
+        });
+        self.announce_if_fainted(position, events);
+        if hp_lost > 0 {
+            self.apply_damaged_hooks(position, events);
+        }
+        hp_lost
+    }
+
+    /// Gives HP back to the Pokémon at the position, if it's still able to fight
+    fn heal(&mut self, position: BattlerPosition, amount: i32, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler_mut(position);
+        if battler.is_fainted() {
+            return;
+        }
+        let hp_recovered = battler.heal(amount.max(1));
+        if hp_recovered > 0 {
+            events.push(BattleEvent::Healed {
+                position,
+                amount: hp_recovered,
+                remaining_hp: battler.current_hp,
+                max_hp: battler.stats.max_hp
+            });
+        }
+    }
+
+    /// When the Pokémon at the position just fainted, announces it, gives the experience to the player if it was
+    /// one of the opponent's, and checks if the battle it's over
+    fn announce_if_fainted(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position);
+        if !battler.is_fainted() || self.is_over() {
+            return;
+        }
+        events.push(BattleEvent::Fainted { position, pokemon_name: battler.name().to_string() });
+        if position.side == BattleSideId::Opponent {
+            self.award_experience(position, events);
+        }
+        self.check_battle_end(events);
+    }
+
+    /// Checks the status conditions that can stop a Pokémon from moving: sleep, freeze, flinch, confusion and paralysis.
+    ///
+    /// Returns false when the Pokémon loses his action
+    fn can_move(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) -> bool {
+        let thaw_roll = self.rng.gen_range(0..100);
+        let confusion_roll = self.rng.gen_range(0..100);
+        let paralysis_roll = self.rng.gen_range(0..100);
+        let battler = self.battler_mut(position);
+
+        match battler.pokemon.status {
+            Some(MajorStatus::Sleep { turns_left }) if turns_left <= 0 => {
+                battler.pokemon.cure_status();
+                events.push(BattleEvent::StatusCured { position, condition: StatusCondition::Major(MajorStatus::Sleep { turns_left }) });
+            },
+            Some(MajorStatus::Sleep { turns_left }) => {
+                battler.pokemon.status = Some(MajorStatus::Sleep { turns_left: turns_left - 1 });
+                events.push(BattleEvent::CantMove { position, condition: StatusCondition::Major(MajorStatus::Sleep { turns_left }) });
+                return false;
+            },
+            Some(MajorStatus::Freeze) if thaw_roll < status_condition::THAW_CHANCE => {
+                battler.pokemon.cure_status();
+                events.push(BattleEvent::StatusCured { position, condition: StatusCondition::Major(MajorStatus::Freeze) });
+            },
+            Some(MajorStatus::Freeze) => {
+                events.push(BattleEvent::CantMove { position, condition: StatusCondition::Major(MajorStatus::Freeze) });
+                return false;
+            },
+            _ => ()
+        }
+
+        if battler.has_volatile(&VolatileStatus::Flinch) {
+            events.push(BattleEvent::CantMove { position, condition: StatusCondition::Volatile(VolatileStatus::Flinch) });
+            return false;
+        }
+
+        if let Some(confusion) = battler.volatile(&VolatileStatus::Confusion { turns_left: 0 }) {
+            battler.remove_volatile(&confusion);
+            match confusion {
+                VolatileStatus::Confusion { turns_left } if turns_left > 0 => {
+                    battler.add_volatile(VolatileStatus::Confusion { turns_left: turns_left - 1 });
+                    if confusion_roll < status_condition::CONFUSION_SELF_HIT_CHANCE {
+                        let damage = status_condition::confusion_self_hit_damage(battler.level, battler.stats.attack, battler.stats.defense);
+                        events.push(BattleEvent::HurtByStatus { position, condition: StatusCondition::Volatile(confusion) });
+                        self.hurt(position, damage, events);
+                        return false;
+                    }
+                },
+                _ => events.push(BattleEvent::StatusCured { position, condition: StatusCondition::Volatile(confusion) })
+            }
+        }
+
+        let battler = self.battler(position);
+        if battler.pokemon.status == Some(MajorStatus::Paralysis) && paralysis_roll < status_condition::FULL_PARALYSIS_CHANCE {
+            events.push(BattleEvent::CantMove { position, condition: StatusCondition::Major(MajorStatus::Paralysis) });
+            return false;
+        }
+        true
+    }
+
+    /// Tries to give a persistent status condition to the Pokémon at the position.
+    ///
+    /// It fails if the Pokémon already has one, or if his types or his hooks are immune to it
+    fn inflict_status(&mut self, position: BattlerPosition, status: MajorStatus, events: &mut Vec<BattleEvent>) -> bool {
+        let battler = self.battler(position);
+        if battler.is_fainted() || battler.pokemon.status.is_some() ||
+            status.is_immune(battler.pokemon.type1(), battler.pokemon.type2())
+        {
+            return false;
+        }
+        let blocked_by = battler.hooks().into_iter()
+            .find(|hooks| hooks.blocks_status(&status, self.weather))
+            .map(|hooks| hooks.activated_event(position, battler.name()));
+        if let Some(activated_event) = blocked_by {
+            events.push(activated_event);
+            return false;
+        }
+
+        let battler = self.battler_mut(position);
+        battler.pokemon.status = Some(status);
+        battler.bad_poison_turns = 0;
+        events.push(BattleEvent::StatusInflicted { position, condition: StatusCondition::Major(status) });
+        self.apply_status_inflicted_hooks(position, events);
+        true
+    }
+
+    /// Tries to give a volatile status condition to the Pokémon at the position. It fails if it already has it
+    fn inflict_volatile(&mut self, position: BattlerPosition, status: VolatileStatus, events: &mut Vec<BattleEvent>) -> bool {
+        let battler = self.battler_mut(position);
+        let grass_type = battler.pokemon.type1() == PokemonType::Grass || battler.pokemon.type2() == Some(PokemonType::Grass);
+        if battler.is_fainted() || battler.volatile(&status).is_some() ||
+            (status == VolatileStatus::LeechSeed && grass_type)
+        {
+            return false;
+        }
+        battler.add_volatile(status);
+        // Flinching it's only shown when it stops the Pokémon from moving
+        if status != VolatileStatus::Flinch {
+            events.push(BattleEvent::StatusInflicted { position, condition: StatusCondition::Volatile(status) });
+        }
+        true
+    }
+
+    /// Rolls the chance of the status condition caused by the move, and tries to inflict it to the target.
+    ///
+    /// Returns `None` if the move doesn't cause any status condition, or if it was inflicted
+    fn apply_status_effect(&mut self, target: BattlerPosition, battle_move: &BattleMove, events: &mut Vec<BattleEvent>) -> Option<bool> {
+        let (chance, condition) = match battle_move.effect {
+            Some(MoveEffect::Burn { chance }) => (chance, StatusCondition::Major(MajorStatus::Burn)),
+            Some(MoveEffect::Poison { chance }) => (chance, StatusCondition::Major(MajorStatus::Poison)),
+            Some(MoveEffect::BadPoison { chance }) => (chance, StatusCondition::Major(MajorStatus::BadPoison)),
+            Some(MoveEffect::Paralysis { chance }) => (chance, StatusCondition::Major(MajorStatus::Paralysis)),
+            Some(MoveEffect::Sleep { chance }) => (chance, StatusCondition::Major(MajorStatus::random_sleep(&mut self.rng))),
+            Some(MoveEffect::Freeze { chance }) => (chance, StatusCondition::Major(MajorStatus::Freeze)),
+            Some(MoveEffect::Confusion { chance }) => (chance, StatusCondition::Volatile(VolatileStatus::random_confusion(&mut self.rng))),
+            Some(MoveEffect::Flinch { chance }) => (chance, StatusCondition::Volatile(VolatileStatus::Flinch)),
+            Some(MoveEffect::LeechSeed) => (100, StatusCondition::Volatile(VolatileStatus::LeechSeed)),
+            _ => return None
+        };
+        if self.rng.gen_range(0..100) >= chance {
+            return Some(false);
+        }
+
+        Some(match condition {
+            StatusCondition::Major(status) => self.inflict_status(target, status, events),
+            StatusCondition::Volatile(status) => self.inflict_volatile(target, status, events)
+        })
+    }
+
+    /// Sorts the actions of every Pokémon on the order that they must be resolved
+    fn order_actions(&mut self, actions: Vec<(BattlerPosition, BattleAction)>) -> Vec<(BattlerPosition, BattleAction)> {
+        let mut sorted_actions: Vec<_> = actions.into_iter()
+            .map(|(position, action)| {
+                let (action_priority, move_priority) = self.action_priority(position, &action);
+                ((action_priority, move_priority, self.speed_of(position)), (position, action))
+            })
+            .collect();
+        sorted_actions.sort_by_key(|(key, _)| Reverse(*key));
+
+        // Every group of actions that tied gets shuffled, so speed ties are decided randomly
+        let mut group_start = 0;
+        while group_start < sorted_actions.len() {
+            let group_key = sorted_actions[group_start].0;
+            let group_end = sorted_actions[group_start..].iter()
+                .position(|(key, _)| *key != group_key)
+                .map_or(sorted_actions.len(), |group_len| group_start + group_len);
+            sorted_actions[group_start..group_end].shuffle(&mut self.rng);
+            group_start = group_end;
+        }
+
+        sorted_actions.into_iter()
+            .map(|(_, positioned_action)| positioned_action)
+            .collect()
+    }
+
+    /// The speed of the Pokémon at the position, after the hooks that depend on the weather
+    fn speed_of(&self, position: BattlerPosition) -> i32 {
+        let battler = self.battler(position);
+        let multiplier: f64 = battler.hooks().iter()
+            .map(|hooks| hooks.speed_multiplier(self.weather))
+            .product();
+        (battler.effective_speed() as f64 * multiplier) as i32
+    }
+
+    /// Returns (action priority, move priority). The move priority only breaks ties between two moves
+    fn action_priority(&self, position: BattlerPosition, action: &BattleAction) -> (i32, i32) {
+        match action {
+            BattleAction::Flee => (2, 0),
+            BattleAction::Switch { .. } | BattleAction::ThrowBall { .. } => (1, 0),
+            BattleAction::UseMove { move_index, .. } => {
+                let move_priority = self.battler(position).moves.get(*move_index)
+                    .map_or(0, |battle_move| battle_move.priority);
+                (0, move_priority)
+            }
+        }
+    }
+
+    /// The Pokémon still standing on the battlefield that the move hits, when the one at the position uses it
+    fn targets_of(&self, position: BattlerPosition, battle_move: &BattleMove, chosen_target: Option<BattlerPosition>) -> Vec<BattlerPosition> {
+        let standing = self.standing_positions();
+        match battle_move.target {
+            MoveTarget::Single => chosen_target
+                .filter(|target| *target != position && standing.contains(target))
+                .or_else(|| self.foe_in_front(position))
+                .into_iter()
+                .collect(),
+            MoveTarget::AdjacentFoes => standing.into_iter()
+                .filter(|target| target.is_foe_of(&position))
+                .collect(),
+            MoveTarget::All => standing.into_iter()
+                .filter(|target| *target != position)
+                .collect(),
+            MoveTarget::Ally => Some(position.ally())
+                .filter(|ally| standing.contains(ally))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    fn use_move(&mut self, position: BattlerPosition, move_index: usize, chosen_target: Option<BattlerPosition>, events: &mut Vec<BattleEvent>) {
+        if !self.can_move(position, events) {
+            return;
+        }
+
+        let battle_move = {
+            let attacker = self.battler_mut(position);
+            if attacker.has_usable_moves() {
+                attacker.use_pp(move_index);
+                attacker.moves[move_index].clone()
+            } else {
+                BattleMove::struggle()
+            }
+        };
+
+        events.push(BattleEvent::MoveUsed {
+            position,
+            pokemon_name: self.battler(position).name().to_string(),
+            move_name: battle_move.name.to_owned()
+        });
+
+        let targets = self.targets_of(position, &battle_move, chosen_target);
+        if targets.is_empty() {
+            events.push(BattleEvent::MoveFailed { position });
+            return;
+        }
+        // The moves that hit more than one Pokémon deal less damage to each one
+        let spread_hit = targets.len() > 1;
+
+        let mut damage_dealt = 0;
+        for target in targets {
+            if self.is_over() {
+                return;
+            }
+            if self.is_standing(target) {
+                damage_dealt += self.use_move_on(position, target, &battle_move, spread_hit, events);
+            }
+        }
+        if damage_dealt > 0 {
+            self.apply_damage_effects(position, &battle_move, damage_dealt, events);
+        }
+    }
+
+    /// Resolves the move used by the Pokémon at the position against one of his targets. Returns the HP that the target lost
+    fn use_move_on(&mut self, position: BattlerPosition, target: BattlerPosition, battle_move: &BattleMove, spread_hit: bool,
+        events: &mut Vec<BattleEvent>) -> i32
+    {
+        // Accuracy check
+        if let Some(accuracy) = battle_move.accuracy {
+            if self.rng.gen_range(1..=100) > accuracy {
+                events.push(BattleEvent::MoveMissed { position: target });
+                return 0;
+            }
+        }
+        // Status moves doesn't deal any damage
+        if battle_move.category == MoveCategory::Status {
+            if self.apply_status_effect(target, battle_move, events) == Some(false) {
+                events.push(BattleEvent::MoveFailed { position });
+            }
+            return 0;
+        }
+
+        let attacker = self.battler(position).clone();
+        let defender = self.battler(target).clone();
+
+        let effectiveness = defender.effectiveness_of(battle_move.move_type);
+        if effectiveness != 1.0 {
+            events.push(BattleEvent::Effectiveness { position: target, multiplier: effectiveness });
+        }
+        // The target it's immune to the move's type
+        if effectiveness == 0.0 {
+            return 0;
+        }
+
+        let damage = match battle_move.effect {
+            Some(MoveEffect::FixedDamage { damage }) => damage,
+            _ => {
+                let critical_hit_ratio = match battle_move.effect {
+                    Some(MoveEffect::HighCriticalHitRatio) => HIGH_CRITICAL_HIT_RATIO,
+                    _ => CRITICAL_HIT_RATIO
+                };
+                let critical_hit = self.rng.gen_ratio(1, critical_hit_ratio);
+                if critical_hit {
+                    events.push(BattleEvent::CriticalHit { position: target });
+                }
+                self.calculate_damage(&attacker, &defender, battle_move, critical_hit, spread_hit)
+            }
+        };
+
+        let hp_lost = self.hurt(target, damage, events);
+        if self.is_over() {
+            return hp_lost;
+        }
+
+        let defender = self.battler_mut(target);
+        if !defender.is_fainted() {
+            // Fire moves thaws out the frozen Pokémon
+            if battle_move.move_type == PokemonType::Fire && defender.pokemon.status == Some(MajorStatus::Freeze) {
+                defender.pokemon.cure_status();
+                events.push(BattleEvent::StatusCured { position: target, condition: StatusCondition::Major(MajorStatus::Freeze) });
+            }
+            if !defender.hooks().iter().any(|hooks| hooks.blocks_secondary_effects()) {
+                self.apply_status_effect(target, battle_move, events);
+            }
+        }
+
+        self.apply_on_hit_hooks(position, target, battle_move, events);
+        hp_lost
+    }
+
+    /// Applies the effects of a damaging move that depends on the damage dealt to all his targets, like draining or recoil
+    fn apply_damage_effects(&mut self, position: BattlerPosition, battle_move: &BattleMove, damage_dealt: i32, events: &mut Vec<BattleEvent>) {
+        if self.is_over() || self.battler(position).is_fainted() {
+            return;
+        }
+
+        match battle_move.effect {
+            Some(MoveEffect::Drain { percent }) => self.heal(position, damage_dealt * percent / 100, events),
+            Some(MoveEffect::Recoil { percent }) => {
+                self.hurt(position, (damage_dealt * percent / 100).max(1), events);
+            },
+            _ => ()
+        }
+    }
+
+    /// Gives the experience and the effort values of the fainted opponent to the player's Pokémon that are still on
+    /// the battlefield, splitting the experience between them
+    fn award_experience(&mut self, defeated_position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let defeated = self.battler(defeated_position);
+        let defeated_level = defeated.level;
+        let defeated_specie = match pokemon_specie_by_id(defeated.pokemon.id) {
+            Some(specie) => specie,
+            None => return
+        };
+        let winners: Vec<BattlerPosition> = self.positions_of(BattleSideId::Player).into_iter()
+            .filter(|position| self.is_standing(*position))
+            .collect();
+        let amount = experience::experience_gain(defeated_specie.base_experience_yield, defeated_level,
+            self.kind == BattleKind::Trainer, winners.len() as i32);
+
+        for winner_position in winners {
+            let winner = self.battler_mut(winner_position);
+            let winner_specie = match pokemon_specie_by_id(winner.pokemon.id) {
+                Some(specie) => specie,
+                None => continue
+            };
+
+            // The Pokémon keeps the damage received on this battle when the stats gets recalculated
+            winner.pokemon.current_hp = winner.current_hp;
+            winner.pokemon.gain_effort_values(&defeated_specie.ev_yield);
+            let level_ups = winner.pokemon.gain_experience(winner_specie, amount);
+            winner.sync_from_pokemon();
+
+            events.push(BattleEvent::ExperienceGained {
+                position: winner_position,
+                pokemon_name: winner.name().to_string(),
+                amount
+            });
+            for level_up in level_ups {
+                events.push(BattleEvent::LeveledUp {
+                    position: winner_position,
+                    pokemon_name: winner.name().to_string(),
+                    new_level: level_up.new_level,
+                    stat_deltas: level_up.stat_deltas,
+                    new_moves: level_up.new_moves
+                });
+            }
+        }
+    }
+
+    /// The classic damage formula:
+    ///
+    /// `((2 * Level / 5 + 2) * Power * Attack / Defense / 50 + 2) * Modifiers`
+    ///
+    /// where the modifiers are the spread reduction, the critical hit, the random factor (between 0.85 and 1.0),
+    /// the STAB, the weather, the hooks of both Pokémon and the type effectiveness
+    fn calculate_damage(&mut self, attacker: &Battler, defender: &Battler, battle_move: &BattleMove, critical_hit: bool,
+        spread_hit: bool) -> i32
+    {
+        let base_damage = base_damage(attacker, defender, battle_move);
+
+        let mut modifier = self.rng.gen_range(85..=100) as f64 / 100.0;
+        if spread_hit {
+            modifier *= SPREAD_MULTIPLIER;
+        }
+        if critical_hit {
+            modifier *= 1.5;
+        }
+        if attacker.has_stab(battle_move) {
+            modifier *= STAB_MULTIPLIER;
+        }
+        modifier *= self.weather.type_multiplier(battle_move.move_type);
+        modifier *= battle_hooks::damage_multiplier(attacker, defender, battle_move, self.weather);
+        modifier *= defender.effectiveness_of(battle_move.move_type);
+
+        ((base_damage as f64 * modifier) as i32).max(1)
+    }
+
+    /// The player has more chances to escape if the Pokémon that tries it it's faster than the fastest wild one, and
+    /// each failed attempt also increases them. Some hooks, like the Run Away ability, always escape
+    fn try_to_flee(&mut self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        self.flee_attempts += 1;
+        let fleeing_battler = self.battler(position);
+        let escapes_by = fleeing_battler.hooks().into_iter()
+            .find(|hooks| hooks.always_escapes())
+            .map(|hooks| hooks.activated_event(position, fleeing_battler.name()));
+        if let Some(activated_event) = escapes_by {
+            events.push(activated_event);
+            events.push(BattleEvent::FleeSucceeded);
+            self.finish(BattleOutcome::PlayerFled, events);
+            return;
+        }
+
+        let player_speed = fleeing_battler.stats.speed;
+        let opponent_speed = self.positions_of(BattleSideId::Opponent).into_iter()
+            .filter(|wild_position| self.is_standing(*wild_position))
+            .map(|wild_position| self.battler(wild_position).stats.speed)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let escape_odds = (player_speed * 128 / opponent_speed + 30 * self.flee_attempts) % 256;
+        if player_speed >= opponent_speed || self.rng.gen_range(0..256) < escape_odds {
+            events.push(BattleEvent::FleeSucceeded);
+            self.finish(BattleOutcome::PlayerFled, events);
+        } else {
+            events.push(BattleEvent::FleeFailed);
+        }
+    }
+
+    /// Tries to catch the wild Pokémon. The battle ends when it gets caught
+    fn throw_ball(&mut self, ball_name: &str, multiplier: f64, events: &mut Vec<BattleEvent>) {
+        let wild_pokemon = match self.catchable_position() {
+            Some(position) => self.battler(position),
+            None => return
+        };
+        let catch_rate = pokemon_specie_by_id(wild_pokemon.pokemon.id)
+            .map_or(0, |specie| specie.catch_rate);
+        let capture_attempt = capture::try_capture(
+            wild_pokemon.stats.max_hp, wild_pokemon.current_hp, catch_rate, multiplier, wild_pokemon.pokemon.status, &mut self.rng
+        );
+
+        events.push(BattleEvent::BallThrown {
+            ball_name: ball_name.to_string(),
+            shakes: capture_attempt.shakes,
+            caught: capture_attempt.caught
+        });
+        if capture_attempt.caught {
+            self.finish(BattleOutcome::PokemonCaught, events);
+        }
+    }
+
+    fn check_battle_end(&mut self, events: &mut Vec<BattleEvent>) {
+        if !self.opponent.can_fight() {
+            self.finish(BattleOutcome::PlayerWon, events);
+        } else if !self.player.can_fight() {
+            self.finish(BattleOutcome::OpponentWon, events);
+        }
+    }
+
+    fn finish(&mut self, outcome: BattleOutcome, events: &mut Vec<BattleEvent>) {
+        self.outcome = outcome;
+        events.push(BattleEvent::BattleEnded { outcome });
+    }
+
+    /// The Pokémon at the position has just entered the battlefield. The shiny ones sparkle when they do
+    fn announce_switch_in(&self, position: BattlerPosition, events: &mut Vec<BattleEvent>) {
+        let battler = self.battler(position);
+        events.push(BattleEvent::SwitchedIn {
+            position,
+            party_index: self.side(position.side).active[position.slot] as i32,
+            pokemon_name: battler.name().to_string()
+        });
+        if battler.pokemon.is_shiny {
+            events.push(BattleEvent::ShinySparkle { position });
+        }
+    }
+}
+
+/// The damage of the move before applying any modifier: `(2 * Level / 5 + 2) * Power * Attack / Defense / 50 + 2`
+pub fn base_damage(attacker: &Battler, defender: &Battler, battle_move: &BattleMove) -> i32 {
+    let (attack, defense) = if battle_move.is_special() {
+        (attacker.stats.special_attack, defender.stats.special_defense)
+    } else if attacker.pokemon.status == Some(MajorStatus::Burn) &&
+        !attacker.hooks().iter().any(|hooks| hooks.ignores_burn_penalty())
+    {
+        // The burns halves the damage of the physical moves
+        (attacker.stats.attack / 2, defender.stats.defense)
+    } else {
+        (attacker.stats.attack, defender.stats.defense)
+    };
+
+    ((2 * attacker.level / 5 + 2) * battle_move.power * attack / defense.max(1)) / 50 + 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::battle::battler::BattleStats;
+    use super::super::test_support::battler;
+
+    fn use_tackle() -> BattleAction {
+        BattleAction::UseMove { move_index: 0, target: None }
+    }
+
+    fn damage_dealt_to(events: &[BattleEvent], side: BattleSideId) -> Vec<(i32, i32, i32)> {
+        events.iter()
+            .filter_map(|event| match event {
+                BattleEvent::Damaged { position, damage, remaining_hp, max_hp } if position.side == side =>
+                    Some((*damage, *remaining_hp, *max_hp)),
+                _ => None
+            })
+            .collect()
+    }
+
+    fn users_of_moves(events: &[BattleEvent]) -> Vec<BattleSideId> {
+        events.iter()
+            .filter_map(|event| match event {
+                BattleEvent::MoveUsed { position, .. } => Some(position.side),
+                _ => None
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plays_a_whole_wild_battle_switching_and_fleeing() {
+        let fast = BattleStats::new(100, 40, 40, 40, 40, 90);
+        let slow = BattleStats::new(200, 40, 40, 40, 40, 20);
+        let player = BattleSide::new("Player", vec![battler(19, fast.clone()), battler(16, fast)]);
+        let wild = BattleSide::new("Wild", vec![battler(19, slow)]);
+        let mut engine = BattleEngine::with_seed(BattleKind::Wild, player, wild, 7).unwrap();
+
+        let events = engine.start();
+        assert_eq!(events[0], BattleEvent::BattleStarted { kind: BattleKind::Wild, format: BattleFormat::Single });
+        let switched_in: Vec<BattleSideId> = events.iter()
+            .filter_map(|event| match event {
+                BattleEvent::SwitchedIn { position, .. } => Some(position.side),
+                _ => None
+            })
+            .collect();
+        assert_eq!(switched_in, vec![BattleSideId::Opponent, BattleSideId::Player]);
+
+        // The fastest Pokémon moves first, and every hit takes the HP that it says
+        let events = engine.play_turn(use_tackle(), use_tackle()).unwrap();
+        assert_eq!(events[0], BattleEvent::TurnStarted { turn: 1 });
+        assert_eq!(users_of_moves(&events), vec![BattleSideId::Player, BattleSideId::Opponent]);
+        let (damage, remaining_hp, max_hp) = damage_dealt_to(&events, BattleSideId::Opponent)[0];
+        assert!(damage > 0);
+        assert_eq!(remaining_hp, max_hp - damage);
+        assert_eq!(engine.battler(BattlerPosition::new(BattleSideId::Opponent, 0)).current_hp, remaining_hp);
+
+        // The switch happens before any move, so the Pokémon that comes in takes the hit
+        let events = engine.play_turn(BattleAction::Switch { party_index: 1 }, use_tackle()).unwrap();
+        let switch_index = events.iter()
+            .position(|event| matches!(event, BattleEvent::SwitchedIn { party_index: 1, .. }))
+            .expect("The player's Pokémon must be switched");
+        let move_index = events.iter()
+            .position(|event| matches!(event, BattleEvent::MoveUsed { .. }))
+            .expect("The wild Pokémon must use his move");
+        assert!(switch_index < move_index);
+        assert_eq!(users_of_moves(&events), vec![BattleSideId::Opponent]);
+        assert_eq!(engine.battler(BattlerPosition::new(BattleSideId::Player, 0)).name(), "Pidgey");
+        assert!(!damage_dealt_to(&events, BattleSideId::Player).is_empty());
+
+        // A faster Pokémon always runs away from a wild battle
+        let events = engine.play_turn(BattleAction::Flee, use_tackle()).unwrap();
+        assert!(events.contains(&BattleEvent::FleeSucceeded));
+        assert_eq!(events.last(), Some(&BattleEvent::BattleEnded { outcome: BattleOutcome::PlayerFled }));
+        assert!(users_of_moves(&events).is_empty());
+        assert!(engine.is_over());
+    }
+
+    #[test]
+    fn every_pokemon_of_a_double_battle_hits_the_foe_that_it_targets() {
+        let stats = |speed| BattleStats::new(200, 40, 40, 40, 40, speed);
+        let player = BattleSide::new("Player", vec![battler(4, stats(90)), battler(7, stats(70))]);
+        let trainer = BattleSide::new("Trainer", vec![battler(1, stats(80)), battler(16, stats(60))]);
+        let mut engine = BattleEngine::with_seed(BattleKind::Trainer, player, trainer, 3).unwrap()
+            .with_format(BattleFormat::Double);
+        engine.start();
+        assert_eq!(engine.standing_positions().len(), 4);
+
+        let player_left = BattlerPosition::new(BattleSideId::Player, 0);
+        let player_right = BattlerPosition::new(BattleSideId::Player, 1);
+        let foe_left = BattlerPosition::new(BattleSideId::Opponent, 0);
+        let foe_right = BattlerPosition::new(BattleSideId::Opponent, 1);
+        assert!(engine.validate_action(player_left, &BattleAction::UseMove { move_index: 0, target: Some(player_left) }).is_err());
+
+        let target = |position| BattleAction::UseMove { move_index: 0, target: Some(position) };
+        let events = engine.play_turn_with(vec![
+            (player_left, target(foe_right)), (player_right, target(foe_right)),
+            (foe_left, target(player_right)), (foe_right, target(player_right)),
+        ]).unwrap();
+        // Every move is followed by the damage of his target, on the order of the speed of the users
+        let hits: Vec<(BattlerPosition, BattlerPosition)> = events.iter()
+            .filter_map(|event| match event {
+                BattleEvent::MoveUsed { position, .. } => Some(*position),
+                _ => None
+            })
+            .zip(events.iter().filter_map(|event| match event {
+                BattleEvent::Damaged { position, .. } => Some(*position),
+                _ => None
+            }))
+            .collect();
+        assert_eq!(hits, vec![(player_left, foe_right), (foe_left, player_right), (player_right, foe_right), (foe_right, player_right)]);
+        assert_eq!(engine.battler(foe_left).current_hp, 200);
+        assert_eq!(engine.battler(player_left).current_hp, 200);
+
+        // The moves that hit more than one Pokémon pick their targets from the ones still standing
+        let mut spread_move = engine.battler(player_left).moves[0].clone();
+        spread_move.target = MoveTarget::AdjacentFoes;
+        assert_eq!(engine.targets_of(player_left, &spread_move, None), vec![foe_left, foe_right]);
+        spread_move.target = MoveTarget::All;
+        assert_eq!(engine.targets_of(player_left, &spread_move, None), vec![player_right, foe_left, foe_right]);
+        spread_move.target = MoveTarget::Ally;
+        assert_eq!(engine.targets_of(player_left, &spread_move, None), vec![player_right]);
+    }
+
+    #[test]
+    fn the_battle_ends_when_the_last_pokemon_faints() {
+        let strong = BattleStats::new(100, 200, 40, 40, 40, 10);
+        let weak = BattleStats::new(5, 10, 10, 10, 10, 90);
+        let player = BattleSide::new("Player", vec![battler(4, strong)]);
+        let trainer = BattleSide::new("Trainer", vec![battler(1, weak)]);
+        let mut engine = BattleEngine::with_seed(BattleKind::Trainer, player, trainer, 11).unwrap();
+        engine.start();
+
+        let events = engine.play_turn(use_tackle(), use_tackle()).unwrap();
+        // The faster foe moves first, even if it's the one that faints
+        assert_eq!(users_of_moves(&events), vec![BattleSideId::Opponent, BattleSideId::Player]);
+        assert!(events.iter().any(|event| matches!(event,
+            BattleEvent::Fainted { position, .. } if position.side == BattleSideId::Opponent)));
+        assert_eq!(events.last(), Some(&BattleEvent::BattleEnded { outcome: BattleOutcome::PlayerWon }));
+        assert_eq!(engine.outcome(), BattleOutcome::PlayerWon);
+        assert!(engine.play_turn(use_tackle(), use_tackle()).is_err());
+    }
+
+    #[test]
+    fn a_side_without_pokemon_cant_battle() {
+        let player = BattleSide::new("Player", Vec::new());
+        let wild = BattleSide::new("Wild", vec![battler(19, BattleStats::new(20, 10, 10, 10, 10, 10))]);
+        assert!(BattleEngine::with_seed(BattleKind::Wild, player, wild, 1).is_err());
+    }
+}
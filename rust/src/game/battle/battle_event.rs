@@ -0,0 +1,99 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::ability::Ability;
+use crate::game::pokemon_stats::StatSet;
+use crate::game::status_condition::StatusCondition;
+
+use super::battle_weather::BattleWeather;
+use super::battler::BattlerPosition;
+
+/// Every battle is played against a wild Pokémon or against a trainer. Players can't flee from a trainer battle.
+#[derive(PartialEq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleKind {
+    Wild,
+    Trainer
+}
+
+/// How many Pokémon of every side are on the battlefield at the same time
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleFormat {
+    Single,
+    /// Two against two. Used by some trainer pairs and some wild encounters
+    Double,
+}
+
+impl Default for BattleFormat {
+    fn default() -> Self { BattleFormat::Single }
+}
+
+impl BattleFormat {
+    /// The slots of the battlefield that every side fills
+    pub fn active_slots(&self) -> usize {
+        match self {
+            BattleFormat::Single => 1,
+            BattleFormat::Double => 2
+        }
+    }
+}
+
+/// The possible ways that a battle can end
+#[derive(PartialEq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleOutcome {
+    Ongoing,
+    PlayerWon,
+    OpponentWon,
+    PlayerFled,
+    PokemonCaught,
+}
+
+impl Default for BattleOutcome {
+    fn default() -> Self { BattleOutcome::Ongoing }
+}
+
+/// Each one of the things that happened during a battle, in the order that they happened.
+///
+/// The battle engine only produces this events, and the Godot battle scene just consumes them
+/// one by one to play the correct animations and texts. Every Pokémon on the battlefield it's identified by his
+/// `BattlerPosition`
+#[derive(PartialEq, Clone, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleEvent {
+    BattleStarted { kind: BattleKind, format: BattleFormat },
+    /// Emitted at the start of the battles that aren't played under a clear sky, and every time that the weather changes
+    WeatherActive { weather: BattleWeather },
+    TurnStarted { turn: i32 },
+    SwitchedIn { position: BattlerPosition, party_index: i32, pokemon_name: String },
+    /// Follows the `SwitchedIn` event of a shiny Pokémon, so the battle scene plays the sparkle effect over it
+    ShinySparkle { position: BattlerPosition },
+    /// The ability of the Pokémon did something. Followed by the events of what it did, if any
+    AbilityActivated { position: BattlerPosition, pokemon_name: String, ability: Ability },
+    /// The held item of the Pokémon did something. Followed by the events of what it did. Berries are eaten after this
+    HeldItemActivated { position: BattlerPosition, pokemon_name: String, item_name: String },
+    MoveUsed { position: BattlerPosition, pokemon_name: String, move_name: String },
+    /// The move missed the Pokémon at the position. Moves that hit several Pokémon can miss only some of them
+    MoveMissed { position: BattlerPosition },
+    /// A status move that couldn't inflict his status condition, or a move that didn't have any target left
+    MoveFailed { position: BattlerPosition },
+    /// The Pokémon loses his action because of a status condition
+    CantMove { position: BattlerPosition, condition: StatusCondition },
+    /// The move landed a critical hit on the Pokémon at the position
+    CriticalHit { position: BattlerPosition },
+    /// Only emitted when the multiplier isn't 1.0. A 0.0 multiplier means that the move doesn't affect the target
+    Effectiveness { position: BattlerPosition, multiplier: f64 },
+    Damaged { position: BattlerPosition, damage: i32, remaining_hp: i32, max_hp: i32 },
+    StatusInflicted { position: BattlerPosition, condition: StatusCondition },
+    StatusCured { position: BattlerPosition, condition: StatusCondition },
+    /// Always followed by the `Damaged` event with the HP lost
+    HurtByStatus { position: BattlerPosition, condition: StatusCondition },
+    /// Always followed by the `Damaged` event with the HP lost
+    HurtByWeather { position: BattlerPosition, weather: BattleWeather },
+    Healed { position: BattlerPosition, amount: i32, remaining_hp: i32, max_hp: i32 },
+    Fainted { position: BattlerPosition, pokemon_name: String },
+    ExperienceGained { position: BattlerPosition, pokemon_name: String, amount: i32 },
+    LeveledUp { position: BattlerPosition, pokemon_name: String, new_level: i32, stat_deltas: StatSet, new_moves: Vec<String> },
+    /// `shakes` it's how many times the ball shook before the wild Pokémon broke free, or got caught
+    BallThrown { ball_name: String, shakes: i32, caught: bool },
+    FleeSucceeded,
+    FleeFailed,
+    BattleEnded { outcome: BattleOutcome },
+}
@@ -0,0 +1,364 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::ability::Ability;
+use crate::game::game_item::HeldEffect;
+use crate::game::pokemon::Pokemon;
+use crate::game::pokemon_type::PokemonType;
+use crate::game::status_condition::MajorStatus;
+use crate::utils::all_game_items::game_item_by_name;
+
+use super::battle_event::BattleEvent;
+use super::battle_weather::BattleWeather;
+use super::battler::{BattleMove, Battler, BattlerPosition};
+
+/// The chance, in percentage, that the abilities that punishes the attacker inflicts their status condition
+const ON_HIT_STATUS_CHANCE: i32 = 30;
+/// The abilities that boosts a type do it while the HP is at or below 1 / N of the max HP
+const PINCH_HP_RATIO: i32 = 3;
+
+/// What happens when a Pokémon enters the battlefield
+#[derive(PartialEq, Clone, Debug)]
+pub enum SwitchInEffect {
+    SetWeather { weather: BattleWeather },
+}
+
+/// What happens to a Pokémon when one of his hooks reacts to the battle, like at the end of every turn
+#[derive(PartialEq, Clone, Debug)]
+pub enum HookEffect {
+    Heal { amount: i32 },
+    Hurt { amount: i32 },
+    CureStatus,
+}
+
+/// The moments of a battle when something attached to a Pokémon, like his ability or his held item, can change what happens.
+///
+/// Every hook has a neutral default, so each implementor only overrides the moments when it acts.
+/// The `BattleEngine` asks every hook of the active Pokémon through `Battler::hooks()`
+pub trait BattleHooks {
+    /// The event that tells the player that this hook just did something
+    fn activated_event(&self, position: BattlerPosition, pokemon_name: &str) -> BattleEvent;
+
+    /// True when the hook it's lost after acting once, like the berries
+    fn is_consumed_on_use(&self) -> bool {
+        false
+    }
+
+    // Switch-in
+
+    /// Called after the Pokémon entered the battlefield
+    fn on_switch_in(&self, _battler: &Battler, _weather: BattleWeather) -> Option<SwitchInEffect> {
+        None
+    }
+
+    // Damage calculation
+
+    /// Multiplies the damage of the moves used by the Pokémon
+    fn attack_multiplier(&self, _attacker: &Battler, _battle_move: &BattleMove, _weather: BattleWeather) -> f64 {
+        1.0
+    }
+
+    /// Multiplies the damage of the moves received by the Pokémon
+    fn defense_multiplier(&self, _defender: &Battler, _battle_move: &BattleMove) -> f64 {
+        1.0
+    }
+
+    /// True when the burn doesn't halve the damage of the physical moves of the Pokémon
+    fn ignores_burn_penalty(&self) -> bool {
+        false
+    }
+
+    /// Called after the Pokémon got hit by a damaging move. Returns the status condition that the attacker
+    /// could get, with his chance in percentage
+    fn on_hit(&self, _battle_move: &BattleMove) -> Option<(MajorStatus, i32)> {
+        None
+    }
+
+    /// Called every time that the Pokémon loses HP, if it's still able to fight
+    fn on_damaged(&self, _battler: &Battler) -> Option<HookEffect> {
+        None
+    }
+
+    // Status
+
+    /// True when the Pokémon can't get the given status condition
+    fn blocks_status(&self, _status: &MajorStatus, _weather: BattleWeather) -> bool {
+        false
+    }
+
+    /// True when the additional effects of the damaging moves, like burning or flinching, don't affect the Pokémon
+    fn blocks_secondary_effects(&self) -> bool {
+        false
+    }
+
+    /// Called right after the Pokémon got a persistent status condition
+    fn on_status_inflicted(&self, _battler: &Battler) -> Option<HookEffect> {
+        None
+    }
+
+    // Weather
+
+    /// Multiplies the speed used to decide who moves first
+    fn speed_multiplier(&self, _weather: BattleWeather) -> f64 {
+        1.0
+    }
+
+    /// True when the weather doesn't hurt the Pokémon
+    fn ignores_weather_damage(&self, _weather: BattleWeather) -> bool {
+        false
+    }
+
+    // End of turn
+
+    /// Called at the end of every turn while the Pokémon is still on the battlefield
+    fn on_end_of_turn(&self, _battler: &Battler, _weather: BattleWeather, _rng: &mut ChaCha8Rng) -> Option<HookEffect> {
+        None
+    }
+
+    // Out of the turn
+
+    /// True when the player can always run away from the wild Pokémon
+    fn always_escapes(&self) -> bool {
+        false
+    }
+}
+
+impl BattleHooks for Ability {
+    fn activated_event(&self, position: BattlerPosition, pokemon_name: &str) -> BattleEvent {
+        BattleEvent::AbilityActivated { position, pokemon_name: pokemon_name.to_string(), ability: *self }
+    }
+
+    fn on_switch_in(&self, _battler: &Battler, weather: BattleWeather) -> Option<SwitchInEffect> {
+        let new_weather = match self {
+            Ability::Drizzle => BattleWeather::Rain,
+            Ability::Drought => BattleWeather::Sun,
+            _ => return None
+        };
+        if new_weather == weather {
+            return None;
+        }
+        Some(SwitchInEffect::SetWeather { weather: new_weather })
+    }
+
+    fn attack_multiplier(&self, attacker: &Battler, battle_move: &BattleMove, weather: BattleWeather) -> f64 {
+        let in_a_pinch = attacker.current_hp * PINCH_HP_RATIO <= attacker.stats.max_hp;
+        let boosted = match self {
+            Ability::Overgrow => in_a_pinch && battle_move.move_type == PokemonType::Grass,
+            Ability::Blaze => in_a_pinch && battle_move.move_type == PokemonType::Fire,
+            Ability::Torrent => in_a_pinch && battle_move.move_type == PokemonType::Water,
+            Ability::SolarPower => weather == BattleWeather::Sun && battle_move.is_special(),
+            Ability::Guts => attacker.pokemon.status.is_some() && !battle_move.is_special(),
+            _ => false
+        };
+        if boosted { 1.5 } else { 1.0 }
+    }
+
+    fn defense_multiplier(&self, _defender: &Battler, battle_move: &BattleMove) -> f64 {
+        match self {
+            Ability::ThickFat if battle_move.move_type == PokemonType::Fire || battle_move.move_type == PokemonType::Ice => 0.5,
+            _ => 1.0
+        }
+    }
+
+    fn ignores_burn_penalty(&self) -> bool {
+        *self == Ability::Guts
+    }
+
+    fn on_hit(&self, battle_move: &BattleMove) -> Option<(MajorStatus, i32)> {
+        // Only the physical moves make contact with the Pokémon
+        if battle_move.is_special() {
+            return None;
+        }
+        match self {
+            Ability::Static => Some((MajorStatus::Paralysis, ON_HIT_STATUS_CHANCE)),
+            Ability::PoisonPoint => Some((MajorStatus::Poison, ON_HIT_STATUS_CHANCE)),
+            _ => None
+        }
+    }
+
+    fn blocks_status(&self, status: &MajorStatus, _weather: BattleWeather) -> bool {
+        matches!(
+            (self, status),
+            (Ability::Immunity, MajorStatus::Poison | MajorStatus::BadPoison) |
+            (Ability::Insomnia, MajorStatus::Sleep { .. }) |
+            (Ability::Limber, MajorStatus::Paralysis)
+        )
+    }
+
+    fn blocks_secondary_effects(&self) -> bool {
+        *self == Ability::ShieldDust
+    }
+
+    fn speed_multiplier(&self, weather: BattleWeather) -> f64 {
+        match (self, weather) {
+            (Ability::Chlorophyll, BattleWeather::Sun) | (Ability::SwiftSwim, BattleWeather::Rain) => 2.0,
+            _ => 1.0
+        }
+    }
+
+    fn ignores_weather_damage(&self, weather: BattleWeather) -> bool {
+        *self == Ability::IceBody && weather == BattleWeather::Hail
+    }
+
+    fn on_end_of_turn(&self, battler: &Battler, weather: BattleWeather, rng: &mut ChaCha8Rng) -> Option<HookEffect> {
+        let max_hp = battler.stats.max_hp;
+        let full_hp = battler.current_hp >= max_hp;
+        match (self, weather) {
+            (Ability::RainDish, BattleWeather::Rain) | (Ability::IceBody, BattleWeather::Hail) if !full_hp =>
+                Some(HookEffect::Heal { amount: (max_hp / 16).max(1) }),
+            (Ability::SolarPower, BattleWeather::Sun) => Some(HookEffect::Hurt { amount: (max_hp / 8).max(1) }),
+            (Ability::ShedSkin, _) if battler.pokemon.status.is_some() && rng.gen_ratio(1, 3) => Some(HookEffect::CureStatus),
+            _ => None
+        }
+    }
+
+    fn always_escapes(&self) -> bool {
+        *self == Ability::RunAway
+    }
+}
+
+/// An item of the catalogue, held by a Pokémon while it fights
+#[derive(PartialEq, Clone, Debug)]
+pub struct HeldItem {
+    pub name: String,
+    pub effect: HeldEffect,
+}
+
+impl HeldItem {
+    /// The item held by the Pokémon, if it does anything on the battles
+    pub fn held_by(pokemon: &Pokemon) -> Option<HeldItem> {
+        let game_item = game_item_by_name(pokemon.held_item.as_ref()?)?;
+        let effect = game_item.held_effect?;
+        Some(HeldItem { name: game_item.name, effect })
+    }
+}
+
+impl BattleHooks for HeldItem {
+    fn activated_event(&self, position: BattlerPosition, pokemon_name: &str) -> BattleEvent {
+        BattleEvent::HeldItemActivated { position, pokemon_name: pokemon_name.to_string(), item_name: self.name.to_owned() }
+    }
+
+    fn is_consumed_on_use(&self) -> bool {
+        self.effect.is_consumed_on_use()
+    }
+
+    fn attack_multiplier(&self, _attacker: &Battler, battle_move: &BattleMove, _weather: BattleWeather) -> f64 {
+        match self.effect {
+            HeldEffect::BoostType { move_type, multiplier } if move_type == battle_move.move_type => multiplier,
+            _ => 1.0
+        }
+    }
+
+    fn on_damaged(&self, battler: &Battler) -> Option<HookEffect> {
+        match self.effect {
+            HeldEffect::HealAtHalfHp { amount } if battler.current_hp * 2 <= battler.stats.max_hp =>
+                Some(HookEffect::Heal { amount }),
+            _ => None
+        }
+    }
+
+    fn on_status_inflicted(&self, battler: &Battler) -> Option<HookEffect> {
+        let current_status = battler.pokemon.status?;
+        match self.effect {
+            HeldEffect::CureStatus { status: None } => Some(HookEffect::CureStatus),
+            HeldEffect::CureStatus { status: Some(cured_status) } if current_status.same_kind(&cured_status) =>
+                Some(HookEffect::CureStatus),
+            _ => None
+        }
+    }
+
+    fn on_end_of_turn(&self, battler: &Battler, _weather: BattleWeather, _rng: &mut ChaCha8Rng) -> Option<HookEffect> {
+        match self.effect {
+            HeldEffect::HealEachTurn { divisor } if battler.current_hp < battler.stats.max_hp =>
+                Some(HookEffect::Heal { amount: (battler.stats.max_hp / divisor.max(1)).max(1) }),
+            _ => None
+        }
+    }
+}
+
+/// The multiplier that the hooks of both Pokémon applies to the damage of a move
+pub fn damage_multiplier(attacker: &Battler, defender: &Battler, battle_move: &BattleMove, weather: BattleWeather) -> f64 {
+    let attack: f64 = attacker.hooks().iter()
+        .map(|hooks| hooks.attack_multiplier(attacker, battle_move, weather))
+        .product();
+    let defense: f64 = defender.hooks().iter()
+        .map(|hooks| hooks.defense_multiplier(defender, battle_move))
+        .product();
+    attack * defense
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use super::super::battler::BattleStats;
+    use super::super::test_support::battler;
+    use crate::utils::all_pokemon_moves::pokemon_move_by_name;
+
+    fn battler_with(ability: Ability) -> Battler {
+        let mut battler = battler(4, BattleStats::new(96, 40, 40, 40, 40, 40));
+        battler.pokemon.ability = Some(ability);
+        battler
+    }
+
+    fn battle_move(move_name: &str) -> BattleMove {
+        let pokemon_move = pokemon_move_by_name(move_name).expect("The move must be on the moves data");
+        BattleMove::from_move(&pokemon_move, pokemon_move.pp)
+    }
+
+    #[test]
+    fn the_type_boosts_only_work_in_a_pinch() {
+        let mut blaze = battler_with(Ability::Blaze);
+        let embers = battle_move("Ascuas");
+        assert_eq!(Ability::Blaze.attack_multiplier(&blaze, &embers, BattleWeather::Clear), 1.0);
+        blaze.current_hp = 33;
+        assert_eq!(Ability::Blaze.attack_multiplier(&blaze, &embers, BattleWeather::Clear), 1.0);
+        blaze.current_hp = 32;
+        assert_eq!(Ability::Blaze.attack_multiplier(&blaze, &embers, BattleWeather::Clear), 1.5);
+        assert_eq!(Ability::Blaze.attack_multiplier(&blaze, &battle_move("Placaje"), BattleWeather::Clear), 1.0);
+    }
+
+    #[test]
+    fn the_hooks_of_both_pokemon_multiply_the_damage() {
+        let mut guts = battler_with(Ability::Guts);
+        let thick_fat = battler_with(Ability::ThickFat);
+        let fire_fang = battle_move("Colmillo Ígneo");
+        assert_eq!(damage_multiplier(&guts, &thick_fat, &fire_fang, BattleWeather::Clear), 0.5);
+        guts.pokemon.status = Some(MajorStatus::Burn);
+        assert_eq!(damage_multiplier(&guts, &thick_fat, &fire_fang, BattleWeather::Clear), 0.75);
+        assert_eq!(damage_multiplier(&guts, &thick_fat, &battle_move("Placaje"), BattleWeather::Clear), 1.5);
+        assert!(Ability::Guts.ignores_burn_penalty());
+    }
+
+    #[test]
+    fn the_status_abilities_only_block_their_own_status() {
+        let blocked = |ability: Ability, status: MajorStatus| ability.blocks_status(&status, BattleWeather::Clear);
+        assert!(blocked(Ability::Immunity, MajorStatus::Poison));
+        assert!(blocked(Ability::Immunity, MajorStatus::BadPoison));
+        assert!(!blocked(Ability::Immunity, MajorStatus::Burn));
+        assert!(blocked(Ability::Insomnia, MajorStatus::Sleep { turns_left: 3 }));
+        assert!(blocked(Ability::Limber, MajorStatus::Paralysis));
+        assert!(!blocked(Ability::Limber, MajorStatus::Freeze));
+        assert!(!blocked(Ability::Static, MajorStatus::Paralysis));
+    }
+
+    #[test]
+    fn the_weather_abilities_react_to_their_weather() {
+        let drizzle = battler_with(Ability::Drizzle);
+        assert_eq!(Ability::Drizzle.on_switch_in(&drizzle, BattleWeather::Sun),
+            Some(SwitchInEffect::SetWeather { weather: BattleWeather::Rain }));
+        assert_eq!(Ability::Drizzle.on_switch_in(&drizzle, BattleWeather::Rain), None);
+        assert_eq!(Ability::SwiftSwim.speed_multiplier(BattleWeather::Rain), 2.0);
+        assert_eq!(Ability::SwiftSwim.speed_multiplier(BattleWeather::Sun), 1.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut rain_dish = battler_with(Ability::RainDish);
+        assert_eq!(Ability::RainDish.on_end_of_turn(&rain_dish, BattleWeather::Rain, &mut rng), None);
+        rain_dish.current_hp = 50;
+        assert_eq!(Ability::RainDish.on_end_of_turn(&rain_dish, BattleWeather::Rain, &mut rng), Some(HookEffect::Heal { amount: 6 }));
+        assert_eq!(Ability::RainDish.on_end_of_turn(&rain_dish, BattleWeather::Clear, &mut rng), None);
+        let solar_power = battler_with(Ability::SolarPower);
+        assert_eq!(Ability::SolarPower.on_end_of_turn(&solar_power, BattleWeather::Sun, &mut rng), Some(HookEffect::Hurt { amount: 12 }));
+    }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::pokemon::Pokemon;
+
+use super::battle_engine::{BattleAction, BattleEngine};
+use super::battle_event::{BattleEvent, BattleFormat, BattleKind};
+use super::battle_weather::BattleWeather;
+use super::battler::{BattleSide, Battler, BattlerPosition};
+
+/// One of the two teams as it was when the battle started
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BattleLogSide {
+    pub name: String,
+    pub team: Vec<Pokemon>,
+}
+
+impl BattleLogSide {
+    pub fn from_side(battle_side: &BattleSide) -> Self {
+        Self {
+            name: battle_side.name.clone(),
+            team: battle_side.battlers.iter()
+                .map(|battler| battler.clone().into_pokemon())
+                .collect()
+        }
+    }
+
+    /// Builds the team again, ready to fight
+    pub fn to_side(&self) -> BattleSide {
+        BattleSide::new(&self.name, self.team.iter().cloned().map(Battler::from_pokemon).collect())
+    }
+}
+
+/// Each one of the steps that moved the battle forward, with the events that it produced
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum BattleLogEntry {
+    Start { events: Vec<BattleEvent> },
+    Turn { turn: i32, actions: Vec<(BattlerPosition, BattleAction)>, events: Vec<BattleEvent> },
+    Replacement { position: BattlerPosition, party_index: usize, events: Vec<BattleEvent> },
+}
+
+impl BattleLogEntry {
+    pub fn events(&self) -> &Vec<BattleEvent> {
+        match self {
+            BattleLogEntry::Start { events } => events,
+            BattleLogEntry::Turn { events, .. } => events,
+            BattleLogEntry::Replacement { events, .. } => events
+        }
+    }
+}
+
+/// Everything needed to play a battle again: the seed of his RNG, how it was set up, the teams that started it and
+/// every action taken, with the events that they produced.
+///
+/// It's written by the `BattleEngine` while the battle it's played, and it can be stored as JSON, so a bug report
+/// can carry the battle where the bug happened
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BattleLog {
+    pub seed: u64,
+    pub kind: BattleKind,
+    pub format: BattleFormat,
+    pub weather: BattleWeather,
+    pub player: BattleLogSide,
+    pub opponent: BattleLogSide,
+    pub entries: Vec<BattleLogEntry>,
+}
+
+impl BattleLog {
+    pub fn new(seed: u64, kind: BattleKind, format: BattleFormat, weather: BattleWeather, player: &BattleSide, opponent: &BattleSide) -> Self {
+        Self {
+            seed,
+            kind,
+            format,
+            weather,
+            player: BattleLogSide::from_side(player),
+            opponent: BattleLogSide::from_side(opponent),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| format!("Can't write the battle log: {}", err))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| format!("Can't read the battle log: {}", err))
+    }
+}
+
+/// Plays again a logged battle, step by step, checking that every step produces the same events that were logged
+pub struct BattleReplay {
+    log: BattleLog,
+    engine: BattleEngine,
+    next_entry: usize,
+}
+
+impl BattleReplay {
+    /// Sets up the battle exactly as it was when it started. Nothing it's played until `play_next()` gets called.
+    ///
+    /// Returns an error when the log has a side without Pokémon, because that battle couldn't have been played
+    pub fn new(log: BattleLog) -> Result<Self, String> {
+        let engine = BattleEngine::with_seed(log.kind, log.player.to_side(), log.opponent.to_side(), log.seed)?
+            .with_weather(log.weather)
+            .with_format(log.format);
+        Ok(Self { log, engine, next_entry: 0 })
+    }
+
+    /// The battle as it's after the steps already replayed
+    pub fn engine(&self) -> &BattleEngine {
+        &self.engine
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_entry >= self.log.entries.len()
+    }
+
+    /// Replays the next step of the log and returns his events, or `None` when all of them were already replayed.
+    ///
+    /// It's an error when the step can't be played, or when it doesn't end up as it was logged
+    pub fn play_next(&mut self) -> Option<Result<Vec<BattleEvent>, String>> {
+        let entry = self.log.entries.get(self.next_entry)?.clone();
+        self.next_entry += 1;
+        let events = match &entry {
+            BattleLogEntry::Start { .. } => Ok(self.engine.start()),
+            BattleLogEntry::Turn { actions, .. } => self.engine.play_turn_with(actions.clone()),
+            BattleLogEntry::Replacement { position, party_index, .. } => self.engine.replace_fainted(*position, *party_index)
+        };
+        Some(events.and_then(|events| {
+            if events == *entry.events() {
+                Ok(events)
+            } else {
+                Err(format!("The replay diverged from the log on the step {}, at the turn {}", self.next_entry - 1, self.engine.turn()))
+            }
+        }))
+    }
+
+    /// Replays every step left on the log, stopping at the first one that doesn't end up as it was logged
+    pub fn play_to_end(&mut self) -> Result<&BattleEngine, String> {
+        while let Some(result) = self.play_next() {
+            result?;
+        }
+        Ok(&self.engine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::battle_ai::AiDifficulty;
+    use super::super::test_support::{play_with_ais, team};
+
+    /// Plays a whole trainer battle, with both sides driven by the AI, and returns the engine as it ended
+    fn play_battle(seed: u64) -> BattleEngine {
+        let player = BattleSide::new("Player", team(&[(4, 14), (16, 12)]));
+        let trainer = BattleSide::new("Trainer", team(&[(1, 13), (19, 12)]));
+        let mut engine = BattleEngine::with_seed(BattleKind::Trainer, player, trainer, seed).unwrap();
+        engine.start();
+        play_with_ais(&mut engine, AiDifficulty::Smart.build(seed), AiDifficulty::Greedy.build(seed + 1), 100);
+        engine
+    }
+
+    #[test]
+    fn a_logged_battle_replays_the_same_events_after_going_through_json() {
+        let engine = play_battle(2024);
+        assert!(engine.is_over());
+        let log = engine.log().expect("A started battle must have a log");
+
+        let json = log.to_json().unwrap();
+        let read_log = BattleLog::from_json(&json).unwrap();
+        assert_eq!(read_log.seed, log.seed);
+        assert_eq!(read_log.entries, log.entries);
+
+        let mut replay = BattleReplay::new(read_log).unwrap();
+        let mut replayed_events = Vec::new();
+        while let Some(events) = replay.play_next() {
+            replayed_events.push(events.unwrap());
+        }
+        let logged_events: Vec<Vec<BattleEvent>> = log.entries.iter().map(|entry| entry.events().clone()).collect();
+        assert_eq!(replayed_events, logged_events);
+        assert_eq!(replay.engine().outcome(), engine.outcome());
+        assert_eq!(replay.engine().turn(), engine.turn());
+    }
+
+    #[test]
+    fn a_replay_with_another_seed_diverges_from_the_log() {
+        let engine = play_battle(7);
+        let mut log = engine.log().unwrap().clone();
+        log.seed += 1;
+        assert!(BattleReplay::new(log).unwrap().play_to_end().is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use gdnative::prelude::*;
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::game::Game;
+
+use super::battle_ai::BattleAi;
+use super::battle_engine::{BattleAction, BattleEngine};
+use super::battle_event::{BattleEvent, BattleFormat};
+use super::battle_weather::BattleWeather;
+use super::battler::{BattleMove, BattleSideId, BattlerPosition};
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The Godot side of a battle.
+///
+/// Holds the `BattleEngine` that resolves the battle, receives the player choices from the battle UI
+/// and emits every `BattleEvent` produced by the engine through the `battle_event` signal, so the scene can animate them.
+/// The battle only finishes when the battle UI calls `finish_battle`, once the player has read how it ended.
+///
+/// On the double battles, the player chooses the action of his Pokémon one after the other, and the turn it's played
+/// when both have one
+pub struct BattleScene {
+    engine: Option<BattleEngine>,
+    // Decides the actions of the opponent
+    opponent_ai: Option<Box<dyn BattleAi>>,
+    // The actions already chosen for the player's Pokémon on the current turn
+    player_actions: Vec<(BattlerPosition, BattleAction)>,
+}
+
+impl RegisterSignal<Self> for BattleScene {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // Carries one `BattleEvent` converted to a Variant
+        builder.signal("battle_event")
+            .with_param("event", VariantType::Dictionary)
+            .done();
+        // Notifies the Game that the battle it's over, carrying the `BattleOutcome`
+        builder.signal("battle_finished")
+            .with_param("outcome", VariantType::Dictionary)
+            .done();
+    }
+}
+
+#[methods]
+impl BattleScene {
+    fn new(_owner: &Node2D) -> Self {
+        Self {
+            engine: None,
+            opponent_ai: None,
+            player_actions: Vec::new(),
+        }
+    }
+
+    /// Sets the battle that this scene will play, and the AI that controls the opponent. Must be called before adding the scene to the tree
+    pub fn set_engine(&mut self, engine: BattleEngine, opponent_ai: Box<dyn BattleAi>) {
+        self.engine = Some(engine);
+        self.opponent_ai = Some(opponent_ai);
+    }
+
+    /// Takes the battle out of the scene, to read how the Pokémon ended up after it's over
+    pub fn take_engine(&mut self) -> Option<BattleEngine> {
+        self.engine.take()
+    }
+
+    /// The battle being played, so the battle UI can show the Pokémon on the battlefield and fill his menus
+    pub fn engine(&self) -> Option<&BattleEngine> {
+        self.engine.as_ref()
+    }
+
+    #[method]
+    fn _ready(&mut self, #[base] base: &Node2D) {
+        // When the battle finishes, the Game takes care about going back to the world scene.
+        // Deferred, because the signal it's emitted while this scene it's still borrowed, and the Game takes the engine out of it
+        let game = unsafe { base.get_node("/root/Game").unwrap().assume_safe() };
+        base.connect("battle_finished", game, "end_battle",
+            VariantArray::new_shared(), 1).unwrap();
+
+        if let Some(engine) = self.engine.as_mut() {
+            let events = engine.start();
+            self.emit_battle_events(base, events);
+        } else {
+            godot_error!("BattleScene entered the tree without a battle to play");
+        }
+    }
+
+    #[method]
+    /// The player wants to use the move at the given slot, against the foe in front
+    fn choose_move(&mut self, #[base] base: &Node2D, move_index: i64) {
+        self.play_player_action(base, BattleAction::UseMove { move_index: move_index as usize, target: None });
+    }
+
+    #[method]
+    /// The player wants to use the move at the given slot against the Pokémon on the given slot of the battlefield,
+    /// one of the foes or, when `on_ally` it's true, his other Pokémon
+    fn choose_move_on_target(&mut self, #[base] base: &Node2D, move_index: i64, target_slot: i64, on_ally: bool) {
+        let target_side = if on_ally { BattleSideId::Player } else { BattleSideId::Opponent };
+        let target = BattlerPosition::new(target_side, target_slot.max(0) as usize);
+        self.play_player_action(base, BattleAction::UseMove { move_index: move_index as usize, target: Some(target) });
+    }
+
+    #[method]
+    /// The player wants to send out the Pokémon at the given party slot
+    fn choose_switch(&mut self, #[base] base: &Node2D, party_index: i64) {
+        self.play_player_action(base, BattleAction::Switch { party_index: party_index as usize });
+    }
+
+    #[method]
+    /// The player throws a Poké Ball of the bag to the wild Pokémon. The ball is only taken out of the bag once the
+    /// turn where it's thrown gets played
+    fn choose_ball(&mut self, #[base] base: &Node2D, ball_name: String) {
+        // The balls already chosen by the other Pokémon of the player for this turn must be on the bag too
+        let already_chosen = self.player_actions.iter()
+            .filter(|(_, action)| matches!(action, BattleAction::ThrowBall { ball_name: chosen_ball, .. } if *chosen_ball == ball_name))
+            .count() as i32;
+        let game = unsafe { base.get_node_as_instance::<Game>("/root/Game") };
+        let ball_on_bag = match game {
+            Some(game) => game.map(|game, _| game.ball_on_bag(&ball_name, already_chosen + 1))
+                .unwrap_or_else(|err| Err(format!("{:?}", err))),
+            None => Err("There's no Game to take the ball from".to_string())
+        };
+        match ball_on_bag {
+            Ok(multiplier) => self.play_player_action(base, BattleAction::ThrowBall { ball_name, multiplier }),
+            Err(err) => godot_print!("Can't throw the ball: {}", err)
+        }
+    }
+
+    #[method]
+    /// The player tries to run away from a wild battle
+    fn choose_flee(&mut self, #[base] base: &Node2D) {
+        self.play_player_action(base, BattleAction::Flee);
+    }
+
+    #[method]
+    /// Notifies the `Game` that the battle it's over, so it brings back the world scene. Does nothing while the battle
+    /// it's still being played
+    fn finish_battle(&self, #[base] base: &Node2D) {
+        if let Some(engine) = self.engine.as_ref().filter(|engine| engine.is_over()) {
+            base.emit_signal("battle_finished", &[engine.outcome().to_variant()]);
+        }
+    }
+
+    #[method]
+    /// Takes back the action chosen for the first Pokémon of the player on a double battle, to choose it again
+    fn cancel_last_choice(&mut self) {
+        self.player_actions.pop();
+    }
+
+    #[method]
+    /// Sends out the Pokémon at the given party slot after one of the player's Pokémon on the battlefield fainted
+    fn choose_replacement(&mut self, #[base] base: &Node2D, party_index: i64) {
+        let result = match self.engine.as_mut() {
+            Some(engine) => match engine.positions_to_replace(BattleSideId::Player).first() {
+                Some(position) => engine.replace_fainted(*position, party_index as usize),
+                None => return
+            },
+            None => return
+        };
+        match result {
+            Ok(events) => self.emit_battle_events(base, events),
+            Err(err) => godot_print!("Invalid replacement: {}", err)
+        }
+    }
+
+    #[method]
+    /// The names of the moves of the player's Pokémon that it's choosing his action, so the battle UI can fill the
+    /// move buttons
+    fn get_player_move_names(&self) -> Vec<String> {
+        self.get_player_moves().into_iter()
+            .map(|battle_move| battle_move.name)
+            .collect()
+    }
+
+    #[method]
+    /// The moves of the player's Pokémon that it's choosing his action, with their type, category and remaining PP
+    fn get_player_moves(&self) -> Vec<BattleMove> {
+        match (self.engine.as_ref(), self.choosing_position()) {
+            (Some(engine), Some(position)) => engine.battler(position).moves.clone(),
+            _ => Vec::new()
+        }
+    }
+
+    #[method]
+    /// The slot of the battlefield of the player's Pokémon that it's choosing his action, or -1 if there's none
+    fn get_choosing_slot(&self) -> i64 {
+        self.choosing_position().map_or(-1, |position| position.slot as i64)
+    }
+
+    #[method]
+    /// True when the battle it's played two against two
+    fn is_double_battle(&self) -> bool {
+        self.engine.as_ref().map_or(false, |engine| engine.format() == BattleFormat::Double)
+    }
+
+    #[method]
+    /// The name of the weather of the battlefield, so the battle UI can show it
+    fn get_weather_name(&self) -> String {
+        self.engine.as_ref()
+            .map_or(BattleWeather::default(), |engine| engine.weather())
+            .to_spanish_str()
+            .to_string()
+    }
+
+    #[method]
+    /// True when the player must choose a new Pokémon before the battle can continue
+    fn player_needs_replacement(&self) -> bool {
+        self.engine.as_ref().map_or(false, |engine| engine.needs_replacement(BattleSideId::Player))
+    }
+
+    /// The player's Pokémon that still has to choose his action for the current turn
+    pub fn choosing_position(&self) -> Option<BattlerPosition> {
+        let engine = self.engine.as_ref()?;
+        engine.positions_of(BattleSideId::Player).into_iter()
+            .filter(|position| engine.is_standing(*position))
+            .find(|position| !self.player_actions.iter().any(|(chosen_position, _)| chosen_position == position))
+    }
+
+    /// Stores the action of the player's Pokémon that it's choosing. When every one of them has his action, resolves a
+    /// whole turn with them and with the opponent's ones
+    fn play_player_action(&mut self, base: &Node2D, player_action: BattleAction) {
+        let position = match self.choosing_position() {
+            Some(position) => position,
+            None => return
+        };
+        let (engine, opponent_ai) = match (self.engine.as_mut(), self.opponent_ai.as_mut()) {
+            (Some(engine), Some(opponent_ai)) => (engine, opponent_ai),
+            _ => return
+        };
+        if let Err(err) = engine.validate_action(position, &player_action) {
+            godot_print!("Invalid battle action: {}", err);
+            return;
+        }
+        self.player_actions.push((position, player_action));
+        // Waits for the action of the other Pokémon of the player
+        let all_chosen = engine.positions_of(BattleSideId::Player).into_iter()
+            .filter(|position| engine.is_standing(*position))
+            .all(|position| self.player_actions.iter().any(|(chosen_position, _)| *chosen_position == position));
+        if !all_chosen {
+            return;
+        }
+
+        let mut actions = std::mem::take(&mut self.player_actions);
+        let player_actions = actions.clone();
+        for position in engine.positions_of(BattleSideId::Opponent) {
+            if engine.is_standing(position) {
+                actions.push((position, opponent_ai.choose_action(engine, position)));
+            }
+        }
+        let mut events = match engine.play_turn_with(actions) {
+            Ok(events) => events,
+            Err(err) => {
+                // Keeps the choices of the player, except the last one, that it's the one that must be chosen again
+                godot_print!("Invalid battle action: {}", err);
+                self.player_actions = player_actions;
+                self.player_actions.pop();
+                return;
+            }
+        };
+        // The turn it's played, so the thrown balls leave the bag
+        for (_, action) in player_actions.iter() {
+            if let BattleAction::ThrowBall { ball_name, .. } = action {
+                Self::take_ball_from_bag(base, ball_name);
+            }
+        }
+
+        // The opponent always sends out it's next Pokémon as soon as one of the active ones faints
+        for position in engine.positions_to_replace(BattleSideId::Opponent) {
+            if let Some(next) = opponent_ai.choose_replacement(engine, position) {
+                if let Ok(mut replacement_events) = engine.replace_fainted(position, next) {
+                    events.append(&mut replacement_events);
+                }
+            }
+        }
+
+        self.emit_battle_events(base, events);
+    }
+
+    /// Takes a thrown Poké Ball out of the bag of the player
+    fn take_ball_from_bag(base: &Node2D, ball_name: &str) {
+        let game = unsafe { base.get_node_as_instance::<Game>("/root/Game") };
+        let ball_taken = match game {
+            Some(game) => game.map_mut(|game, _| game.take_ball_from_bag(ball_name).map(|_| ()))
+                .unwrap_or_else(|err| Err(format!("{:?}", err))),
+            None => Err("There's no Game to take the ball from".to_string())
+        };
+        if let Err(err) = ball_taken {
+            godot_print!("Can't take the thrown ball from the bag: {}", err);
+        }
+    }
+
+    /// Sends every event to the listeners
+    fn emit_battle_events(&self, base: &Node2D, events: Vec<BattleEvent>) {
+        for event in events.iter() {
+            base.emit_signal("battle_event", &[event.to_variant()]);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::VecDeque;
+
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::game::Game;
+use crate::game::game_item::BagPocket;
+use crate::utils::utils;
+
+use super::battle_engine::BattleEngine;
+use super::battle_event::{BattleEvent, BattleKind, BattleOutcome};
+use super::battle_scene::BattleScene;
+use super::battler::{BattleSideId, Battler};
+
+/// The options of the main menu of the battle, in the order that they're shown
+const MAIN_MENU_OPTIONS: [&str; 4] = ["Luchar", "Mochila", "Pokémon", "Huir"];
+
+/// The menus that the player goes through to choose what his Pokémon does on every turn
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum BattleMenu {
+    Main,
+    Moves,
+    // The Poké Balls of the bag
+    Balls,
+    // The team, to switch the Pokémon on the battlefield or to replace the one that fainted
+    Party,
+}
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// The battle UI. Shows every `BattleEvent` of the parent `BattleScene` as a message, one after the other, and the menus
+/// where the player chooses his actions, that are sent to the `BattleScene`.
+///
+/// Once the battle is over and the player has read the last message, asks the `BattleScene` to finish the battle
+pub struct BattleUi {
+    // The texts of the events that the player still has to read
+    messages: VecDeque<String>,
+    // Wild battles and trainer battles talk differently about the opponent
+    battle_kind: BattleKind,
+    battle_over: bool,
+    battle_finished: bool,
+
+    menu: BattleMenu,
+    // The texts of the options of the current menu, with the value sent to the `BattleScene` when one gets chosen
+    options: Vec<(String, Variant)>,
+    current_option_selected: usize,
+
+    x_option_position: f32,
+    y_option_position: f32,
+    option_separation: f32,
+}
+
+#[methods]
+impl BattleUi {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            battle_kind: BattleKind::Wild,
+            battle_over: false,
+            battle_finished: false,
+            menu: BattleMenu::Main,
+            options: Vec::new(),
+            current_option_selected: 0,
+            x_option_position: 1240.0,
+            y_option_position: 780.0,
+            option_separation: 60.0,
+        }
+    }
+
+    #[method]
+    fn _ready(&mut self, #[base] owner: TRef<Control>) {
+        // Deferred, because the `BattleScene` emits his events while it's borrowed, and the menus read his battle
+        let battle_scene = self.battle_scene_node(&owner);
+        battle_scene.connect("battle_event", owner, "_on_battle_event",
+            VariantArray::new_shared(), 1).unwrap();
+
+        owner.set_process(true);
+    }
+
+    #[method]
+    /// Receives the `battle_event` signal of the `BattleScene`, and queues the message that tells what happened
+    fn _on_battle_event(&mut self, #[base] owner: &Control, event: BattleEvent) {
+        match &event {
+            BattleEvent::BattleStarted { kind, .. } => self.battle_kind = *kind,
+            BattleEvent::BattleEnded { .. } => self.battle_over = true,
+            _ => ()
+        }
+        if let Some(message) = self.event_message(&event) {
+            self.messages.push_back(message);
+        }
+        self.refresh(owner);
+    }
+
+    #[method]
+    fn _process(&mut self, #[base] owner: &Control, _delta: f64) {
+        let input: &Input = Input::godot_singleton();
+
+        // The messages are read one after the other, before any menu it's shown again
+        if !self.messages.is_empty() {
+            if Input::is_action_just_pressed(&input, "Interact", false) {
+                self.messages.pop_front();
+                self.refresh(owner);
+            }
+            return;
+        }
+        if self.battle_over {
+            if !self.battle_finished {
+                self.battle_finished = true;
+                unsafe { self.battle_scene_node(owner).call("finish_battle", &[]) };
+            }
+            return;
+        }
+
+        if Input::is_action_just_pressed(&input, "ui_up", false) {
+            if self.current_option_selected > 0 {
+                self.current_option_selected -= 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "ui_down", false) {
+            if self.current_option_selected + 1 < self.options.len() {
+                self.current_option_selected += 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "Interact", false) {
+            self.choose_option(owner);
+        } else if Input::is_action_just_pressed(&input, "Exit", false) && self.menu != BattleMenu::Main
+            && !self.with_engine(owner, |engine| engine.needs_replacement(BattleSideId::Player)).unwrap_or(false)
+        {
+            self.open_menu(owner, BattleMenu::Main);
+        }
+    }
+
+    /// Does what the selected option of the current menu says
+    fn choose_option(&mut self, owner: &Control) {
+        let value = match self.options.get(self.current_option_selected) {
+            Some((_, value)) => value.clone(),
+            None => return
+        };
+        let battle_scene = self.battle_scene_node(owner);
+
+        match self.menu {
+            BattleMenu::Main => match value.to::<i64>().unwrap_or(0) {
+                0 => self.open_menu(owner, BattleMenu::Moves),
+                1 if self.battle_kind == BattleKind::Trainer =>
+                    self.show_message(owner, "¡No puedes capturar el Pokémon de otro entrenador!"),
+                1 => self.open_menu(owner, BattleMenu::Balls),
+                2 => self.open_menu(owner, BattleMenu::Party),
+                _ if self.battle_kind == BattleKind::Trainer =>
+                    self.show_message(owner, "¡No puedes huir de un combate contra un entrenador!"),
+                _ => {
+                    unsafe { battle_scene.call("choose_flee", &[]) };
+                    self.open_menu(owner, BattleMenu::Main);
+                }
+            },
+            BattleMenu::Moves => {
+                unsafe { battle_scene.call("choose_move", &[value]) };
+                self.open_menu(owner, BattleMenu::Main);
+            },
+            BattleMenu::Balls => {
+                unsafe { battle_scene.call("choose_ball", &[value]) };
+                self.open_menu(owner, BattleMenu::Main);
+            },
+            BattleMenu::Party => {
+                let replacing = self.with_engine(owner, |engine| engine.needs_replacement(BattleSideId::Player)).unwrap_or(false);
+                let method = if replacing { "choose_replacement" } else { "choose_switch" };
+                unsafe { battle_scene.call(method, &[value]) };
+                self.open_menu(owner, BattleMenu::Main);
+            }
+        }
+    }
+
+    /// Shows a message that doesn't come from the battle, like the reason why an option can't be chosen
+    fn show_message(&mut self, owner: &Control, message: &str) {
+        self.messages.push_back(message.to_string());
+        self.refresh(owner);
+    }
+
+    fn open_menu(&mut self, owner: &Control, menu: BattleMenu) {
+        self.menu = menu;
+        self.current_option_selected = 0;
+        self.refresh(owner);
+    }
+
+    /// Updates the Pokémon on the battlefield, and shows the first message not read yet or, when there's none, the menu
+    /// where the player chooses what to do
+    fn refresh(&mut self, owner: &Control) {
+        // A fainted Pokémon must be replaced before anything else
+        if self.with_engine(owner, |engine| engine.needs_replacement(BattleSideId::Player)).unwrap_or(false) {
+            self.menu = BattleMenu::Party;
+        }
+
+        let (opponent_info, player_info) = self.with_engine(owner, |engine| (
+            Self::side_info(engine, BattleSideId::Opponent),
+            Self::side_info(engine, BattleSideId::Player)
+        )).unwrap_or_default();
+        utils::set_label_text(owner, &"OpponentInfo".to_string(), &opponent_info);
+        utils::set_label_text(owner, &"PlayerInfo".to_string(), &player_info);
+
+        let (message, options) = match self.messages.front() {
+            Some(message) => (message.clone(), Vec::new()),
+            None if self.battle_over => (String::new(), Vec::new()),
+            None => (self.menu_prompt(owner), self.menu_options(owner))
+        };
+        utils::set_label_text(owner, &"Message".to_string(), &message);
+        self.options = options;
+        self.current_option_selected = self.current_option_selected.min(self.options.len().saturating_sub(1));
+        self.show_options(owner);
+    }
+
+    /// The names, levels and HP of the Pokémon of the side that are on the battlefield, one per line
+    fn side_info(engine: &BattleEngine, side: BattleSideId) -> String {
+        engine.positions_of(side).into_iter()
+            .map(|position| engine.battler(position))
+            .map(|battler| format!("{}  Nv. {}    PS {}/{}", battler.name(), battler.level, battler.current_hp.max(0), battler.stats.max_hp))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn menu_prompt(&self, owner: &Control) -> String {
+        let choosing_pokemon = self.choosing_battler(owner)
+            .map_or(String::new(), |battler| battler.name().to_string());
+
+        match self.menu {
+            BattleMenu::Main => format!("¿Qué debería hacer {}?", choosing_pokemon),
+            BattleMenu::Moves => format!("¿Qué movimiento debería usar {}?", choosing_pokemon),
+            BattleMenu::Balls => "¿Qué Poké Ball quieres lanzar?".to_string(),
+            BattleMenu::Party => "¿Qué Pokémon quieres sacar?".to_string()
+        }
+    }
+
+    /// The options of the current menu, with the value that every one sends to the `BattleScene`
+    fn menu_options(&self, owner: &Control) -> Vec<(String, Variant)> {
+        match self.menu {
+            BattleMenu::Main => MAIN_MENU_OPTIONS.iter()
+                .enumerate()
+                .map(|(idx, option)| (option.to_string(), (idx as i64).to_variant()))
+                .collect(),
+            BattleMenu::Moves => self.choosing_battler(owner)
+                .map_or(Vec::new(), |battler| battler.moves)
+                .iter()
+                .enumerate()
+                .map(|(idx, battle_move)| (
+                    format!("{}    PP {}/{}", battle_move.name, battle_move.current_pp, battle_move.max_pp),
+                    (idx as i64).to_variant()
+                ))
+                .collect(),
+            BattleMenu::Balls => unsafe { owner.get_node_as_instance::<Game>("/root/Game") }
+                .and_then(|game| game.map(|game, _| game.player_data().bag().pocket(BagPocket::PokeBalls)).ok())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|bag_slot| (format!("{}    x{}", bag_slot.item_name, bag_slot.quantity), bag_slot.item_name.to_variant()))
+                .collect(),
+            BattleMenu::Party => self.with_engine(owner, |engine| engine.side(BattleSideId::Player).battlers.iter()
+                .enumerate()
+                .map(|(idx, battler)| (
+                    format!("{}  Nv. {}    PS {}/{}", battler.name(), battler.level, battler.current_hp.max(0), battler.stats.max_hp),
+                    (idx as i64).to_variant()
+                ))
+                .collect()
+            ).unwrap_or_default()
+        }
+    }
+
+    /// Replaces the labels of the options holder with the ones of the current menu
+    fn show_options(&self, owner: &Control) {
+        let options_holder = unsafe { owner.get_node_as::<Node2D>("Options").unwrap() };
+
+        // Removes the labels of the previous menu
+        for child in options_holder.get_children().iter() {
+            if let Ok(child_node) = child.try_to_object::<Node>() {
+                unsafe { child_node.assume_safe().queue_free() };
+            }
+        }
+
+        for (idx, (text, _)) in self.options.iter().enumerate() {
+            let option_label = Label::new();
+            option_label.set_text(text.as_str());
+            option_label.set_position(
+                Vector2::new(self.x_option_position, self.y_option_position + idx as f32 * self.option_separation),
+                false
+            );
+            options_holder.add_child(option_label, true);
+        }
+
+        self.update_selection(owner);
+    }
+
+    /// Moves the selector over the selected option, and hides it when there isn't any
+    fn update_selection(&self, owner: &Control) {
+        let selector = unsafe { owner.get_node_as::<NinePatchRect>("Selector").unwrap() };
+        selector.set_visible(!self.options.is_empty());
+        selector.set_global_position(
+            Vector2::new(
+                selector.global_position().x,
+                self.y_option_position + self.current_option_selected as f32 * self.option_separation - 10.0
+            ),
+            false
+        );
+    }
+
+    /// The text that tells the player what happened on the event. `None` for the events that the labels already show,
+    /// like the HP lost
+    fn event_message(&self, event: &BattleEvent) -> Option<String> {
+        let message = match event {
+            BattleEvent::BattleStarted { kind: BattleKind::Wild, .. } => "¡Un Pokémon salvaje te sale al paso!".to_string(),
+            BattleEvent::BattleStarted { kind: BattleKind::Trainer, .. } => "¡Un entrenador te reta a un combate!".to_string(),
+            BattleEvent::WeatherActive { weather } => format!("Tiempo en el campo de batalla: {}", weather.to_spanish_str()),
+            BattleEvent::SwitchedIn { position, pokemon_name, .. } => match (position.side, self.battle_kind) {
+                (BattleSideId::Player, _) => format!("¡Adelante, {}!", pokemon_name),
+                (BattleSideId::Opponent, BattleKind::Wild) => format!("¡Un {} salvaje!", pokemon_name),
+                (BattleSideId::Opponent, BattleKind::Trainer) => format!("¡El entrenador saca a {}!", pokemon_name)
+            },
+            BattleEvent::ShinySparkle { .. } => "¡Es un Pokémon variocolor!".to_string(),
+            BattleEvent::AbilityActivated { pokemon_name, ability, .. } =>
+                format!("¡Se activa {} de {}!", ability.to_spanish_str(), pokemon_name),
+            BattleEvent::HeldItemActivated { pokemon_name, item_name, .. } => format!("¡{} usa su {}!", pokemon_name, item_name),
+            BattleEvent::MoveUsed { pokemon_name, move_name, .. } => format!("¡{} usa {}!", pokemon_name, move_name),
+            BattleEvent::MoveMissed { .. } => "¡El ataque no acertó!".to_string(),
+            BattleEvent::MoveFailed { .. } => "¡Pero falló!".to_string(),
+            BattleEvent::CantMove { condition, .. } => format!("¡No se puede mover! ({})", condition.to_spanish_str()),
+            BattleEvent::CriticalHit { .. } => "¡Un golpe crítico!".to_string(),
+            BattleEvent::Effectiveness { multiplier, .. } if *multiplier == 0.0 => "No afecta...".to_string(),
+            BattleEvent::Effectiveness { multiplier, .. } if *multiplier > 1.0 => "¡Es muy eficaz!".to_string(),
+            BattleEvent::Effectiveness { .. } => "No es muy eficaz...".to_string(),
+            BattleEvent::StatusInflicted { condition, .. } => format!("¡Ahora está {}!", condition.to_spanish_str().to_lowercase()),
+            BattleEvent::StatusCured { condition, .. } => format!("¡Ya no está {}!", condition.to_spanish_str().to_lowercase()),
+            BattleEvent::HurtByStatus { condition, .. } => format!("¡Sufre por estar {}!", condition.to_spanish_str().to_lowercase()),
+            BattleEvent::HurtByWeather { weather, .. } => format!("¡Le afecta el tiempo! ({})", weather.to_spanish_str()),
+            BattleEvent::Healed { amount, .. } => format!("¡Recupera {} PS!", amount),
+            BattleEvent::Fainted { pokemon_name, .. } => format!("¡{} se ha debilitado!", pokemon_name),
+            BattleEvent::ExperienceGained { pokemon_name, amount, .. } =>
+                format!("¡{} gana {} puntos de experiencia!", pokemon_name, amount),
+            BattleEvent::LeveledUp { pokemon_name, new_level, new_moves, .. } => new_moves.iter()
+                .fold(format!("¡{} sube al nivel {}!", pokemon_name, new_level), |message, move_name| {
+                    format!("{} ¡Aprende {}!", message, move_name)
+                }),
+            BattleEvent::BallThrown { ball_name, caught: true, .. } => format!("¡Lanzas una {}! ¡Lo has atrapado!", ball_name),
+            BattleEvent::BallThrown { ball_name, .. } => format!("¡Lanzas una {}! ¡Oh, no! ¡Se ha escapado!", ball_name),
+            BattleEvent::FleeSucceeded => "¡Escapaste sin problemas!".to_string(),
+            BattleEvent::FleeFailed => "¡No puedes escapar!".to_string(),
+            BattleEvent::BattleEnded { outcome: BattleOutcome::PlayerWon } => "¡Has ganado el combate!".to_string(),
+            BattleEvent::BattleEnded { outcome: BattleOutcome::OpponentWon } => "¡No te quedan Pokémon! Has perdido el combate...".to_string(),
+            BattleEvent::TurnStarted { .. } | BattleEvent::Damaged { .. } | BattleEvent::BattleEnded { .. } => return None
+        };
+        Some(message)
+    }
+
+    /// The node of the `BattleScene` that plays the battle shown by this UI
+    fn battle_scene_node(&self, owner: &Control) -> TRef<'static, Node> {
+        unsafe { owner.get_node("../..").unwrap().assume_safe() }
+    }
+
+    /// Reads the battle being played. `None` when the `BattleScene` doesn't have it, or can't be read now
+    fn with_engine<T, F: FnOnce(&BattleEngine) -> T>(&self, owner: &Control, read: F) -> Option<T> {
+        self.battle_scene_node(owner)
+            .cast::<Node2D>()
+            .and_then(|node| node.cast_instance::<BattleScene>())
+            .and_then(|battle_scene| battle_scene.map(|battle_scene, _| battle_scene.engine().map(read)).ok())
+            .flatten()
+    }
+
+    /// The player's Pokémon that has to choose his action now
+    fn choosing_battler(&self, owner: &Control) -> Option<Battler> {
+        self.battle_scene_node(owner)
+            .cast::<Node2D>()
+            .and_then(|node| node.cast_instance::<BattleScene>())
+            .and_then(|battle_scene| battle_scene.map(|battle_scene, _| {
+                let position = battle_scene.choosing_position()?;
+                battle_scene.engine().map(|engine| engine.battler(position).clone())
+            }).ok())
+            .flatten()
+    }
+}
@@ -0,0 +1,68 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::game::Weather;
+use crate::game::pokemon_type::PokemonType;
+
+/// The damage multiplier of the moves boosted by the weather
+pub const WEATHER_BOOST: f64 = 1.5;
+/// The damage multiplier of the moves weakened by the weather
+pub const WEATHER_PENALTY: f64 = 0.5;
+/// The hail takes 1 / N of the max HP at the end of every turn
+pub const HAIL_DAMAGE_RATIO: i32 = 16;
+
+/// The weather of the battlefield, that changes the power of some moves and can hurt the Pokémon.
+///
+/// Battles inherit it from the real weather of the city where they start
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleWeather {
+    Clear,
+    Rain,
+    Sun,
+    Hail,
+}
+
+impl Default for BattleWeather {
+    fn default() -> Self { BattleWeather::Clear }
+}
+
+impl BattleWeather {
+    /// The field weather that matches the real weather of a city
+    pub fn from_weather(weather: &Weather) -> BattleWeather {
+        match weather {
+            // The drizzle it's a light rain on the OpenWeather codes
+            Weather::Rain | Weather::Drizzle | Weather::Thunderstorm => BattleWeather::Rain,
+            Weather::Sun => BattleWeather::Sun,
+            Weather::Snow => BattleWeather::Hail,
+            Weather::Clouds => BattleWeather::Clear
+        }
+    }
+
+    /// The rain boosts the Water moves and weakens the Fire ones. The sun does the reverse
+    pub fn type_multiplier(&self, move_type: PokemonType) -> f64 {
+        match (self, move_type) {
+            (BattleWeather::Rain, PokemonType::Water) | (BattleWeather::Sun, PokemonType::Fire) => WEATHER_BOOST,
+            (BattleWeather::Rain, PokemonType::Fire) | (BattleWeather::Sun, PokemonType::Water) => WEATHER_PENALTY,
+            _ => 1.0
+        }
+    }
+
+    /// The HP lost at the end of every turn by a Pokémon of the given types. The Ice types aren't hurt by the hail
+    pub fn end_of_turn_damage(&self, type1: PokemonType, type2: Option<PokemonType>, max_hp: i32) -> i32 {
+        let ice_type = type1 == PokemonType::Ice || type2 == Some(PokemonType::Ice);
+        match self {
+            BattleWeather::Hail if !ice_type => (max_hp / HAIL_DAMAGE_RATIO).max(1),
+            _ => 0
+        }
+    }
+
+    // Returns the weather's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            BattleWeather::Clear => "Despejado",
+            BattleWeather::Rain => "Lluvia",
+            BattleWeather::Sun => "Sol",
+            BattleWeather::Hail => "Granizo"
+        }
+    }
+}
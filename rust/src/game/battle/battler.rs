@@ -0,0 +1,364 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::pokemon::Pokemon;
+use crate::game::pokemon_type::PokemonType;
+use crate::game::pokemon_move::{self, MoveCategory, MoveEffect, MoveTarget, PokemonMove};
+use crate::game::status_condition::{MajorStatus, VolatileStatus};
+use crate::utils::all_pokemon_moves::pokemon_move_by_name;
+
+use super::battle_hooks::{BattleHooks, HeldItem};
+
+/// Identifies each one of the two sides that are fighting on a battle
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BattleSideId {
+    Player,
+    Opponent
+}
+
+impl BattleSideId {
+    /// Returns the side that it's facing this one
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Player => Self::Opponent,
+            Self::Opponent => Self::Player
+        }
+    }
+}
+
+/// Identifies each Pokémon on the battlefield: his side, and the slot of that side where it's fighting.
+///
+/// Single battles only use the slot 0 of every side, and double battles also use the slot 1
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub struct BattlerPosition {
+    pub side: BattleSideId,
+    pub slot: usize,
+}
+
+impl BattlerPosition {
+    pub fn new(side: BattleSideId, slot: usize) -> Self {
+        Self { side, slot }
+    }
+
+    /// The position that it's in front of this one, on the other side
+    pub fn facing(&self) -> Self {
+        Self::new(self.side.opposite(), self.slot)
+    }
+
+    /// The position of the partner of the Pokémon on a double battle
+    pub fn ally(&self) -> Self {
+        Self::new(self.side, 1 - self.slot.min(1))
+    }
+
+    pub fn is_foe_of(&self, other: &BattlerPosition) -> bool {
+        self.side != other.side
+    }
+}
+
+/// The final stats that a Pokémon uses while it's fighting
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BattleStats {
+    pub max_hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub special_attack: i32,
+    pub special_defense: i32,
+    pub speed: i32,
+}
+
+impl BattleStats {
+    pub fn new(max_hp: i32, attack: i32, defense: i32, special_attack: i32, special_defense: i32, speed: i32) -> Self {
+        Self { max_hp, attack, defense, special_attack, special_defense, speed }
+    }
+}
+
+/// A move as the battle engine understands it, with the PP that it still has on this battle
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub struct BattleMove {
+    pub name: String,
+    pub move_type: PokemonType,
+    pub category: MoveCategory,
+    pub power: i32,
+    /// Percentage from 0 to 100. `None` never misses
+    pub accuracy: Option<i32>,
+    pub priority: i32,
+    pub effect: Option<MoveEffect>,
+    pub target: MoveTarget,
+    pub current_pp: i32,
+    pub max_pp: i32,
+}
+
+impl BattleMove {
+    /// Prepares a move to be used on a battle, with the given remaining PP
+    pub fn from_move(pokemon_move: &PokemonMove, current_pp: i32) -> Self {
+        Self {
+            name: pokemon_move.name.to_owned(),
+            move_type: pokemon_move.move_type,
+            category: pokemon_move.category,
+            power: pokemon_move.power,
+            accuracy: pokemon_move.accuracy,
+            priority: pokemon_move.priority,
+            effect: pokemon_move.effect.clone(),
+            target: pokemon_move.target,
+            current_pp,
+            max_pp: pokemon_move.pp,
+        }
+    }
+
+    /// The move that gets used when there's no PP left on any other move
+    pub fn struggle() -> Self {
+        let struggle = pokemon_move_by_name(pokemon_move::STRUGGLE)
+            .expect("Struggle must be always on the moves data");
+        Self::from_move(&struggle, struggle.pp)
+    }
+
+    /// True for the moves that uses the special attack and special defense stats
+    pub fn is_special(&self) -> bool {
+        self.category == MoveCategory::Special
+    }
+}
+
+/// Wraps a `Pokemon` with all the data that only lives while a battle is running
+#[derive(Clone, Debug)]
+pub struct Battler {
+    pub pokemon: Pokemon,
+    pub level: i32,
+    pub stats: BattleStats,
+    pub current_hp: i32,
+    pub moves: Vec<BattleMove>,
+    /// The conditions that are lost when the Pokémon leaves the battlefield
+    pub volatile_statuses: Vec<VolatileStatus>,
+    /// How many turns the Pokémon has been badly poisoned since it entered the battlefield
+    pub bad_poison_turns: i32,
+    /// The item that the Pokémon holds, when it does anything on the battles
+    pub held_item: Option<HeldItem>,
+}
+
+impl Battler {
+    pub fn new(pokemon: Pokemon, level: i32, stats: BattleStats, moves: Vec<BattleMove>) -> Self {
+        Self {
+            held_item: HeldItem::held_by(&pokemon),
+            pokemon,
+            level,
+            current_hp: stats.max_hp,
+            stats,
+            moves,
+            volatile_statuses: Vec::new(),
+            bad_poison_turns: 0,
+        }
+    }
+
+    /// Prepares a `Pokemon` to fight, taking his level, his current HP, his calculated stats and the moves that he knows
+    pub fn from_pokemon(pokemon: Pokemon) -> Self {
+        let stats = pokemon.stats();
+        let moves = pokemon.move_set.slots().iter()
+            .filter_map(|slot| pokemon_move_by_name(&slot.move_name)
+                .map(|pokemon_move| BattleMove::from_move(&pokemon_move, slot.current_pp)))
+            .collect();
+        Self {
+            level: pokemon.level,
+            current_hp: pokemon.current_hp,
+            stats: BattleStats::new(stats.hp, stats.attack, stats.defense, stats.special_attack, stats.special_defense, stats.speed),
+            held_item: HeldItem::held_by(&pokemon),
+            pokemon,
+            moves,
+            volatile_statuses: Vec::new(),
+            bad_poison_turns: 0,
+        }
+    }
+
+    /// Copies back the level and the stats of the wrapped `Pokemon`, after they changed in the middle of the battle
+    pub fn sync_from_pokemon(&mut self) {
+        let stats = self.pokemon.stats();
+        self.level = self.pokemon.level;
+        self.current_hp = self.pokemon.current_hp;
+        self.stats = BattleStats::new(stats.hp, stats.attack, stats.defense, stats.special_attack, stats.special_defense, stats.speed);
+    }
+
+    /// Ends the battle for this Pokémon, giving back the wrapped `Pokemon` with the HP that it has left
+    pub fn into_pokemon(self) -> Pokemon {
+        let mut pokemon = self.pokemon;
+        pokemon.current_hp = self.current_hp.clamp(0, pokemon.max_hp);
+        pokemon
+    }
+
+    pub fn name(&self) -> &str {
+        self.pokemon.name.as_str()
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.current_hp <= 0
+    }
+
+    /// Returns true if the move shares one of the types of the Pokémon (Same Type Attack Bonus)
+    pub fn has_stab(&self, battle_move: &BattleMove) -> bool {
+        battle_move.move_type == self.pokemon.type1() || Some(battle_move.move_type) == self.pokemon.type2()
+    }
+
+    /// The type effectiveness multiplier of a move of the given type hitting this Pokémon
+    pub fn effectiveness_of(&self, move_type: PokemonType) -> f64 {
+        move_type.effectiveness_against(self.pokemon.type1(), self.pokemon.type2())
+    }
+
+    /// The speed used to decide who moves first. Paralysis halves it
+    pub fn effective_speed(&self) -> i32 {
+        match self.pokemon.status {
+            Some(MajorStatus::Paralysis) => self.stats.speed / 2,
+            _ => self.stats.speed
+        }
+    }
+
+    /// Everything attached to the Pokémon that can act on the battle through the `BattleHooks`: his ability
+    /// and his held item
+    pub fn hooks(&self) -> Vec<&dyn BattleHooks> {
+        let mut hooks: Vec<&dyn BattleHooks> = Vec::new();
+        if let Some(ability) = self.pokemon.ability.as_ref() {
+            hooks.push(ability);
+        }
+        if let Some(held_item) = self.held_item.as_ref() {
+            hooks.push(held_item);
+        }
+        hooks
+    }
+
+    /// The held item was used up, like an eaten berry. The Pokémon doesn't get it back after the battle
+    pub fn consume_held_item(&mut self) {
+        self.held_item = None;
+        self.pokemon.held_item = None;
+    }
+
+    /// Returns the volatile condition of the same kind than the given one, if the Pokémon has it
+    pub fn volatile(&self, status: &VolatileStatus) -> Option<VolatileStatus> {
+        self.volatile_statuses.iter().find(|volatile| volatile.same_kind(status)).copied()
+    }
+
+    pub fn has_volatile(&self, status: &VolatileStatus) -> bool {
+        self.volatile(status).is_some()
+    }
+
+    pub fn add_volatile(&mut self, status: VolatileStatus) {
+        self.volatile_statuses.push(status);
+    }
+
+    /// Removes the volatile condition of the same kind than the given one
+    pub fn remove_volatile(&mut self, status: &VolatileStatus) {
+        self.volatile_statuses.retain(|volatile| !volatile.same_kind(status));
+    }
+
+    /// Called when the Pokémon leaves the battlefield
+    pub fn clear_volatile_statuses(&mut self) {
+        self.volatile_statuses.clear();
+        self.bad_poison_turns = 0;
+    }
+
+    /// True while at least one of the moves has PP left. When none has, the Pokémon can only struggle
+    pub fn has_usable_moves(&self) -> bool {
+        self.moves.iter().any(|battle_move| battle_move.current_pp > 0)
+    }
+
+    /// Spends one PP of the move at the given slot, also on the move set of the wrapped `Pokemon`
+    pub fn use_pp(&mut self, move_index: usize) {
+        if let Some(battle_move) = self.moves.get_mut(move_index) {
+            battle_move.current_pp = (battle_move.current_pp - 1).max(0);
+            if self.pokemon.move_set.get(move_index).map_or(false, |slot| slot.move_name == battle_move.name) {
+                self.pokemon.move_set.use_pp(move_index);
+            }
+        }
+    }
+
+    /// Recovers HP, never going above the max HP. Returns the real HP recovered
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        let hp_recovered = amount.min(self.stats.max_hp - self.current_hp).max(0);
+        self.current_hp += hp_recovered;
+        hp_recovered
+    }
+
+    /// Substracts the damage from the current HP, never going below zero. Returns the real HP lost
+    pub fn take_damage(&mut self, damage: i32) -> i32 {
+        let hp_lost = damage.min(self.current_hp).max(0);
+        self.current_hp -= hp_lost;
+        hp_lost
+    }
+}
+
+/// One of the two teams of a battle, with the indexes of the `Battler`s that are currently on the field, one per slot
+#[derive(Clone, Debug)]
+pub struct BattleSide {
+    pub name: String,
+    pub battlers: Vec<Battler>,
+    pub active: Vec<usize>,
+}
+
+impl BattleSide {
+    pub fn new(name: &str, battlers: Vec<Battler>) -> Self {
+        let mut battle_side = Self {
+            name: name.to_string(),
+            battlers,
+            active: Vec::new(),
+        };
+        battle_side.send_out_first(1);
+        battle_side
+    }
+
+    /// The first healthy Pokémon of the team are the ones that start the battle, one per slot. When the team doesn't
+    /// have enough of them, the side fights with less slots. A team without any Pokémon doesn't get any slot
+    pub fn send_out_first(&mut self, slots: usize) {
+        self.active = self.battlers.iter()
+            .enumerate()
+            .filter(|(_, battler)| !battler.is_fainted())
+            .map(|(idx, _)| idx)
+            .take(slots)
+            .collect();
+        if self.active.is_empty() && !self.battlers.is_empty() {
+            self.active.push(0);
+        }
+    }
+
+    /// The Pokémon on the first slot. On the single battles, the only one on the battlefield
+    pub fn active_battler(&self) -> &Battler {
+        self.active_battler_at(0)
+    }
+
+    pub fn active_battler_mut(&mut self) -> &mut Battler {
+        self.active_battler_at_mut(0)
+    }
+
+    pub fn active_battler_at(&self, slot: usize) -> &Battler {
+        &self.battlers[self.active[slot]]
+    }
+
+    pub fn active_battler_at_mut(&mut self, slot: usize) -> &mut Battler {
+        let party_index = self.active[slot];
+        &mut self.battlers[party_index]
+    }
+
+    /// How many slots of the battlefield the side fills
+    pub fn slots(&self) -> usize {
+        self.active.len()
+    }
+
+    /// True when the Pokémon at `party_index` it's on the battlefield
+    pub fn is_active(&self, party_index: usize) -> bool {
+        self.active.contains(&party_index)
+    }
+
+    /// True while there's at least one Pokémon that still can fight
+    pub fn can_fight(&self) -> bool {
+        self.battlers.iter().any(|battler| !battler.is_fainted())
+    }
+
+    /// Returns the index of the first Pokémon, out of the battlefield, that still can fight
+    pub fn next_healthy_index(&self) -> Option<usize> {
+        self.battlers.iter()
+            .enumerate()
+            .position(|(idx, battler)| !self.is_active(idx) && !battler.is_fainted())
+    }
+
+    /// Checks that the Pokémon at `party_index` exists, it's not on the battlefield and it's able to fight
+    pub fn can_switch_to(&self, party_index: usize) -> bool {
+        match self.battlers.get(party_index) {
+            Some(battler) => !self.is_active(party_index) && !battler.is_fainted(),
+            None => false
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod battle_engine;
+pub mod battle_ai;
+pub mod battle_event;
+pub mod battle_hooks;
+pub mod battle_log;
+pub mod battle_weather;
+pub mod battler;
+pub mod battle_scene;
+pub mod battle_ui;
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,55 @@
+use crate::game::personality::{Personality, TrainerIds};
+use crate::game::pokemon::Pokemon;
+use crate::game::pokemon_stats::StatSet;
+use crate::game::species_registry::pokemon_specie_by_id;
+use crate::utils::all_pokemon_moves::pokemon_move_by_name;
+
+use super::battle_ai::BattleAi;
+use super::battle_engine::{BattleAction, BattleEngine};
+use super::battler::{BattleMove, BattleSideId, BattleStats, Battler, BattlerPosition};
+
+/// A team with the species of the data file at the given levels. Every Pokémon has the same IVs, and a personality that
+/// only depends on his specie, so the teams are the same on every run
+pub fn team(species: &[(i32, i32)]) -> Vec<Battler> {
+    species.iter()
+        .map(|&(id, level)| {
+            let specie = pokemon_specie_by_id(id).expect("The specie must be on the species data");
+            let personality = Personality::new(id as u32 * 7919, TrainerIds::new(1234, 5678));
+            Battler::from_pokemon(Pokemon::from_specie(specie, level, StatSet::uniform(15), personality))
+        })
+        .collect()
+}
+
+/// A Pokémon of the given specie that only knows Placaje, with the given stats, so the battles don't depend on the species data
+pub fn battler(specie_id: i32, stats: BattleStats) -> Battler {
+    let specie = pokemon_specie_by_id(specie_id).expect("The specie must be on the species data");
+    let tackle = pokemon_move_by_name("Placaje").expect("Placaje must be on the moves data");
+    Battler::new(Pokemon::new_pokemon(specie.id, specie.name.to_owned()), 10, stats, vec![BattleMove::from_move(&tackle, tackle.pp)])
+}
+
+/// Plays a started battle until it ends, or until the given turn, with every side driven by his AI.
+/// Returns every decision that the AIs took, on the order they took them, the replacements of the fainted Pokémon included
+pub fn play_with_ais(engine: &mut BattleEngine, mut player_ai: Box<dyn BattleAi>, mut opponent_ai: Box<dyn BattleAi>, max_turns: i32)
+    -> Vec<(BattlerPosition, BattleAction)> {
+    let mut decisions = Vec::new();
+    while !engine.is_over() && engine.turn() < max_turns {
+        let actions: Vec<(BattlerPosition, BattleAction)> = engine.standing_positions().into_iter()
+            .map(|position| match position.side {
+                BattleSideId::Player => (position, player_ai.choose_action(engine, position)),
+                BattleSideId::Opponent => (position, opponent_ai.choose_action(engine, position))
+            })
+            .collect();
+        decisions.extend(actions.iter().cloned());
+        engine.play_turn_with(actions).unwrap();
+        for side in [BattleSideId::Player, BattleSideId::Opponent] {
+            for position in engine.positions_to_replace(side) {
+                let ai = if side == BattleSideId::Player { &mut player_ai } else { &mut opponent_ai };
+                if let Some(party_index) = ai.choose_replacement(engine, position) {
+                    decisions.push((position, BattleAction::Switch { party_index }));
+                    engine.replace_fainted(position, party_index).unwrap();
+                }
+            }
+        }
+    }
+    decisions
+}
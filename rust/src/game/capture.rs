@@ -0,0 +1,118 @@
+use rand::Rng;
+
+use super::status_condition::MajorStatus;
+
+/// The times that the ball shakes before the Pokémon gets caught
+pub const CAPTURE_SHAKES: i32 = 4;
+/// The catch value from which the Pokémon gets caught without any shake check
+pub const MAX_CATCH_VALUE: f64 = 255.0;
+
+/// The result of throwing a ball to a wild Pokémon
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CaptureAttempt {
+    /// How many times the ball shook before the Pokémon broke free, or `CAPTURE_SHAKES` when it got caught
+    pub shakes: i32,
+    pub caught: bool,
+}
+
+/// The bonus that the status conditions give to the catch chance. Sleeping and frozen Pokémon are the easiest ones
+pub fn status_bonus(status: Option<MajorStatus>) -> f64 {
+    match status {
+        Some(MajorStatus::Sleep { .. }) | Some(MajorStatus::Freeze) => 2.0,
+        Some(_) => 1.5,
+        None => 1.0
+    }
+}
+
+/// The modified catch rate of the wild Pokémon, from 0 to 255.
+///
+/// Grows when the Pokémon has less HP left, with the multiplier of the ball and with the status conditions
+pub fn catch_value(max_hp: i32, current_hp: i32, catch_rate: i32, ball_multiplier: f64, status: Option<MajorStatus>) -> f64 {
+    let max_hp = max_hp.max(1) as f64;
+    let current_hp = current_hp.clamp(0, max_hp as i32) as f64;
+    let hp_factor = (3.0 * max_hp - 2.0 * current_hp) / (3.0 * max_hp);
+
+    (hp_factor * catch_rate as f64 * ball_multiplier * status_bonus(status)).min(MAX_CATCH_VALUE)
+}
+
+/// The value that every one of the shake checks must beat, from 0 to 65535
+pub fn shake_threshold(catch_value: f64) -> i32 {
+    if catch_value >= MAX_CATCH_VALUE {
+        return 65536;
+    }
+    (65536.0 / (MAX_CATCH_VALUE / catch_value.max(1.0)).powf(0.25)) as i32
+}
+
+/// Throws a ball to a wild Pokémon. Every shake check that passes makes the ball shake once more,
+/// and the Pokémon it's caught when it passes all of them
+pub fn try_capture<R: Rng>(max_hp: i32, current_hp: i32, catch_rate: i32, ball_multiplier: f64,
+    status: Option<MajorStatus>, rng: &mut R) -> CaptureAttempt
+{
+    let threshold = shake_threshold(catch_value(max_hp, current_hp, catch_rate, ball_multiplier, status));
+
+    let mut shakes = 0;
+    while shakes < CAPTURE_SHAKES {
+        if rng.gen_range(0..65536) >= threshold {
+            return CaptureAttempt { shakes, caught: false };
+        }
+        shakes += 1;
+    }
+    CaptureAttempt { shakes, caught: true }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn sleep_and_freeze_give_the_biggest_status_bonus() {
+        assert_eq!(status_bonus(None), 1.0);
+        assert_eq!(status_bonus(Some(MajorStatus::Sleep { turns_left: 2 })), 2.0);
+        assert_eq!(status_bonus(Some(MajorStatus::Freeze)), 2.0);
+        for status in [MajorStatus::Poison, MajorStatus::BadPoison, MajorStatus::Burn, MajorStatus::Paralysis] {
+            assert_eq!(status_bonus(Some(status)), 1.5, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn the_catch_value_grows_as_the_hp_goes_down_up_to_the_cap() {
+        // At full HP the catch value is a third of the catch rate, and it nearly reaches it with no HP left
+        assert_eq!(catch_value(90, 90, 45, 1.0, None), 15.0);
+        assert_eq!(catch_value(90, 0, 45, 1.0, None), 45.0);
+        assert_eq!(catch_value(90, 45, 45, 1.0, None), 30.0);
+        // The HP out of range are clamped, and a Pokémon without max HP doesn't divide by zero
+        assert_eq!(catch_value(90, 200, 45, 1.0, None), catch_value(90, 90, 45, 1.0, None));
+        assert_eq!(catch_value(90, -5, 45, 1.0, None), catch_value(90, 0, 45, 1.0, None));
+        assert_eq!(catch_value(0, 0, 45, 1.0, None), 45.0);
+
+        assert_eq!(catch_value(90, 90, 45, 2.0, Some(MajorStatus::Freeze)), 60.0);
+        assert_eq!(catch_value(90, 0, 255, 1.0, None), MAX_CATCH_VALUE);
+        assert_eq!(catch_value(90, 0, 255, 255.0, Some(MajorStatus::Freeze)), MAX_CATCH_VALUE);
+    }
+
+    #[test]
+    fn the_shake_threshold_is_guaranteed_from_the_max_catch_value() {
+        assert_eq!(shake_threshold(MAX_CATCH_VALUE), 65536);
+        assert_eq!(shake_threshold(MAX_CATCH_VALUE * 10.0), 65536);
+        assert_eq!(shake_threshold(254.9), 65529);
+        assert_eq!(shake_threshold(127.5), 55108);
+        // Catch values below 1 still have a small chance
+        assert_eq!(shake_threshold(1.0), 16400);
+        assert_eq!(shake_threshold(0.0), shake_threshold(1.0));
+    }
+
+    #[test]
+    fn a_capped_catch_value_always_catches_and_a_low_one_can_break_free() {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        for _ in 0..1000 {
+            let attempt = try_capture(90, 1, 255, 255.0, None, &mut rng);
+            assert_eq!(attempt, CaptureAttempt { shakes: CAPTURE_SHAKES, caught: true });
+        }
+
+        let attempts: Vec<CaptureAttempt> = (0..1000).map(|_| try_capture(300, 300, 3, 1.0, None, &mut rng)).collect();
+        assert!(attempts.iter().any(|attempt| !attempt.caught));
+        assert!(attempts.iter().all(|attempt| attempt.caught == (attempt.shakes == CAPTURE_SHAKES)));
+    }
+}
@@ -19,6 +19,17 @@ impl GameCity {
         vec![Self::CampoDePruebas, Self::PuebloDeTeo, Self::SantiagoDeCompostela, Self::ACoruna, Self::Ames]
     }
 
+    /// Converts back a formatted city name (like "Pueblo de Teo") to his `GameCity` variant
+    pub fn from_fmt_string<S: AsRef<str>>(name: S) -> Option<GameCity> {
+        Self::values().into_iter().find(|game_city| game_city.to_fmt_string() == name.as_ref())
+    }
+
+    /// Finds the city or town whose location scene has the given node name (like "PuebloDeTeo")
+    pub fn from_node_name<S: AsRef<str>>(node_name: S) -> Option<GameCity> {
+        Self::values().into_iter()
+            .find(|game_city| City::new(game_city.to_fmt_string(), None).get_as_node_path() == node_name.as_ref())
+    }
+
     pub fn to_fmt_string(&self) -> &'static str {
         match self {
             Self::CampoDePruebas => "Campo de Pruebas",
@@ -72,11 +72,20 @@ pub mod database {
             db_resource
         }
 
+        /// Prints the tables of the database and the rows of the Pokémon one.
+        ///
+        /// The tables are only built when the `debug_tables` property of the `PokemonDB` is enabled, so without them there's nothing to print
         fn debug_database_info(&self, database: TRef<Node>) {
             for num in 0..database.get_child_count() {
                 godot_print!("Database Tables {:?}", unsafe { database.get_child(num).unwrap().assume_safe().name() })
             }
-            let pokemon_table = unsafe { database.get_child(0).unwrap().assume_safe() };
+            let pokemon_table = match database.get_child(0) {
+                Some(pokemon_table) => unsafe { pokemon_table.assume_safe() },
+                None => {
+                    godot_print!("The database doesn't have debug tables. Enable `debug_tables` on the PokemonDB to build them");
+                    return;
+                }
+            };
             for num in 0..pokemon_table.get_child_count() {
                 godot_print!("Pokémon row NODE name: {:?}", unsafe { pokemon_table.get_child(num).unwrap().assume_safe().name() });
                 godot_print!("Pokémon ID: {:?}", unsafe { pokemon_table.get_child(num).unwrap().assume_safe().get("id").to::<i64>() })
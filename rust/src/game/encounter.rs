@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use chrono::{NaiveTime, Timelike};
+use rand::Rng;
+
+use super::game::{DayNightCycle, Weather};
+
+/// The area used by the tall grass that doesn't specify his own one
+pub const DEFAULT_ENCOUNTER_AREA: &str = "Hierba alta";
+/// The hour when the morning ends and the day starts, for the encounters
+pub const MORNING_END_HOUR: u32 = 10;
+
+/// The parts of the day that changes the wild Pokémon that can be found
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum EncounterTime {
+    Morning,
+    Day,
+    Night,
+}
+
+impl EncounterTime {
+    /// The part of the day from the real `DayNightCycle`. The morning it's the first part of the day, until `MORNING_END_HOUR`
+    pub fn from_day_night_cycle(day_night_cycle: &DayNightCycle, current_time: NaiveTime) -> EncounterTime {
+        match day_night_cycle {
+            DayNightCycle::Night => Self::Night,
+            _ if current_time.hour() < MORNING_END_HOUR => Self::Morning,
+            _ => Self::Day
+        }
+    }
+}
+
+/// The real conditions where the player it's looking for wild Pokémon
+#[derive(PartialEq, Clone, Debug)]
+pub struct EncounterConditions {
+    pub time: EncounterTime,
+    pub weather: Weather,
+}
+
+impl EncounterConditions {
+    pub fn new(time: EncounterTime, weather: Weather) -> Self {
+        Self { time, weather }
+    }
+}
+
+/// One of the Pokémon that can appear on an area, with the levels that it can have and how often it appears
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterSlot {
+    /// The Pokédex number of the specie
+    pub specie_id: i32,
+    pub min_level: i32,
+    pub max_level: i32,
+    /// The weight of this slot against the rest of slots of the table. Higher means more common
+    pub rarity: i32,
+    /// The parts of the day when this slot can appear. Empty means at any time
+    #[serde(default)]
+    pub times: Vec<EncounterTime>,
+    /// The real weathers of the city when this slot can appear. Empty means with any weather
+    #[serde(default)]
+    pub weathers: Vec<Weather>,
+}
+
+impl EncounterSlot {
+    /// True when the slot can appear on the given conditions
+    pub fn is_available(&self, conditions: &EncounterConditions) -> bool {
+        (self.times.is_empty() || self.times.contains(&conditions.time)) &&
+            (self.weathers.is_empty() || self.weathers.contains(&conditions.weather))
+    }
+}
+
+/// The wild Pokémon that lives on an area of a city or town, as it's stored on the `godot/Encounters/*.json` files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterTable {
+    /// The name of the area, that must match the `encounter_area` property of the `TallGrass` nodes
+    pub area: String,
+    /// The chance, as a percentage, of finding a wild Pokémon on every step inside the area
+    pub encounter_rate: i32,
+    /// The chance, as a percentage, that a second wild Pokémon joins the encounter, starting a double battle
+    #[serde(default)]
+    pub double_battle_rate: i32,
+    pub slots: Vec<EncounterSlot>,
+}
+
+/// A wild Pokémon that has just appeared in front of the player
+#[derive(Debug, Clone, PartialEq)]
+pub struct WildEncounter {
+    pub specie_id: i32,
+    pub level: i32,
+}
+
+impl EncounterTable {
+    /// Checks if a wild Pokémon appears on this step, and picks which one by the rarity of the slots available
+    /// on the current conditions
+    pub fn roll_encounter<R: Rng>(&self, conditions: &EncounterConditions, rng: &mut R) -> Option<WildEncounter> {
+        if rng.gen_range(0..100) >= self.encounter_rate {
+            return None;
+        }
+        self.pick_slot(conditions, rng).map(|slot| WildEncounter {
+            specie_id: slot.specie_id,
+            level: rng.gen_range(slot.min_level..=slot.max_level.max(slot.min_level)),
+        })
+    }
+
+    /// Checks if another wild Pokémon of the area joins the one that just appeared, for a double battle
+    pub fn roll_partner<R: Rng>(&self, conditions: &EncounterConditions, rng: &mut R) -> Option<WildEncounter> {
+        if rng.gen_range(0..100) >= self.double_battle_rate {
+            return None;
+        }
+        self.pick_slot(conditions, rng).map(|slot| WildEncounter {
+            specie_id: slot.specie_id,
+            level: rng.gen_range(slot.min_level..=slot.max_level.max(slot.min_level)),
+        })
+    }
+
+    /// Picks a random slot between the available ones, where every slot has a chance proportional to his rarity
+    fn pick_slot<R: Rng>(&self, conditions: &EncounterConditions, rng: &mut R) -> Option<&EncounterSlot> {
+        let available_slots: Vec<&EncounterSlot> = self.slots.iter()
+            .filter(|slot| slot.is_available(conditions))
+            .collect();
+        let total_rarity: i32 = available_slots.iter().map(|slot| slot.rarity.max(0)).sum();
+        if total_rarity <= 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total_rarity);
+        for slot in available_slots {
+            if roll < slot.rarity.max(0) {
+                return Some(slot);
+            }
+            roll -= slot.rarity.max(0);
+        }
+        None
+    }
+}
+
+/// Finds the table of the given area between all the tables of a city
+pub fn table_for_area<'a>(tables: &'a [EncounterTable], area: &str) -> Option<&'a EncounterTable> {
+    tables.iter().find(|table| table.area == area)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn slot(specie_id: i32, rarity: i32, times: Vec<EncounterTime>, weathers: Vec<Weather>) -> EncounterSlot {
+        EncounterSlot { specie_id, min_level: 3, max_level: 5, rarity, times, weathers }
+    }
+
+    fn table(encounter_rate: i32, slots: Vec<EncounterSlot>) -> EncounterTable {
+        EncounterTable { area: DEFAULT_ENCOUNTER_AREA.to_string(), encounter_rate, double_battle_rate: 0, slots }
+    }
+
+    /// The species picked on many rolls, to check which slots can appear
+    fn picked_species(table: &EncounterTable, conditions: &EncounterConditions) -> Vec<i32> {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut species: Vec<i32> = (0..500)
+            .filter_map(|_| table.pick_slot(conditions, &mut rng).map(|slot| slot.specie_id))
+            .collect();
+        species.sort();
+        species.dedup();
+        species
+    }
+
+    #[test]
+    fn an_empty_table_never_finds_a_pokemon() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let conditions = EncounterConditions::new(EncounterTime::Day, Weather::Sun);
+        let empty_table = table(100, Vec::new());
+        assert!((0..100).all(|_| empty_table.roll_encounter(&conditions, &mut rng).is_none()));
+        assert!(empty_table.pick_slot(&conditions, &mut rng).is_none());
+    }
+
+    #[test]
+    fn the_encounter_rate_decides_if_a_pokemon_appears() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let conditions = EncounterConditions::new(EncounterTime::Day, Weather::Sun);
+        let never = table(0, vec![slot(1, 10, Vec::new(), Vec::new())]);
+        let always = table(100, vec![slot(1, 10, Vec::new(), Vec::new())]);
+        for _ in 0..100 {
+            assert!(never.roll_encounter(&conditions, &mut rng).is_none());
+            let wild_encounter = always.roll_encounter(&conditions, &mut rng).unwrap();
+            assert_eq!(wild_encounter.specie_id, 1);
+            assert!((3..=5).contains(&wild_encounter.level));
+        }
+    }
+
+    #[test]
+    fn only_the_slots_of_the_current_time_and_weather_appear() {
+        let area_table = table(100, vec![
+            slot(1, 10, Vec::new(), Vec::new()),
+            slot(54, 10, Vec::new(), vec![Weather::Rain, Weather::Drizzle]),
+            slot(5, 10, vec![EncounterTime::Night], Vec::new()),
+            slot(60, 10, vec![EncounterTime::Morning], vec![Weather::Rain]),
+        ]);
+        let picked = |time, weather| picked_species(&area_table, &EncounterConditions::new(time, weather));
+
+        assert_eq!(picked(EncounterTime::Day, Weather::Sun), vec![1]);
+        assert_eq!(picked(EncounterTime::Day, Weather::Drizzle), vec![1, 54]);
+        assert_eq!(picked(EncounterTime::Night, Weather::Rain), vec![1, 5, 54]);
+        assert_eq!(picked(EncounterTime::Morning, Weather::Rain), vec![1, 54, 60]);
+        assert_eq!(picked(EncounterTime::Morning, Weather::Sun), vec![1]);
+
+        let night_only = table(100, vec![slot(5, 10, vec![EncounterTime::Night], Vec::new())]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert!(night_only.roll_encounter(&EncounterConditions::new(EncounterTime::Day, Weather::Sun), &mut rng).is_none());
+    }
+
+    #[test]
+    fn the_slots_appear_proportionally_to_their_rarity() {
+        let area_table = table(100, vec![
+            slot(1, 90, Vec::new(), Vec::new()),
+            slot(4, 10, Vec::new(), Vec::new()),
+            slot(7, 0, Vec::new(), Vec::new()),
+        ]);
+        let conditions = EncounterConditions::new(EncounterTime::Day, Weather::Sun);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let picks: Vec<i32> = (0..10_000).map(|_| area_table.pick_slot(&conditions, &mut rng).unwrap().specie_id).collect();
+
+        let common = picks.iter().filter(|specie_id| **specie_id == 1).count();
+        assert!((8_700..=9_300).contains(&common), "the common slot appeared {} times", common);
+        assert!(!picks.contains(&7));
+    }
+}
@@ -0,0 +1,178 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::city::GameCity;
+use super::game::DayNightCycle;
+use super::pokemon::Pokemon;
+use super::pokemon_specie::PokemonSpecie;
+
+/// The friendship that a Pokémon has when it's caught or received
+pub const BASE_FRIENDSHIP: i32 = 70;
+/// The highest friendship value
+pub const MAX_FRIENDSHIP: i32 = 255;
+/// The friendship that most of the friendship based evolutions requires
+pub const HIGH_FRIENDSHIP: i32 = 220;
+/// Every how many steps walked by the player the Pokémon of his team gain a point of friendship
+pub const FRIENDSHIP_STEPS: i32 = 128;
+
+/// The friendship that a Pokémon gains when it levels up. The friendlier it already is, the slower it grows
+pub fn level_up_friendship(friendship: i32) -> i32 {
+    match friendship {
+        friendship if friendship < 100 => 5,
+        friendship if friendship < 200 => 3,
+        _ => 2
+    }
+}
+
+/// The event that makes a Pokémon check if it's able to evolve
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub enum EvolutionTrigger {
+    /// Checked every time that the Pokémon gains a level
+    LevelUp,
+    /// Checked when the player uses an item (like an evolutionary stone) over the Pokémon.
+    /// The species that evolve by trading on other games use the Cordón Unión, because there aren't trades on this one
+    UseItem { item_name: String },
+}
+
+/// An extra requirement that must be satisfied, besides the trigger, for an evolution to happen
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub enum EvolutionCondition {
+    MinLevel { level: i32 },
+    MinFriendship { friendship: i32 },
+    /// Only evolves at the given real world part of the day
+    TimeOfDay { day_night_cycle: DayNightCycle },
+    /// Only evolves when the player it's on the given city
+    Location { city: GameCity },
+}
+
+/// One of the possible evolutions of a specie
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub struct Evolution {
+    /// Pokédex number of the specie that this one evolves into
+    pub evolves_into: i32,
+    pub trigger: EvolutionTrigger,
+    pub conditions: Vec<EvolutionCondition>,
+}
+
+impl Evolution {
+    pub fn new(evolves_into: i32, trigger: EvolutionTrigger, conditions: Vec<EvolutionCondition>) -> Self {
+        Self { evolves_into, trigger, conditions }
+    }
+
+    /// The classic evolution that happens when the Pokémon reaches certain level
+    pub fn by_level(evolves_into: i32, level: i32) -> Self {
+        Self::new(evolves_into, EvolutionTrigger::LevelUp, vec![EvolutionCondition::MinLevel { level }])
+    }
+
+    /// True when the given Pokémon, on the given situation, satisfies the trigger and all the conditions
+    pub fn is_satisfied_by(&self, pokemon: &Pokemon, context: &EvolutionContext) -> bool {
+        self.trigger == context.trigger &&
+            self.conditions.iter().all(|condition| match condition {
+                EvolutionCondition::MinLevel { level } => pokemon.level >= *level,
+                EvolutionCondition::MinFriendship { friendship } => pokemon.friendship >= *friendship,
+                EvolutionCondition::TimeOfDay { day_night_cycle } => context.day_night_cycle == *day_night_cycle,
+                EvolutionCondition::Location { city } => context.location.as_ref() == Some(city),
+            })
+    }
+}
+
+/// The situation in which an evolution it's being checked
+#[derive(PartialEq, Clone, Debug)]
+pub struct EvolutionContext {
+    pub trigger: EvolutionTrigger,
+    pub day_night_cycle: DayNightCycle,
+    pub location: Option<GameCity>,
+}
+
+impl EvolutionContext {
+    pub fn new(trigger: EvolutionTrigger, day_night_cycle: DayNightCycle, location: Option<GameCity>) -> Self {
+        Self { trigger, day_night_cycle, location }
+    }
+}
+
+/// Decides if the Pokémon evolves in the given situation.
+///
+/// Returns the first of the evolutions of his specie that it's satisfied, if any. The `specie` must be the one of the Pokémon.
+pub fn evaluate_evolution<'a>(pokemon: &Pokemon, specie: &'a PokemonSpecie, context: &EvolutionContext) -> Option<&'a Evolution> {
+    specie.evolutions.iter().find(|evolution| evolution.is_satisfied_by(pokemon, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::personality::{Personality, TrainerIds};
+    use crate::game::pokemon_stats::StatSet;
+    use crate::game::species_registry::pokemon_specie_by_id;
+
+    fn pokemon(specie_id: i32, level: i32) -> Pokemon {
+        let specie = pokemon_specie_by_id(specie_id).expect("The specie must be on the species data");
+        Pokemon::from_specie(specie, level, StatSet::uniform(15), Personality::new(42, TrainerIds::new(1, 2)))
+    }
+
+    fn context(trigger: EvolutionTrigger) -> EvolutionContext {
+        EvolutionContext::new(trigger, DayNightCycle::Day, Some(GameCity::SantiagoDeCompostela))
+    }
+
+    fn use_item(item_name: &str) -> EvolutionTrigger {
+        EvolutionTrigger::UseItem { item_name: item_name.to_string() }
+    }
+
+    #[test]
+    fn evolves_by_level_only_when_leveling_up_at_the_level() {
+        let charmander = pokemon_specie_by_id(4).unwrap();
+        let level_up = context(EvolutionTrigger::LevelUp);
+        assert_eq!(evaluate_evolution(&pokemon(4, 15), charmander, &level_up), None);
+        assert_eq!(evaluate_evolution(&pokemon(4, 16), charmander, &level_up).map(|evolution| evolution.evolves_into), Some(5));
+        assert_eq!(evaluate_evolution(&pokemon(4, 16), charmander, &context(use_item("Piedra Fuego"))), None);
+    }
+
+    #[test]
+    fn the_item_evolutions_need_that_same_item() {
+        let kadabra = pokemon_specie_by_id(64).unwrap();
+        let linking_cord = context(use_item("Cordón Unión"));
+        assert_eq!(evaluate_evolution(&pokemon(64, 20), kadabra, &linking_cord).map(|evolution| evolution.evolves_into), Some(65));
+        assert_eq!(evaluate_evolution(&pokemon(64, 20), kadabra, &context(use_item("Piedra Lunar"))), None);
+        assert_eq!(evaluate_evolution(&pokemon(64, 100), kadabra, &context(EvolutionTrigger::LevelUp)), None);
+    }
+
+    #[test]
+    fn every_condition_must_be_satisfied() {
+        let evolution = Evolution::new(26, EvolutionTrigger::LevelUp, vec![
+            EvolutionCondition::MinFriendship { friendship: HIGH_FRIENDSHIP },
+            EvolutionCondition::TimeOfDay { day_night_cycle: DayNightCycle::Day },
+            EvolutionCondition::Location { city: GameCity::SantiagoDeCompostela },
+        ]);
+        let mut friendly = pokemon(25, 10);
+        friendly.friendship = HIGH_FRIENDSHIP;
+        let mut unfriendly = pokemon(25, 10);
+        unfriendly.friendship = HIGH_FRIENDSHIP - 1;
+
+        assert!(evolution.is_satisfied_by(&friendly, &context(EvolutionTrigger::LevelUp)));
+        assert!(!evolution.is_satisfied_by(&unfriendly, &context(EvolutionTrigger::LevelUp)));
+        let at_night = EvolutionContext::new(EvolutionTrigger::LevelUp, DayNightCycle::Night, Some(GameCity::SantiagoDeCompostela));
+        assert!(!evolution.is_satisfied_by(&friendly, &at_night));
+        let elsewhere = EvolutionContext::new(EvolutionTrigger::LevelUp, DayNightCycle::Day, Some(GameCity::ACoruna));
+        assert!(!evolution.is_satisfied_by(&friendly, &elsewhere));
+        let nowhere = EvolutionContext::new(EvolutionTrigger::LevelUp, DayNightCycle::Day, None);
+        assert!(!evolution.is_satisfied_by(&friendly, &nowhere));
+    }
+
+    #[test]
+    fn the_friendship_grows_slower_the_friendlier_it_is_and_stops_at_the_top() {
+        assert_eq!(level_up_friendship(BASE_FRIENDSHIP), 5);
+        assert_eq!(level_up_friendship(99), 5);
+        assert_eq!(level_up_friendship(100), 3);
+        assert_eq!(level_up_friendship(199), 3);
+        assert_eq!(level_up_friendship(200), 2);
+
+        let mut pikachu = pokemon(25, 10);
+        assert_eq!(pikachu.friendship, BASE_FRIENDSHIP);
+        let specie = pokemon_specie_by_id(25).unwrap();
+        pikachu.gain_experience(specie, specie.growth_rate.experience_for_level(12) - pikachu.experience);
+        assert_eq!(pikachu.friendship, BASE_FRIENDSHIP + 10);
+
+        pikachu.friendship = MAX_FRIENDSHIP - 1;
+        assert_eq!(pikachu.gain_friendship(5), 1);
+        assert_eq!(pikachu.friendship, MAX_FRIENDSHIP);
+    }
+}
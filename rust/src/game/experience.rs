@@ -0,0 +1,141 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::pokemon_stats::{self, StatSet};
+
+/// The curve that sets how much experience a Pokémon needs to reach every level.
+///
+/// Every specie has one of those six curves.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, Serialize, Deserialize)]
+pub enum GrowthRate {
+    Fast,
+    MediumFast,
+    MediumSlow,
+    Slow,
+    Erratic,
+    Fluctuating,
+}
+
+impl Default for GrowthRate {
+    fn default() -> Self { GrowthRate::MediumFast }
+}
+
+impl GrowthRate {
+    /// The total experience that a Pokémon needs to have to be at the given level
+    pub fn experience_for_level(&self, level: i32) -> i32 {
+        let n = level.clamp(pokemon_stats::MIN_LEVEL, pokemon_stats::MAX_LEVEL) as i64;
+        // Every Pokémon starts at level 1 with zero experience points, whatever it's curve is
+        if n == 1 {
+            return 0;
+        }
+        let cube = n * n * n;
+
+        let experience = match self {
+            Self::Fast => 4 * cube / 5,
+            Self::MediumFast => cube,
+            Self::MediumSlow => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+            Self::Slow => 5 * cube / 4,
+            Self::Erratic => match n {
+                n if n < 50 => cube * (100 - n) / 50,
+                n if n < 68 => cube * (150 - n) / 100,
+                n if n < 98 => cube * ((1911 - 10 * n) / 3) / 500,
+                n => cube * (160 - n) / 100
+            },
+            Self::Fluctuating => match n {
+                n if n < 15 => cube * ((n + 1) / 3 + 24) / 50,
+                n if n < 36 => cube * (n + 14) / 50,
+                n => cube * (n / 2 + 32) / 50
+            }
+        };
+
+        experience.max(0) as i32
+    }
+
+    /// The level that a Pokémon with the given amount of total experience has
+    pub fn level_for_experience(&self, experience: i32) -> i32 {
+        (pokemon_stats::MIN_LEVEL..=pokemon_stats::MAX_LEVEL)
+            .take_while(|level| self.experience_for_level(*level) <= experience)
+            .last()
+            .unwrap_or(pokemon_stats::MIN_LEVEL)
+    }
+
+    // Returns the growth rate's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Fast => "Rápido",
+            Self::MediumFast => "Medio",
+            Self::MediumSlow => "Parabólico",
+            Self::Slow => "Lento",
+            Self::Erratic => "Errático",
+            Self::Fluctuating => "Fluctuante",
+        }
+    }
+}
+
+/// Describes one level gained by a Pokémon, so the game can show the new stats and teach the new moves
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub struct LevelUpEvent {
+    pub new_level: i32,
+    /// How much every stat has increased with this level
+    pub stat_deltas: StatSet,
+    /// The names of the moves that the Pokémon can learn at this level
+    pub new_moves: Vec<String>,
+}
+
+/// The experience points earned by every Pokémon that took part on defeating another one.
+///
+/// `(a * b * L) / (7 * s)`, where `a` it's 1.5 on trainer battles and 1 on wild ones, `b` it's the base experience
+/// yield of the defeated specie, `L` the defeated Pokémon's level and `s` the number of participants
+pub fn experience_gain(base_experience_yield: i32, defeated_level: i32, trainer_battle: bool, participants: i32) -> i32 {
+    let mut experience = base_experience_yield * defeated_level / 7;
+    if trainer_battle {
+        experience = experience * 3 / 2;
+    }
+    (experience / participants.max(1)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The experience needed at the levels 10, 50 and 100, as the games list them
+    const KNOWN_EXPERIENCE: [(GrowthRate, [i32; 3]); 6] = [
+        (GrowthRate::Fast, [800, 100000, 800000]),
+        (GrowthRate::MediumFast, [1000, 125000, 1000000]),
+        (GrowthRate::MediumSlow, [560, 117360, 1059860]),
+        (GrowthRate::Slow, [1250, 156250, 1250000]),
+        (GrowthRate::Erratic, [1800, 125000, 600000]),
+        (GrowthRate::Fluctuating, [540, 142500, 1640000]),
+    ];
+
+    #[test]
+    fn every_growth_rate_matches_the_known_experience() {
+        for (growth_rate, experience) in KNOWN_EXPERIENCE.iter() {
+            assert_eq!(growth_rate.experience_for_level(1), 0, "{:?} at level 1", growth_rate);
+            for (level, expected) in [10, 50, 100].iter().zip(experience.iter()) {
+                assert_eq!(growth_rate.experience_for_level(*level), *expected, "{:?} at level {}", growth_rate, level);
+            }
+        }
+    }
+
+    #[test]
+    fn the_level_goes_up_only_when_the_experience_is_reached() {
+        for (growth_rate, _) in KNOWN_EXPERIENCE.iter() {
+            for level in pokemon_stats::MIN_LEVEL..=pokemon_stats::MAX_LEVEL {
+                let experience = growth_rate.experience_for_level(level);
+                assert_eq!(growth_rate.level_for_experience(experience), level, "{:?} at level {}", growth_rate, level);
+                if level > pokemon_stats::MIN_LEVEL {
+                    assert_eq!(growth_rate.level_for_experience(experience - 1), level - 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_experience_is_split_between_the_participants() {
+        assert_eq!(experience_gain(64, 10, false, 1), 91);
+        assert_eq!(experience_gain(64, 10, true, 1), 136);
+        assert_eq!(experience_gain(64, 10, true, 2), 68);
+        assert_eq!(experience_gain(1, 1, false, 3), 1);
+    }
+}
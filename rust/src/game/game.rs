@@ -7,15 +7,34 @@ use gdnative::api::{HTTPClient, HTTPRequest};
 use serde::{Deserialize, Serialize};
 
 use crate::utils::{consts::game_consts, networking, utils};
-use crate::game::player::{PlayerData, PlayerDirection};
+use crate::game::player::{CaughtPokemonDestination, PlayerData, PlayerDirection};
 
 use chrono::{Duration, NaiveTime};
+use rand::Rng;
 
 use super::code_abstractions::database::Database;
+use super::code_abstractions::signals::RegisterSignal;
 use super::city::{GameCity, City, CityWeather};
+use super::battle::battle_ai::{AiDifficulty, BattleAi};
+use super::battle::battle_engine::BattleEngine;
+use super::battle::battle_event::{BattleFormat, BattleKind, BattleOutcome};
+use super::battle::battle_weather::BattleWeather;
+use super::battle::battle_scene::BattleScene;
+use super::battle::battler::{BattleSide, BattleSideId, Battler};
+use super::pokemon::Pokemon;
+use super::pokemon_specie::PokemonSpecie;
+use super::evolution::{self, EvolutionContext, EvolutionTrigger};
+use super::game_item::ItemEffect;
+use super::status_condition;
+use super::encounter::{self, EncounterConditions, EncounterTable, EncounterTime};
+use super::game_options::GameOptions;
+use crate::game::species_registry::pokemon_specie_by_id;
+use crate::utils::all_game_items::game_item_by_name;
+use crate::utils::all_trainers::trainer_by_id;
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
+#[register_with(Self::register_signal)]
 #[derive(Debug, Serialize, Deserialize)]
 #[derive(Clone)]
 pub struct Game {
@@ -31,10 +50,25 @@ pub struct Game {
 
     // The struct that will hold all necesary Player Data
     player_data: PlayerData,
+    // The preferences chosen on the Options screen
+    #[serde(default)]
+    options: GameOptions,
 
     // A list storing all the availiable locations on the game
     #[serde(skip)]
     game_cities: Vec<City>,
+    // The city or town where the player currently is
+    #[serde(default)]
+    current_city: Option<GameCity>,
+    // The steps that the player has walked since the game started
+    #[serde(skip)]
+    steps_walked: i32,
+    // The wild Pokémon that lives on every area of the current city
+    #[serde(skip)]
+    encounter_tables: Vec<EncounterTable>,
+    // The encounter areas of the tall grass that the player is currently stepping on
+    #[serde(skip)]
+    tall_grass_under_player: Vec<String>,
 
     // Some "trackers"
     received_signals: i32,
@@ -45,6 +79,12 @@ pub struct Game {
     current_scene_type: CurrentSceneType,
     // CurrentScenePath
     current_scene_path: String,
+    // The kind of scene where the player was before entering on a battle, to go back there when it's over
+    #[serde(skip)]
+    scene_type_before_battle: CurrentSceneType,
+    // The id of the trainer that the player it's fighting against, if the current battle it's a trainer one
+    #[serde(skip)]
+    current_trainer: Option<String>,
 
     // Game real time when the game starts
     game_external_data: GameExternalData,
@@ -59,6 +99,8 @@ pub struct Game {
     #[serde(skip)]
     current_scene: Option<Ref<Node>>,
     #[serde(skip)]
+    battle_scene: Option<Ref<Node>>,
+    #[serde(skip)]
     database: Option<TRef<'static, Node>>,
 
     // The current real time in GTM + 1. When game it's saved, stores the time when game has succesfully saved.
@@ -80,6 +122,21 @@ pub struct Game {
 // Impl of database will use the "default implementation of the trait methods"
 impl Database for Game {}
 
+impl RegisterSignal<Self> for Game {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // Notifies the overworld that a Pokémon it's evolving, so the evolution scene can be played
+        builder.signal("pokemon_evolution")
+            .with_param("pokemon_name", VariantType::GodotString)
+            .with_param("evolves_into", VariantType::GodotString)
+            .done();
+        // Notifies the trainers of the overworld that the battle against one of them it's over
+        builder.signal("trainer_battle_finished")
+            .with_param("trainer_id", VariantType::GodotString)
+            .with_param("player_won", VariantType::Bool)
+            .done();
+    }
+}
+
 #[methods]
 impl Game {
     fn new(_owner: &Node2D) -> Self {
@@ -91,8 +148,13 @@ impl Game {
             production_url: "",
             // Initializes a new `PlayerData` struct 
             player_data: PlayerData::new(),
+            options: GameOptions::new(),
             // Locations
             game_cities: Vec::new(),
+            current_city: None,
+            steps_walked: 0,
+            encounter_tables: Vec::new(),
+            tall_grass_under_player: Vec::new(),
             // Next API call
             next_api_call: NaiveTime::from_hms(0, 0, 0),
             // Counters that sync arriving times of different signals
@@ -104,10 +166,13 @@ impl Game {
             current_scene_type: Default::default(),
             // Default path of the game
             current_scene_path: "res://godot/Game/Map.tscn".to_string(),
+            scene_type_before_battle: Default::default(),
+            current_trainer: None,
             // Core nodes to track
             game_node: None,
             world_map_node: None,
             current_scene: None,
+            battle_scene: None,
             // Game data of non game elements
             game_external_data: GameExternalData::new(),
             // Current time
@@ -128,6 +193,11 @@ impl Game {
         base.set_process(true);
         base.add_to_group("save_game_data", false);
 
+        // Takes back the progress of the last saved game that no node of the world sends again, like the bag
+        let saved_game_data = utils::retrieve_game_data();
+        self.player_data.restore_progress(&saved_game_data.player_data);
+        self.options = saved_game_data.options.clone();
+
         // Load the database and add it as a node
         let database = self.database.unwrap();
         base.add_child(database, true);
@@ -380,6 +450,428 @@ impl Game {
     }
 
 
+    /// Leaves the world scene that it's currently being played and starts the given battle.
+    ///
+    /// The world scene isn't freed, just removed from the SceneTree, so `end_battle` can bring it back exactly as it was
+    pub fn start_battle(&mut self, base: &Node2D, engine: BattleEngine, opponent_ai: Box<dyn BattleAi>) {
+        if self.current_scene_type == CurrentSceneType::Battle {
+            godot_print!("Can't start a battle while another one it's being played");
+            return;
+        }
+        // The battle it's played under the real weather of the city where it starts
+        let engine = engine.with_weather(self.battle_weather());
+
+        let scene_transition_animation = unsafe { base.get_node_as::<CanvasLayer>("SceneTransition")
+            .unwrap().get_node("AnimationPlayer").unwrap().assume_safe().cast::<AnimationPlayer>().unwrap()
+        };
+        scene_transition_animation.play("FadeToBlack", -1.0, 0.5, false);
+
+        // Freezes and hides the player while the battle it's running
+        let player = unsafe { base.get_node_as::<Node2D>("Player").unwrap() };
+        unsafe { player.call("handle_interaction", &[Variant::new("battle_active")]) };
+        player.set_visible(false);
+
+        // Removes the current world scene from the SceneTree
+        self.scene_type_before_battle = self.current_scene_type.clone();
+        self.current_scene_type = CurrentSceneType::Battle;
+        let world_scene = self.current_world_scene();
+        unsafe { base.call_deferred("remove_child", &[world_scene.to_variant()]) };
+
+        // Loads the battle scene and gives it the battle to play
+        let battle_scene = unsafe { ResourceLoader::godot_singleton()
+            .load("res://godot/Game/Battle.tscn", "", false)
+            .unwrap().assume_safe()
+            .cast::<PackedScene>()
+            .unwrap()
+            .instance(0)
+            .unwrap().assume_safe() };
+
+        battle_scene.cast::<Node2D>()
+            .and_then(|node| node.cast_instance::<BattleScene>())
+            .expect("Battle.tscn must have a BattleScene as root node")
+            .map_mut(|scene, _| scene.set_engine(engine, opponent_ai))
+            .unwrap();
+
+        self.battle_scene = Some(battle_scene.claim());
+        unsafe { base.call_deferred("add_child", &[battle_scene.to_variant()]) };
+
+        scene_transition_animation.play("FadeToNormal", -1.0, 1.0, false);
+    }
+
+    #[method]
+    /// Receives the `battle_finished` signal of the `BattleScene`. Frees the battle scene and brings back the world
+    /// scene where the player was before the battle started.
+    fn end_battle(&mut self, #[base] base: &Node2D, _outcome: Variant) {
+        if let Some(battle_scene) = self.battle_scene.take() {
+            let battle_scene = unsafe { battle_scene.assume_safe() };
+            // The party keeps the HP, PP, experience and status conditions that it has after the battle
+            let engine = match battle_scene.cast::<Node2D>().and_then(|node| node.cast_instance::<BattleScene>()) {
+                Some(scene) => scene.map_mut(|scene, _| scene.take_engine())
+                    .unwrap_or_else(|err| {
+                        godot_error!("Can't take the results of the battle out of the BattleScene: {:?}", err);
+                        None
+                    }),
+                None => {
+                    godot_error!("The battle scene doesn't have a BattleScene as root node");
+                    None
+                }
+            };
+            if let Some(engine) = engine {
+                // The log of the last battle it's kept, so it can be attached to a bug report and replayed
+                if let Some(battle_log) = engine.log() {
+                    utils::save_battle_log(battle_log);
+                }
+
+                let levels_before: Vec<i32> = self.player_data.party().members().iter()
+                    .map(|pokemon| pokemon.level)
+                    .collect();
+                let mut team: Vec<Pokemon> = engine.side(BattleSideId::Player).battlers.iter()
+                    .cloned()
+                    .map(Battler::into_pokemon)
+                    .collect();
+                // The members that levelled up on the battle, and are still able to fight, can evolve now
+                for (idx, pokemon) in team.iter_mut().enumerate() {
+                    let levelled_up = levels_before.get(idx).map_or(false, |level_before| pokemon.level > *level_before);
+                    if levelled_up && !pokemon.is_fainted() {
+                        self.check_evolution(base, pokemon, EvolutionTrigger::LevelUp);
+                    }
+                }
+                self.player_data.party_mut().set_members(team);
+
+                // Defeated trainers are remembered, so they don't challenge the player again
+                if let Some(trainer_id) = self.current_trainer.take() {
+                    let player_won = engine.outcome() == BattleOutcome::PlayerWon;
+                    if player_won {
+                        self.player_data.register_defeated_trainer(&trainer_id);
+                    }
+                    base.emit_signal("trainer_battle_finished", &[trainer_id.to_variant(), player_won.to_variant()]);
+                }
+
+                if engine.outcome() == BattleOutcome::PokemonCaught {
+                    // The ball can only be thrown when there's one wild Pokémon left, so it's the caught one
+                    if let Some(caught_position) = engine.catchable_position() {
+                        let caught_pokemon = engine.battler(caught_position).clone().into_pokemon();
+                        match self.player_data.add_caught_pokemon(caught_pokemon) {
+                            Ok(CaughtPokemonDestination::Party) => (),
+                            Ok(CaughtPokemonDestination::PcBox(box_index)) =>
+                                godot_print!("The party is full. The caught Pokémon was sent to the box {} of the PC", box_index + 1),
+                            Err(err) => godot_print!("The caught Pokémon couldn't be stored: {}", err)
+                        }
+                    }
+                }
+            }
+            battle_scene.queue_free();
+        }
+
+        self.current_scene_type = self.scene_type_before_battle.clone();
+        let world_scene = self.current_world_scene();
+        unsafe { base.call_deferred("add_child", &[world_scene.to_variant()]) };
+        unsafe { base.call_deferred("move_child", &[world_scene.to_variant(), 0.to_variant()]) };
+
+        // Gives back the control to the player
+        let player = unsafe { base.get_node_as::<Node2D>("Player").unwrap() };
+        player.set_visible(true);
+        unsafe { player.call("handle_interaction", &[Variant::new("")]) };
+    }
+
+    pub fn is_battle_running(&self) -> bool {
+        self.current_scene_type == CurrentSceneType::Battle
+    }
+
+    /// Starts a battle against the team of the given trainer. Returns false when the battle can't start, because the
+    /// trainer doesn't exist or was already defeated, or because the player doesn't have any Pokémon able to fight
+    pub fn start_trainer_battle(&mut self, base: &Node2D, trainer_id: &str) -> bool {
+        if self.is_battle_running() || self.player_data.has_defeated_trainer(trainer_id) {
+            return false;
+        }
+        if !self.player_data.party().has_healthy_member() {
+            return false;
+        }
+        let trainer = match trainer_by_id(trainer_id) {
+            Some(trainer) => trainer,
+            None => {
+                godot_print!("The trainer isn't on the game: {}", trainer_id);
+                return false;
+            }
+        };
+        let trainer_team = trainer.build_team();
+        if trainer_team.is_empty() {
+            return false;
+        }
+
+        for pokemon in trainer_team.iter() {
+            self.player_data.pokedex_mut().register_spotted(pokemon.id);
+        }
+        let player_side = BattleSide::new(
+            self.player_data.name(),
+            self.player_data.party().members().iter().cloned().map(Battler::from_pokemon).collect()
+        );
+        let trainer_side = BattleSide::new(&trainer.name, trainer_team.into_iter().map(Battler::from_pokemon).collect());
+
+        let engine = match BattleEngine::new(BattleKind::Trainer, player_side, trainer_side) {
+            Ok(engine) => engine.with_format(trainer.format),
+            Err(err) => {
+                godot_print!("The battle against {} can't start: {}", trainer.name, err);
+                return false;
+            }
+        };
+        let trainer_ai = trainer.ai.build(rand::random());
+        self.current_trainer = Some(trainer.id);
+        self.start_battle(base, engine, trainer_ai);
+        true
+    }
+
+    /// Returns the scene that represents the world where the player currently is, the Map or an indoors one
+    fn current_world_scene(&self) -> Ref<Node> {
+        match self.current_scene_type {
+            CurrentSceneType::Indoors => self.current_scene.unwrap(),
+            _ => self.world_map_node.unwrap()
+        }
+    }
+
+
+    #[method]
+    /// Receives the name of the city or town that the player has just entered, as it's formatted on `GameCity::to_fmt_string()`
+    fn _on_city_entered(&mut self, city_name: String) {
+        let game_city = GameCity::from_fmt_string(&city_name);
+        if game_city != self.current_city {
+            self.encounter_tables = game_city.as_ref().map_or(Vec::new(), utils::load_encounter_tables);
+        }
+        self.current_city = game_city;
+    }
+
+    #[method]
+    /// Receives the city and the encounter area of the `TallGrass` that the player has just stepped into
+    fn _on_tall_grass_entered(&mut self, city_name: String, encounter_area: String) {
+        self._on_city_entered(city_name);
+        self.tall_grass_under_player.push(encounter_area);
+    }
+
+    #[method]
+    /// Receives the encounter area of the `TallGrass` that the player has just left
+    fn _on_tall_grass_exited(&mut self, encounter_area: String) {
+        if let Some(idx) = self.tall_grass_under_player.iter().position(|area| *area == encounter_area) {
+            self.tall_grass_under_player.remove(idx);
+        }
+    }
+
+    /// Rolls the encounter table of the grass where the player it's standing, and when a wild Pokémon appears,
+    /// starts a battle against it. Sometimes a second one joins it, for a double battle. Nothing happens if the player
+    /// doesn't have any Pokémon able to fight
+    fn check_wild_encounter(&mut self, base: &Node2D) {
+        if self.current_scene_type == CurrentSceneType::Battle {
+            return;
+        }
+        let mut rng = rand::thread_rng();
+        let conditions = self.encounter_conditions();
+        let encounter_table = match self.tall_grass_under_player.last()
+            .and_then(|area| encounter::table_for_area(&self.encounter_tables, area))
+        {
+            Some(encounter_table) => encounter_table,
+            None => return
+        };
+        let wild_encounter = match encounter_table.roll_encounter(&conditions, &mut rng) {
+            Some(wild_encounter) => wild_encounter,
+            None => return
+        };
+        // Two wild Pokémon only appear when the player has two Pokémon able to fight them
+        let partner_encounter = encounter_table.roll_partner(&conditions, &mut rng)
+            .filter(|_| self.player_data.party().healthy_members() >= 2);
+
+        if !self.player_data.party().has_healthy_member() {
+            return;
+        }
+        let specie = match pokemon_specie_by_id(wild_encounter.specie_id) {
+            Some(specie) => specie,
+            None => {
+                godot_print!("The encounter table has a specie that isn't on the game: {}", wild_encounter.specie_id);
+                return;
+            }
+        };
+
+        let partner_specie = partner_encounter.as_ref().and_then(|partner| pokemon_specie_by_id(partner.specie_id));
+
+        let trainer_ids = self.player_data.trainer_ids();
+        let mut wild_battlers = vec![Battler::from_pokemon(Pokemon::generate(specie, wild_encounter.level, trainer_ids, &mut rng))];
+        self.player_data.pokedex_mut().register_spotted(specie.id);
+        if let (Some(partner), Some(partner_specie)) = (partner_encounter, partner_specie) {
+            wild_battlers.push(Battler::from_pokemon(Pokemon::generate(partner_specie, partner.level, trainer_ids, &mut rng)));
+            self.player_data.pokedex_mut().register_spotted(partner_specie.id);
+        }
+        let format = if wild_battlers.len() > 1 { BattleFormat::Double } else { BattleFormat::Single };
+
+        let player_side = BattleSide::new(
+            self.player_data.name(),
+            self.player_data.party().members().iter().cloned().map(Battler::from_pokemon).collect()
+        );
+        let wild_side = BattleSide::new(&specie.name, wild_battlers);
+
+        // The wild Pokémon just use any of their moves
+        let wild_ai = AiDifficulty::Random.build(rng.gen());
+        match BattleEngine::new(BattleKind::Wild, player_side, wild_side) {
+            Ok(engine) => self.start_battle(base, engine.with_format(format), wild_ai),
+            Err(err) => godot_print!("The wild battle can't start: {}", err)
+        }
+    }
+
+    /// The real time of the day and the real weather of the current city, that decides which wild Pokémon can appear
+    fn encounter_conditions(&self) -> EncounterConditions {
+        let time = EncounterTime::from_day_night_cycle(&self.game_external_data.current_dn_cycle, utils::get_current_time());
+        EncounterConditions::new(time, self.current_city_weather())
+    }
+
+    /// The real weather of the city where the player currently is
+    fn current_city_weather(&self) -> Weather {
+        self.current_city.as_ref()
+            .and_then(|current_city| self.game_cities.iter().find(|city| city.get_name() == current_city.to_fmt_string()))
+            .and_then(|city| city.get_weather().as_ref())
+            .map_or(Weather::default(), |city_weather| city_weather.get_main_code_as_weather_variant())
+    }
+
+    /// The weather of the battlefield for the battles started right now. Always clear when the player disabled
+    /// the battle weather on the Options screen
+    fn battle_weather(&self) -> BattleWeather {
+        if self.options.battle_weather {
+            BattleWeather::from_weather(&self.current_city_weather())
+        } else {
+            BattleWeather::Clear
+        }
+    }
+
+    #[method]
+    /// Receives the `player_step_completed` signal of the `PlayerCharacter`. Every few steps, the poisoned Pokémon
+    /// of the team loses HP, until they're left with only one, and every `FRIENDSHIP_STEPS` the healthy ones gain
+    /// friendship. Inside the tall grass, every step can find a wild Pokémon
+    fn _on_player_step_completed(&mut self, #[base] base: &Node2D) {
+        self.steps_walked += 1;
+        if self.steps_walked % status_condition::OVERWORLD_POISON_STEPS == 0 {
+            for pokemon in self.player_data.party_mut().members_mut().iter_mut() {
+                if pokemon.take_overworld_poison_damage() {
+                    godot_print!("{} survived the poisoning!", pokemon.name);
+                }
+            }
+        }
+        if self.steps_walked % evolution::FRIENDSHIP_STEPS == 0 {
+            for pokemon in self.player_data.party_mut().members_mut().iter_mut().filter(|pokemon| !pokemon.is_fainted()) {
+                pokemon.gain_friendship(1);
+            }
+        }
+
+        self.check_wild_encounter(base);
+    }
+
+    /// The specie that the Pokémon evolves into after the given trigger, on the current time of the day and the current city.
+    /// `None` when it doesn't evolve
+    pub fn evolution_for(&self, pokemon: &Pokemon, trigger: EvolutionTrigger) -> Option<&'static PokemonSpecie> {
+        let specie = pokemon_specie_by_id(pokemon.id)?;
+        let context = EvolutionContext::new(
+            trigger,
+            self.game_external_data.current_dn_cycle.clone(),
+            self.current_city.clone()
+        );
+
+        evolution::evaluate_evolution(pokemon, specie, &context)
+            .and_then(|evolution| pokemon_specie_by_id(evolution.evolves_into))
+    }
+
+    /// Checks if the Pokémon evolves after the given trigger, on the current time of the day and the current city.
+    ///
+    /// When it does, the Pokémon gets evolved, the new specie it's registered on the Pokédex and the `pokemon_evolution`
+    /// signal it's emitted, so the overworld can play the evolution scene. Returns true if the Pokémon evolved.
+    pub fn check_evolution(&mut self, base: &Node2D, pokemon: &mut Pokemon, trigger: EvolutionTrigger) -> bool {
+        match self.evolution_for(pokemon, trigger) {
+            Some(evolved_specie) => {
+                base.emit_signal("pokemon_evolution", &[
+                    pokemon.name.to_variant(), evolved_specie.name.to_variant()
+                ]);
+                pokemon.evolve_into(evolved_specie);
+                self.player_data.pokedex_mut().register_captured(evolved_specie.id);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn player_data(&self) -> &PlayerData {
+        &self.player_data
+    }
+
+    pub fn player_data_mut(&mut self) -> &mut PlayerData {
+        &mut self.player_data
+    }
+
+    pub fn options(&self) -> &GameOptions {
+        &self.options
+    }
+
+    pub fn options_mut(&mut self) -> &mut GameOptions {
+        &mut self.options
+    }
+
+    #[method]
+    /// Stores the given units of an item on the player's bag. Returns false if they didn't fit or the item doesn't exist
+    fn add_item_to_bag(&mut self, item_name: String, quantity: i32) -> bool {
+        match self.player_data.bag_mut().add(&item_name, quantity) {
+            Ok(()) => true,
+            Err(err) => {
+                godot_print!("{}", err);
+                false
+            }
+        }
+    }
+
+    /// Checks that there are at least `quantity` units of the Poké Ball on the bag, without taking any of them.
+    /// Returns the multiplier of the ball
+    pub fn ball_on_bag(&self, ball_name: &str, quantity: i32) -> Result<f64, String> {
+        let multiplier = match game_item_by_name(ball_name).and_then(|game_item| game_item.effect) {
+            Some(ItemEffect::CatchRate { multiplier }) => multiplier,
+            _ => return Err(format!("{} isn't a Poké Ball", ball_name))
+        };
+        if self.player_data.bag().quantity_of(ball_name) < quantity {
+            return Err(format!("There aren't enough {} on the bag", ball_name));
+        }
+        Ok(multiplier)
+    }
+
+    /// Takes a Poké Ball out of the bag once it's thrown on a wild battle. Returns the multiplier of the ball
+    pub fn take_ball_from_bag(&mut self, ball_name: &str) -> Result<f64, String> {
+        let multiplier = self.ball_on_bag(ball_name, 1)?;
+        self.player_data.bag_mut().remove(ball_name, 1)?;
+        Ok(multiplier)
+    }
+
+    /// Uses one unit of an item of the bag over the member of the party at the given index, and returns what happened,
+    /// to show it to the player.
+    ///
+    /// Evolution items are only spent when the Pokémon really evolves with them
+    pub fn use_item_on_member(&mut self, party_index: usize, item_name: &str) -> Result<String, String> {
+        let game_item = game_item_by_name(item_name)
+            .ok_or_else(|| format!("{} isn't an item of the game", item_name))?;
+        let mut pokemon = self.player_data.party().get(party_index).cloned()
+            .ok_or_else(|| format!("There's no Pokémon at the slot {} of the party", party_index))?;
+
+        let message = if game_item.effect == Some(ItemEffect::Evolution) {
+            if !self.player_data.bag().has(item_name) {
+                return Err(format!("There's no {} on the bag", item_name));
+            }
+            let evolved_specie = self.evolution_for(&pokemon, EvolutionTrigger::UseItem { item_name: item_name.to_string() })
+                .ok_or_else(|| format!("{} won't have any effect on {}", item_name, pokemon.name))?;
+            let message = format!("¡{} ha evolucionado en {}!", pokemon.name, evolved_specie.name);
+            pokemon.evolve_into(evolved_specie);
+            self.player_data.pokedex_mut().register_captured(evolved_specie.id);
+            self.player_data.bag_mut().remove(item_name, 1)?;
+            message
+        } else {
+            self.player_data.bag_mut().use_item(item_name, &mut pokemon)?;
+            format!("Has usado {} en {}", item_name, pokemon.name)
+        };
+
+        if let Some(member) = self.player_data.party_mut().get_mut(party_index) {
+            *member = pokemon;
+        }
+        Ok(message)
+    }
+
     // <--------------------------- HTTP ZONE CONTROL --------------------------------------->    
 
     /// Creates a new HTTP Godot Node and insert it into a tree. When a url is specified, performs an HTTP request, and if `connect_to`
@@ -0,0 +1,212 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::pokemon::Pokemon;
+use super::pokemon_type::PokemonType;
+use super::status_condition::MajorStatus;
+
+/// The pockets of the player's bag. Every item of the game it's stored on one of them
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum BagPocket {
+    Items,
+    Medicine,
+    PokeBalls,
+    TMs,
+    Berries,
+    KeyItems,
+}
+
+impl Default for BagPocket {
+    fn default() -> Self { BagPocket::Items }
+}
+
+impl BagPocket {
+    // Returns a Vec<BagPocket> with all the Variants, in the order that they're shown on the bag
+    pub fn values() -> Vec<BagPocket> {
+        vec![Self::Items, Self::Medicine, Self::PokeBalls, Self::TMs, Self::Berries, Self::KeyItems]
+    }
+
+    // Returns the pocket's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Items => "Objetos",
+            Self::Medicine => "Botiquín",
+            Self::PokeBalls => "Poké Balls",
+            Self::TMs => "MT y MO",
+            Self::Berries => "Bayas",
+            Self::KeyItems => "Objetos Clave",
+        }
+    }
+
+    // Returns the pocket's name translated into galician
+    pub fn to_galician_str(&self) -> &'static str {
+        match self {
+            Self::Items => "Obxectos",
+            Self::Medicine => "Botiquín",
+            Self::PokeBalls => "Poké Balls",
+            Self::TMs => "MT e MO",
+            Self::Berries => "Bagas",
+            Self::KeyItems => "Obxectos Clave",
+        }
+    }
+}
+
+/// What happens when an item it's used
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub enum ItemEffect {
+    /// Restores HP to a Pokémon that isn't fainted
+    HealHp { amount: i32 },
+    /// Brings back a fainted Pokémon with this percentage of his max HP
+    Revive { hp_percent: i32 },
+    /// Cures the given status condition, or any of them when it's `None`
+    CureStatus { status: Option<MajorStatus> },
+    /// Restores PP to every move of a Pokémon
+    RestorePp { amount: i32 },
+    /// A Poké Ball, that multiplies the catch rate of the specie
+    CatchRate { multiplier: f64 },
+    /// A TM, that teaches his move
+    TeachMove { move_name: String },
+    /// Makes some species evolve
+    Evolution,
+}
+
+/// What an item does on the battles while a Pokémon holds it
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub enum HeldEffect {
+    /// A berry that restores HP when the Pokémon falls to half of his max HP. It's eaten after that
+    HealAtHalfHp { amount: i32 },
+    /// A berry that cures the given status condition, or any of them when it's `None`, as soon as the Pokémon gets it.
+    /// It's eaten after that
+    CureStatus { status: Option<MajorStatus> },
+    /// Multiplies the damage of the moves of the given type
+    BoostType { move_type: PokemonType, multiplier: f64 },
+    /// Restores 1 / `divisor` of the max HP at the end of every turn
+    HealEachTurn { divisor: i32 },
+}
+
+impl HeldEffect {
+    /// Berries are eaten the first time that they act
+    pub fn is_consumed_on_use(&self) -> bool {
+        matches!(self, HeldEffect::HealAtHalfHp { .. } | HeldEffect::CureStatus { .. })
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The data of an item, as it's stored on the items table of the `PokemonDB`
+pub struct GameItem {
+    #[property]
+    pub id: i32,
+    #[property]
+    pub name: String,
+    pub pocket: BagPocket,
+    // What the item costs on the shops. Zero for the ones that can't be bought
+    #[property]
+    pub price: i32,
+    #[property]
+    pub description: String,
+    // `None` for the items that can't be used directly, like the most of the key items
+    pub effect: Option<ItemEffect>,
+    // `None` for the items that don't do anything while a Pokémon holds them
+    #[serde(default)]
+    pub held_effect: Option<HeldEffect>,
+}
+
+#[methods]
+impl GameItem {
+    // The mandatory constructor needed by `Godot - Rust `
+    pub fn new(_owner: &Node2D) -> Self {
+        Self {
+            id: 0,
+            name: "".to_string(),
+            pocket: BagPocket::default(),
+            price: 0,
+            description: "".to_string(),
+            effect: None,
+            held_effect: None,
+        }
+    }
+
+    // The constructor that given the needed data, creates a new item
+    pub fn new_item(id: i32, name: &str, pocket: BagPocket, price: i32, description: &str, effect: Option<ItemEffect>) -> Self {
+        Self {
+            id: id,
+            name: name.to_string(),
+            pocket: pocket,
+            price: price,
+            description: description.to_string(),
+            effect: effect,
+            held_effect: None,
+        }
+    }
+
+    /// Gives the item an effect on the battles, for when a Pokémon holds it
+    pub fn with_held_effect(mut self, held_effect: HeldEffect) -> Self {
+        self.held_effect = Some(held_effect);
+        self
+    }
+
+    #[method]
+    /// The spanish name of the pocket where the item it's stored
+    pub fn get_pocket_name(&self) -> String {
+        self.pocket.to_spanish_str().to_string()
+    }
+
+    /// Key items are never consumed when used
+    pub fn is_consumable(&self) -> bool {
+        self.pocket != BagPocket::KeyItems
+    }
+
+    /// Every item can be given to a Pokémon, except the key items
+    pub fn can_be_held(&self) -> bool {
+        self.pocket != BagPocket::KeyItems
+    }
+
+    /// Applies the effect of the item over the given Pokémon.
+    ///
+    /// Returns an error, without changing the Pokémon, when the item doesn't have any effect on it.
+    /// Poké Balls and evolution items can't be applied here, because they depend on the battle and on the evolutions
+    pub fn apply_to(&self, pokemon: &mut Pokemon) -> Result<(), String> {
+        let no_effect = || format!("{} won't have any effect on {}", self.name, pokemon.name);
+
+        match &self.effect {
+            Some(ItemEffect::HealHp { amount }) => {
+                if pokemon.current_hp <= 0 || pokemon.current_hp >= pokemon.max_hp {
+                    return Err(no_effect());
+                }
+                pokemon.current_hp = (pokemon.current_hp + amount).min(pokemon.max_hp);
+            },
+            Some(ItemEffect::Revive { hp_percent }) => {
+                if pokemon.current_hp > 0 {
+                    return Err(no_effect());
+                }
+                pokemon.current_hp = (pokemon.max_hp * hp_percent / 100).clamp(1, pokemon.max_hp);
+            },
+            Some(ItemEffect::CureStatus { status }) => {
+                let cures_it = match (pokemon.status, status) {
+                    (Some(current_status), Some(cured_status)) => current_status.same_kind(cured_status),
+                    (Some(_), None) => true,
+                    (None, _) => false
+                };
+                if pokemon.is_fainted() || !cures_it {
+                    return Err(no_effect());
+                }
+                pokemon.cure_status();
+            },
+            Some(ItemEffect::RestorePp { amount }) => {
+                if !pokemon.move_set.recover_pp(*amount) {
+                    return Err(no_effect());
+                }
+            },
+            Some(ItemEffect::TeachMove { move_name }) => {
+                if !pokemon.learn_move(move_name) {
+                    return Err(format!("{} can't learn {}", pokemon.name, move_name));
+                }
+            },
+            Some(ItemEffect::CatchRate { .. }) => return Err(format!("{} can only be used on a wild battle", self.name)),
+            Some(ItemEffect::Evolution) | None => return Err(no_effect())
+        }
+        Ok(())
+    }
+}
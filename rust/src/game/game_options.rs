@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use super::language::Language;
+
+/// The preferences that the player can change from the Options screen of the menu. They're saved alongside the game
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameOptions {
+    // When enabled, the battles are played under the real weather of the city where they started
+    pub battle_weather: bool,
+    // The language of the categories and the descriptions of the Pokédex
+    pub pokedex_language: Language,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self { GameOptions::new() }
+}
+
+impl GameOptions {
+    pub fn new() -> Self {
+        Self { battle_weather: true, pokedex_language: Language::default() }
+    }
+
+    /// The text shown for every option on the Options screen, with his current value
+    pub fn labels(&self) -> Vec<String> {
+        vec![
+            format!("Clima en combate    {}", if self.battle_weather { "Sí" } else { "No" }),
+            format!("Idioma de la Pokédex    {}", self.pokedex_language.native_name()),
+        ]
+    }
+
+    /// Switches the value of the option shown at the given position of `labels()`. The ones with more than two
+    /// values move to the next one
+    pub fn toggle(&mut self, option_index: usize) -> Result<(), String> {
+        match option_index {
+            0 => self.battle_weather = !self.battle_weather,
+            1 => self.pokedex_language = self.pokedex_language.next(),
+            _ => return Err(format!("There's no option at index {}", option_index))
+        }
+        Ok(())
+    }
+}
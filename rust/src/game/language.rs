@@ -0,0 +1,65 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The languages that the texts of the Pokédex are written in
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum Language {
+    Spanish,
+    Galician,
+    English,
+}
+
+impl Default for Language {
+    fn default() -> Self { Language::Spanish }
+}
+
+impl Language {
+    pub fn values() -> Vec<Language> {
+        vec![Self::Spanish, Self::Galician, Self::English]
+    }
+
+    /// The name of the language, written in the language itself
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::Spanish => "Español",
+            Language::Galician => "Galego",
+            Language::English => "English"
+        }
+    }
+
+    /// The language that comes after this one on `values()`, going back to the first one after the last
+    pub fn next(&self) -> Language {
+        let languages = Self::values();
+        let idx = languages.iter().position(|language| language == self).unwrap_or(0);
+        languages[(idx + 1) % languages.len()]
+    }
+}
+
+/// A text written in every language of the game.
+///
+/// The english one it's always required. The spanish and the galician ones fall back to it when they're missing
+#[derive(PartialEq, Clone, Debug, Default, ToVariant, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalizedText {
+    #[serde(default)]
+    pub es: String,
+    #[serde(default)]
+    pub gl: String,
+    pub en: String,
+}
+
+impl LocalizedText {
+    pub fn new(es: &str, gl: &str, en: &str) -> Self {
+        Self { es: es.to_string(), gl: gl.to_string(), en: en.to_string() }
+    }
+
+    /// The text in the given language, or the english one when it isn't translated into it
+    pub fn get(&self, language: Language) -> &str {
+        let translated = match language {
+            Language::Spanish => &self.es,
+            Language::Galician => &self.gl,
+            Language::English => &self.en
+        };
+        if translated.is_empty() { &self.en } else { translated }
+    }
+}
@@ -0,0 +1,224 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::utils::utils;
+use crate::utils::all_game_items::game_item_by_name;
+use crate::game::bag::{Bag, BagSlot};
+use crate::game::game_item::{BagPocket, ItemEffect};
+use crate::game::party::Party;
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// The screen that shows the content of the player's bag, pocket by pocket.
+///
+/// With the Interact action, the selected item gets used over one of the members of the party, like the evolution stones
+pub struct BagScreen {
+    // The bag and the party, as they're saved
+    bag: Bag,
+    party: Party,
+    bag_items_holder_node: Option<Ref<Node>>,
+
+    current_pocket: usize,
+    current_item_selected: usize,
+    // The member of the party that the player it's going to use the selected item on, while choosing it
+    member_being_chosen: Option<usize>,
+    // What happened when the last item was used, shown until the selection changes
+    last_use_message: Option<String>,
+
+    x_item_position: f32,
+    y_item_position: f32,
+    item_separation: f32,
+}
+
+#[methods]
+impl BagScreen {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            bag: Bag::new(),
+            party: Party::new(),
+            bag_items_holder_node: None,
+            current_pocket: 0,
+            current_item_selected: 0,
+            member_being_chosen: None,
+            last_use_message: None,
+            x_item_position: 120.0,
+            y_item_position: 160.0,
+            item_separation: 60.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Control) {
+        // The game it's always saved when the menu gets opened, so the saved bag and party are the current ones
+        let game_data = utils::retrieve_game_data();
+        self.bag = game_data.player_data().bag().clone();
+        self.party = game_data.player_data().party().clone();
+
+        // A child node that will get one label per item of the selected pocket
+        self.bag_items_holder_node = owner.get_node("BagItems");
+
+        self.show_current_pocket(owner);
+
+        // Enable processing
+        owner.set_process(true);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Control, _delta: f64) {
+        if self.member_being_chosen.is_some() {
+            self.handle_member_input_events(owner);
+        } else {
+            self.handle_bag_input_events(owner);
+        }
+    }
+
+    fn handle_bag_input_events(&mut self, owner: &Control) {
+        // Gets an input singleton to point to the input events
+        let input: &Input = Input::godot_singleton();
+        let number_of_pockets = BagPocket::values().len();
+
+        if Input::is_action_just_pressed(&input, "Left", false) {
+            self.current_pocket = (self.current_pocket + number_of_pockets - 1) % number_of_pockets;
+            self.current_item_selected = 0;
+            self.show_current_pocket(owner);
+        } else if Input::is_action_just_pressed(&input, "Right", false) {
+            self.current_pocket = (self.current_pocket + 1) % number_of_pockets;
+            self.current_item_selected = 0;
+            self.show_current_pocket(owner);
+        } else if Input::is_action_just_pressed(&input, "ui_up", false) {
+            if self.current_item_selected > 0 {
+                self.current_item_selected -= 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "ui_down", false) {
+            if self.current_item_selected + 1 < self.current_pocket_items().len() {
+                self.current_item_selected += 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "Interact", false) && self.selected_item_can_be_used() {
+            self.member_being_chosen = Some(0);
+            self.update_selection(owner);
+        }
+
+        // Exits the Bag scene and goes back to the Game
+        if Input::is_action_just_pressed(&input, "Exit", false) {
+            utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string())
+        }
+    }
+
+    /// While choosing the member to use the item on, left and right go through the members of the party
+    fn handle_member_input_events(&mut self, owner: &Control) {
+        let input: &Input = Input::godot_singleton();
+        let party_members = self.party.len().max(1);
+        let member_index = self.member_being_chosen.unwrap_or(0);
+
+        if Input::is_action_just_pressed(&input, "Left", false) {
+            self.member_being_chosen = Some((member_index + party_members - 1) % party_members);
+            self.update_selection(owner);
+        } else if Input::is_action_just_pressed(&input, "Right", false) {
+            self.member_being_chosen = Some((member_index + 1) % party_members);
+            self.update_selection(owner);
+        } else if Input::is_action_just_pressed(&input, "Interact", false) {
+            self.member_being_chosen = None;
+            if let Some(bag_slot) = self.current_pocket_items().get(self.current_item_selected) {
+                self.use_item_on_member(member_index, &bag_slot.item_name);
+            }
+            self.current_item_selected = self.current_item_selected.min(self.current_pocket_items().len().saturating_sub(1));
+            self.show_current_pocket(owner);
+        } else if Input::is_action_just_pressed(&input, "Exit", false) {
+            self.member_being_chosen = None;
+            self.update_selection(owner);
+        }
+    }
+
+    /// Uses the item over the member of the party, and stores the changes on the saved game, that the `Game` restores
+    /// when the player goes back to it
+    fn use_item_on_member(&mut self, member_index: usize, item_name: &str) {
+        let mut game_data = utils::retrieve_game_data();
+        match game_data.use_item_on_member(member_index, item_name) {
+            Ok(message) => {
+                utils::save_game_data(&game_data);
+                self.bag = game_data.player_data().bag().clone();
+                self.party = game_data.player_data().party().clone();
+                self.last_use_message = Some(message);
+            },
+            Err(err) => godot_print!("{}", err)
+        }
+    }
+
+    /// True when the selected item does something used over a Pokémon, out of the battles, and there's someone to use it on
+    fn selected_item_can_be_used(&self) -> bool {
+        let effect = self.current_pocket_items().get(self.current_item_selected)
+            .and_then(|bag_slot| game_item_by_name(&bag_slot.item_name))
+            .and_then(|game_item| game_item.effect);
+        !self.party.is_empty() && !matches!(effect, None | Some(ItemEffect::CatchRate { .. }))
+    }
+
+    fn current_pocket_items(&self) -> Vec<BagSlot> {
+        self.bag.pocket(BagPocket::values()[self.current_pocket])
+    }
+
+    /// Replaces the labels of the items holder with the ones of the items stored on the current pocket
+    fn show_current_pocket(&mut self, owner: &Control) {
+        let bag_items_holder = unsafe { self.bag_items_holder_node.unwrap().assume_safe() };
+
+        // Removes the labels of the previous pocket
+        for child in bag_items_holder.get_children().iter() {
+            if let Ok(child_node) = child.try_to_object::<Node>() {
+                unsafe { child_node.assume_safe().queue_free() };
+            }
+        }
+
+        let pocket = BagPocket::values()[self.current_pocket];
+        utils::set_label_text(owner, &"PocketName".to_string(), &pocket.to_spanish_str().to_string());
+
+        for (idx, bag_slot) in self.current_pocket_items().iter().enumerate() {
+            let item_label = Label::new();
+            item_label.set_text(format!("{}    x{}", bag_slot.item_name, bag_slot.quantity));
+            item_label.set_position(
+                Vector2::new(self.x_item_position, self.y_item_position + idx as f32 * self.item_separation),
+                false
+            );
+            bag_items_holder.add_child(item_label, true);
+        }
+
+        self.update_selection(owner);
+    }
+
+    /// Moves the selector over the selected item, and shows his description
+    fn update_selection(&mut self, owner: &Control) {
+        let selector = unsafe {
+            owner.get_node_as::<NinePatchRect>("Selector")
+            .unwrap()
+        };
+        let items = self.current_pocket_items();
+
+        match items.get(self.current_item_selected) {
+            Some(bag_slot) => {
+                selector.set_visible(true);
+                selector.set_global_position(
+                    Vector2::new(
+                        selector.global_position().x,
+                        self.y_item_position + self.current_item_selected as f32 * self.item_separation - 10.0
+                    ),
+                    false
+                );
+                let member_being_chosen = self.member_being_chosen.and_then(|member_index| self.party.get(member_index));
+                let description = match (member_being_chosen, self.last_use_message.take()) {
+                    (Some(pokemon), _) => format!("¿En qué Pokémon quieres usar {}?    < {} Nv. {} >",
+                        bag_slot.item_name, pokemon.name, pokemon.level),
+                    (None, Some(last_use_message)) => last_use_message,
+                    (None, None) => game_item_by_name(&bag_slot.item_name)
+                        .map_or("".to_string(), |game_item| game_item.description)
+                };
+                utils::set_label_text(owner, &"Description".to_string(), &description);
+            },
+            None => {
+                selector.set_visible(false);
+                let description = self.last_use_message.take().unwrap_or_default();
+                utils::set_label_text(owner, &"Description".to_string(), &description);
+            }
+        }
+    }
+}
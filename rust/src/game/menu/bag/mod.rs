@@ -0,0 +1 @@
+pub mod bag_screen;
\ No newline at end of file
@@ -211,6 +211,9 @@ impl Menu {
     fn menu_option_to_scene(&mut self, owner: &NinePatchRect, menu_option: i32) {
         match menu_option + 1 {
             1 => utils::change_scene(owner, "res://godot/Game/Pokedex.tscn".to_string()),
+            2 => utils::change_scene(owner, "res://godot/Game/Party.tscn".to_string()),
+            4 => utils::change_scene(owner, "res://godot/Game/Bag.tscn".to_string()),
+            6 => utils::change_scene(owner, "res://godot/Game/Options.tscn".to_string()),
             _ => godot_print!("Menu option implemented yet!")
         }
     }
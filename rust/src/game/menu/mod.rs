@@ -1,2 +1,5 @@
 pub mod menu;
-pub mod pokedex;
\ No newline at end of file
+pub mod pokedex;
+pub mod bag;
+pub mod party;
+pub mod options;
\ No newline at end of file
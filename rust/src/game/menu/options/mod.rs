@@ -0,0 +1 @@
+pub mod options_screen;
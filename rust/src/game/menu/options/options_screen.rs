@@ -0,0 +1,137 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::utils::utils;
+use crate::game::game_options::GameOptions;
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// The screen where the player changes the preferences of the game, like playing the battles under the real weather.
+///
+/// Every change it's saved right away
+pub struct OptionsScreen {
+    options: GameOptions,
+    options_list_node: Option<Ref<Node>>,
+
+    current_option_selected: usize,
+
+    x_option_position: f32,
+    y_option_position: f32,
+    option_separation: f32,
+}
+
+#[methods]
+impl OptionsScreen {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            options: GameOptions::new(),
+            options_list_node: None,
+            current_option_selected: 0,
+            x_option_position: 120.0,
+            y_option_position: 160.0,
+            option_separation: 100.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Control) {
+        // The game it's always saved when the menu gets opened, so the saved options are the current ones
+        self.options = utils::retrieve_game_data().options().clone();
+
+        // A child node that will get one label per option
+        self.options_list_node = owner.get_node("OptionsList");
+
+        self.show_options(owner);
+
+        // Enable processing
+        owner.set_process(true);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Control, _delta: f64) {
+        self.handle_options_input_events(owner);
+    }
+
+    fn handle_options_input_events(&mut self, owner: &Control) {
+        // Gets an input singleton to point to the input events
+        let input: &Input = Input::godot_singleton();
+
+        if Input::is_action_just_pressed(&input, "ui_up", false) {
+            if self.current_option_selected > 0 {
+                self.current_option_selected -= 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "ui_down", false) {
+            if self.current_option_selected + 1 < self.options.labels().len() {
+                self.current_option_selected += 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "Interact", false) {
+            match self.options.toggle(self.current_option_selected) {
+                Ok(()) => self.save_options(),
+                Err(err) => godot_print!("{}", err)
+            }
+            self.show_options(owner);
+        }
+
+        // Exits the Options scene and goes back to the Game
+        if Input::is_action_just_pressed(&input, "Exit", false) {
+            utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string())
+        }
+    }
+
+    /// Stores the options on the saved game, that the `Game` restores when the player goes back to it
+    fn save_options(&self) {
+        let mut game_data = utils::retrieve_game_data();
+        *game_data.options_mut() = self.options.clone();
+        utils::save_game_data(&game_data);
+    }
+
+    /// Replaces the labels of the options list with the current value of every option
+    fn show_options(&mut self, owner: &Control) {
+        let options_list = unsafe { self.options_list_node.unwrap().assume_safe() };
+
+        // Removes the labels with the previous values
+        for child in options_list.get_children().iter() {
+            if let Ok(child_node) = child.try_to_object::<Node>() {
+                unsafe { child_node.assume_safe().queue_free() };
+            }
+        }
+
+        for (idx, option_label) in self.options.labels().into_iter().enumerate() {
+            let label = Label::new();
+            label.set_text(option_label);
+            label.set_position(
+                Vector2::new(self.x_option_position, self.y_option_position + idx as f32 * self.option_separation),
+                false
+            );
+            options_list.add_child(label, true);
+        }
+
+        self.update_selection(owner);
+    }
+
+    /// Moves the selector over the selected option, and explains what it does
+    fn update_selection(&self, owner: &Control) {
+        let selector = unsafe {
+            owner.get_node_as::<NinePatchRect>("Selector")
+            .unwrap()
+        };
+
+        selector.set_global_position(
+            Vector2::new(
+                selector.global_position().x,
+                self.y_option_position + self.current_option_selected as f32 * self.option_separation - 10.0
+            ),
+            false
+        );
+
+        let description = match self.current_option_selected {
+            0 => "Los combates se juegan con el tiempo real de la ciudad: la lluvia potencia el agua, el sol potencia el fuego y la nieve daña a los Pokémon",
+            1 => "El idioma de las categorías y las descripciones de la Pokédex. Las que no están traducidas se muestran en inglés",
+            _ => ""
+        };
+        utils::set_label_text(owner, &"Description".to_string(), &description.to_string());
+    }
+}
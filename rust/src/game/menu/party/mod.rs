@@ -0,0 +1 @@
+pub mod party_screen;
\ No newline at end of file
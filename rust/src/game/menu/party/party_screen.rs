@@ -0,0 +1,247 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::utils::utils;
+use crate::game::bag::BagSlot;
+use crate::game::player::PlayerData;
+use crate::game::pokemon::Pokemon;
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// The screen that shows the Pokémon of the player's party, with their level, HP, status condition and held item.
+///
+/// The members can be reordered, choosing one of them and then the slot where it should go.
+/// With the Menu action, the selected member gets an item of the bag to hold, or gives back the one that it holds
+pub struct PartyScreen {
+    // The party and the bag, as they're saved
+    player_data: PlayerData,
+    party_members_holder_node: Option<Ref<Node>>,
+
+    current_member_selected: usize,
+    // The slot of the member that the player it's moving to another position, if any
+    member_being_moved: Option<usize>,
+    // The item of the bag that the player it's going to give to the selected member, while choosing it
+    item_being_given: Option<usize>,
+
+    x_member_position: f32,
+    y_member_position: f32,
+    member_separation: f32,
+}
+
+#[methods]
+impl PartyScreen {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            player_data: PlayerData::new(),
+            party_members_holder_node: None,
+            current_member_selected: 0,
+            member_being_moved: None,
+            item_being_given: None,
+            x_member_position: 120.0,
+            y_member_position: 160.0,
+            member_separation: 100.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Control) {
+        // The game it's always saved when the menu gets opened, so the saved party and bag are the current ones
+        self.player_data = utils::retrieve_game_data().player_data().clone();
+
+        // A child node that will get one label per member of the party
+        self.party_members_holder_node = owner.get_node("PartyMembers");
+
+        self.show_party(owner);
+
+        // Enable processing
+        owner.set_process(true);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Control, _delta: f64) {
+        if self.item_being_given.is_some() {
+            self.handle_item_input_events(owner);
+        } else {
+            self.handle_party_input_events(owner);
+        }
+    }
+
+    fn handle_party_input_events(&mut self, owner: &Control) {
+        // Gets an input singleton to point to the input events
+        let input: &Input = Input::godot_singleton();
+
+        if Input::is_action_just_pressed(&input, "ui_up", false) {
+            if self.current_member_selected > 0 {
+                self.current_member_selected -= 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "ui_down", false) {
+            if self.current_member_selected + 1 < self.player_data.party().len() {
+                self.current_member_selected += 1;
+                self.update_selection(owner);
+            }
+        } else if Input::is_action_just_pressed(&input, "Interact", false) {
+            self.choose_member(owner);
+        } else if Input::is_action_just_pressed(&input, "Menu", false) && self.member_being_moved.is_none() {
+            self.give_or_take_item(owner);
+        }
+
+        // Cancels the movement of a member, or exits the Party scene and goes back to the Game
+        if Input::is_action_just_pressed(&input, "Exit", false) {
+            if self.member_being_moved.is_some() {
+                self.member_being_moved = None;
+                self.update_selection(owner);
+            } else {
+                utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string())
+            }
+        }
+    }
+
+    /// While choosing the item to give, left and right go through the items of the bag that can be held
+    fn handle_item_input_events(&mut self, owner: &Control) {
+        let input: &Input = Input::godot_singleton();
+        let holdable_items = self.player_data.bag().holdable_items().len().max(1);
+        let item_index = self.item_being_given.unwrap_or(0);
+
+        if Input::is_action_just_pressed(&input, "Left", false) {
+            self.item_being_given = Some((item_index + holdable_items - 1) % holdable_items);
+            self.update_selection(owner);
+        } else if Input::is_action_just_pressed(&input, "Right", false) {
+            self.item_being_given = Some((item_index + 1) % holdable_items);
+            self.update_selection(owner);
+        } else if Input::is_action_just_pressed(&input, "Interact", false) {
+            self.item_being_given = None;
+            if let Some(bag_slot) = self.player_data.bag().holdable_items().get(item_index) {
+                match self.player_data.give_item(self.current_member_selected, &bag_slot.item_name) {
+                    Ok(()) => self.save_party_and_bag(),
+                    Err(err) => godot_print!("{}", err)
+                }
+            }
+            self.show_party(owner);
+        } else if Input::is_action_just_pressed(&input, "Exit", false) {
+            self.item_being_given = None;
+            self.update_selection(owner);
+        }
+    }
+
+    /// The first choice picks the member to move, and the second one the slot where it goes
+    fn choose_member(&mut self, owner: &Control) {
+        if self.player_data.party().is_empty() {
+            return;
+        }
+
+        match self.member_being_moved.take() {
+            None => self.member_being_moved = Some(self.current_member_selected),
+            Some(from) => {
+                match self.player_data.party_mut().reorder(from, self.current_member_selected) {
+                    Ok(()) => self.save_party_and_bag(),
+                    Err(err) => godot_print!("{}", err)
+                }
+                self.show_party(owner);
+                return;
+            }
+        }
+        self.update_selection(owner);
+    }
+
+    /// Takes back the item of the selected member, or starts choosing the one to give it when it doesn't hold any
+    fn give_or_take_item(&mut self, owner: &Control) {
+        let holds_an_item = match self.player_data.party().get(self.current_member_selected) {
+            Some(pokemon) => pokemon.held_item.is_some(),
+            None => return
+        };
+
+        if holds_an_item {
+            match self.player_data.take_item(self.current_member_selected) {
+                Ok(_) => self.save_party_and_bag(),
+                Err(err) => godot_print!("{}", err)
+            }
+            self.show_party(owner);
+        } else if !self.player_data.bag().holdable_items().is_empty() {
+            self.item_being_given = Some(0);
+            self.update_selection(owner);
+        }
+    }
+
+    /// Stores the party and the bag on the saved game, that the `Game` restores when the player goes back to it
+    fn save_party_and_bag(&self) {
+        let mut game_data = utils::retrieve_game_data();
+        game_data.player_data_mut().party_mut().set_members(self.player_data.party().members().clone());
+        *game_data.player_data_mut().bag_mut() = self.player_data.bag().clone();
+        utils::save_game_data(&game_data);
+    }
+
+    /// Replaces the labels of the members holder with the current members of the party
+    fn show_party(&mut self, owner: &Control) {
+        let party_members_holder = unsafe { self.party_members_holder_node.unwrap().assume_safe() };
+
+        // Removes the labels of the previous order
+        for child in party_members_holder.get_children().iter() {
+            if let Ok(child_node) = child.try_to_object::<Node>() {
+                unsafe { child_node.assume_safe().queue_free() };
+            }
+        }
+
+        for (idx, pokemon) in self.player_data.party().members().iter().enumerate() {
+            let member_label = Label::new();
+            member_label.set_text(Self::member_summary(pokemon));
+            member_label.set_position(
+                Vector2::new(self.x_member_position, self.y_member_position + idx as f32 * self.member_separation),
+                false
+            );
+            party_members_holder.add_child(member_label, true);
+        }
+
+        self.update_selection(owner);
+    }
+
+    /// The line shown for each member: his name, with a star when it's shiny, level, HP and status condition
+    fn member_summary(pokemon: &Pokemon) -> String {
+        let status = if pokemon.is_fainted() { "DEB".to_string() } else { pokemon.get_status_name() };
+        let shiny_mark = if pokemon.is_shiny { " ★" } else { "" };
+        format!("{}{}    Nv. {}    PS {}/{}    {}", pokemon.name, shiny_mark, pokemon.level, pokemon.current_hp, pokemon.max_hp, status)
+    }
+
+    /// The summary of the selected member: his type, gender, nature, ability and held item
+    fn member_details(pokemon: &Pokemon) -> String {
+        let held_item = pokemon.held_item.as_deref().unwrap_or("Ninguno");
+        format!("{}    {}    Naturaleza {}    Habilidad {}\nObjeto {}", pokemon.get_type1_name(), pokemon.get_gender_name(),
+            pokemon.get_nature_name(), pokemon.get_ability_name(), held_item)
+    }
+
+    /// Moves the selector over the selected member, and shows what the player can do with it
+    fn update_selection(&self, owner: &Control) {
+        let selector = unsafe {
+            owner.get_node_as::<NinePatchRect>("Selector")
+            .unwrap()
+        };
+
+        match self.player_data.party().get(self.current_member_selected) {
+            Some(pokemon) => {
+                selector.set_visible(true);
+                selector.set_global_position(
+                    Vector2::new(
+                        selector.global_position().x,
+                        self.y_member_position + self.current_member_selected as f32 * self.member_separation - 10.0
+                    ),
+                    false
+                );
+                let pokemon_being_moved = self.member_being_moved.and_then(|from| self.player_data.party().get(from));
+                let item_being_given: Option<BagSlot> = self.item_being_given
+                    .and_then(|item_index| self.player_data.bag().holdable_items().get(item_index).cloned());
+                let description = match (pokemon_being_moved, item_being_given) {
+                    (Some(pokemon_being_moved), _) => format!("¿Dónde quieres mover a {}?", pokemon_being_moved.name),
+                    (None, Some(bag_slot)) =>
+                        format!("¿Qué objeto le das a {}?    < {} x{} >", pokemon.name, bag_slot.item_name, bag_slot.quantity),
+                    (None, None) => Self::member_details(pokemon)
+                };
+                utils::set_label_text(owner, &"Description".to_string(), &description);
+            },
+            None => {
+                selector.set_visible(false);
+                utils::set_label_text(owner, &"Description".to_string(), &"No tienes ningún Pokémon".to_string());
+            }
+        }
+    }
+}
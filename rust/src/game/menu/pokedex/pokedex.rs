@@ -2,6 +2,9 @@ use gdnative::prelude::*;
 use gdnative::api::{NinePatchRect, PackedScene, Resource};
 
 use crate::utils::utils;
+use crate::game::pokemon_specie::PokemonSpecie;
+use crate::game::species_registry::pokemon_specie_by_id;
+use crate::game::language::Language;
 
 // use crate::game::pokemon::Pokemon;
 
@@ -27,6 +30,9 @@ pub struct Pokedex {
     current_pokedex_entry_selected: i32,
 
     times_pressed: f64,
+
+    // The language of the categories and the descriptions, chosen on the options
+    language: Language,
 }
 
 #[methods]
@@ -40,6 +46,7 @@ impl Pokedex {
             y_entry_position: 300.0, 
             current_pokedex_entry_selected: 1, 
             times_pressed: 0.0,
+            language: Language::default(),
         }
     }
 
@@ -56,8 +63,11 @@ impl Pokedex {
         // And this pretty one is a reference to a child node of the Pokédex that will get as many childs as Pokémon are in the game
         self.pokedex_items_holder_node = _owner.get_node("PokedexItems");
 
+        self.language = utils::retrieve_game_data().options().pokedex_language;
+
         // Currently just makes as much entries as availiable Pokémons are.
         self.init_pokedex();
+        self.show_entry_details(_owner);
 
         // Enable processing
         _owner.set_process(true);
@@ -72,6 +82,7 @@ impl Pokedex {
     fn handle_pokedex_input_events(&mut self, owner: &Control, delta: f64) {
         // Gets an input singleton to point to the input events
         let input: &Input = Input::godot_singleton();
+        let previous_entry_selected = self.current_pokedex_entry_selected;
 
         // Set a variable that let us to directly access the PokédexHolderOfEntries Node methods
         let pokedex_entry_node = unsafe {
@@ -151,6 +162,10 @@ impl Pokedex {
             self.times_pressed = 0.0;
         }
 
+        if self.current_pokedex_entry_selected != previous_entry_selected {
+            self.show_entry_details(owner);
+        }
+
         // Exits the Pokédex scene and goes back to the Game
         if Input::is_action_just_pressed(&input, "Exit", false) {
             utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string())
@@ -229,10 +244,10 @@ impl Pokedex {
         // Now that we got the references to those crazy Pokedata labels, we set it's text passing the entries data
         if pokemon.captured_by_player == true && pokemon.spotted_by_player == true {
             pokemon_number_label.set_text("N.º".to_owned() + &pokemon.pokedex_entry_number.to_string());
-            pokemon_name_label.set_text(&pokemon.name);
+            pokemon_name_label.set_text(pokemon.name());
         } else if !pokemon.captured_by_player && pokemon.spotted_by_player {
             pokemon_number_label.set_text("N.º".to_owned() + &pokemon.pokedex_entry_number.to_string());
-            pokemon_name_label.set_text(&pokemon.name);
+            pokemon_name_label.set_text(pokemon.name());
             pokeball_sprite.set_visible(false);
         } else {
             pokemon_number_label.set_text("N.º".to_owned() + &pokemon.pokedex_entry_number.to_string());
@@ -255,81 +270,44 @@ impl Pokedex {
 
     }
 
-    fn availiable_pokemon_list(&mut self) {
-        
-        let bulbasaur = PokedexEntry::new(
-            001, 
-            "Bulbasaur".to_string(), 
-            "Planta".to_string(), 
-            "Veneno".to_string(), 
-            0.8,
-            1.5,
-            "Cacho de tipo planta".to_string(), 
-            true, 
-            true,
-        );
-
-        let charmander = PokedexEntry::new(
-            004, 
-            "Charmander".to_string(), 
-            "Fuego".to_string(), 
-            "".to_string(), 
-            0.8,
-            1.5,
-            "Escupo fuego".to_string(), 
-            true, 
-            false,
+    /// Shows the category, the measures and the description of the selected entry on the details box.
+    /// They stay hidden until the player catches the Pokémon
+    fn show_entry_details(&self, owner: &Control) {
+        let details = self.pokedex_entries.get((self.current_pokedex_entry_selected - 1) as usize)
+            .and_then(|entry| entry.details(self.language));
+        let (category, measures, description) = details.unwrap_or_else(||
+            ("?????".to_string(), "??? m    ??? kg".to_string(), "".to_string())
         );
 
-        let squirtle = PokedexEntry::new(
-            007, 
-            "Squirtle".to_string(), 
-            "Agua".to_string(), 
-            "".to_string(), 
-            0.8,
-            1.5,
-            "Amo a calmarno".to_string(), 
-            false, 
-            false,
-        );
+        utils::set_label_text(owner, &"EntryDetails/Category".to_string(), &category);
+        utils::set_label_text(owner, &"EntryDetails/Measures".to_string(), &measures);
+        utils::set_label_text(owner, &"EntryDetails/Description".to_string(), &description);
+    }
 
-        // let mut pokemon_vec = vec![];
+    /// Fills the Pokédex with the species of the game, and with the ones that the player has seen and caught on the saved game
+    fn availiable_pokemon_list(&mut self) {
+        let pokedex_progress = utils::retrieve_game_data().player_data().pokedex().clone();
 
         for number in 1..=151 {
-            if number == 1 {
-                self.pokedex_entries.push(bulbasaur.clone())
-            } else if number == 4 {
-                self.pokedex_entries.push(charmander.clone())
-            } else if number == 7 {
-                self.pokedex_entries.push(squirtle.clone())
-            } else {
-                let pokemon: PokedexEntry = PokedexEntry::new(
-                    number, 
-                    "".to_string(), 
-                    "".to_string(), 
-                    "".to_string(), 
-                    0.0,
-                    0.0,
-                    "".to_string(), 
-                    false, 
-                    false,
-                );
-                self.pokedex_entries.push(pokemon)
-            }  
+            let pokemon = PokedexEntry::new(
+                number,
+                pokemon_specie_by_id(number),
+                pokedex_progress.is_spotted(number),
+                pokedex_progress.is_captured(number),
+            );
+            self.pokedex_entries.push(pokemon)
         }
     }
 }
 
-/// Struct that representes every Pokédex entry with the correlative Pokémon 
+/// Struct that representes every Pokédex entry with the correlative Pokémon.
+///
+/// The data of the specie, like the name, the types or the description, it's read from the `SpeciesRegistry`
 #[derive(Clone, Debug)]
 pub struct PokedexEntry {
     pokedex_entry_number: i32,
-    name: String,
-    type1: String,
-    type2: String,
-    height: f64,
-    weight: f64,
-    description: String,
+    // `None` when there's no specie with this number on the game
+    specie: Option<&'static PokemonSpecie>,
     // scream: AudioStreamSample,
     
     //*! Player related attributes
@@ -340,27 +318,36 @@ pub struct PokedexEntry {
 impl PokedexEntry {
     pub fn new(
         pokedex_entry_number: i32,
-        name: String,
-        type1: String,
-        type2: String,
-        height: f64,
-        weight: f64,
-        description: String,
+        specie: Option<&'static PokemonSpecie>,
         // scream: AudioStreamSample,
         spotted_by_player: bool,
         captured_by_player: bool
     ) -> Self {
             Self {
                 pokedex_entry_number,
-                name,
-                type1,
-                type2,
-                height,
-                weight,
-                description,
+                specie,
                 // scream,
                 spotted_by_player,
                 captured_by_player,
             }
         }
-}
\ No newline at end of file
+
+    pub fn name(&self) -> &str {
+        self.specie.map_or("", |specie| &specie.name)
+    }
+
+    pub fn specie(&self) -> Option<&'static PokemonSpecie> {
+        self.specie
+    }
+
+    /// The category, the height and weight, and the description of the specie, in the given language.
+    /// `None` until the player catches it
+    pub fn details(&self, language: Language) -> Option<(String, String, String)> {
+        let specie = self.specie.filter(|_| self.captured_by_player)?;
+        Some((
+            specie.category(language).to_string(),
+            format!("{} m    {} kg", specie.height, specie.weight),
+            specie.pokedex_description(language).to_string()
+        ))
+    }
+}
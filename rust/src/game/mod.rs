@@ -4,7 +4,27 @@ pub mod city;
 pub mod player;
 pub mod pokemon;
 pub mod pokemon_specie;
+pub mod species_registry;
+pub mod pokemon_type;
+pub mod ability;
+pub mod pokemon_move;
+pub mod pokemon_stats;
+pub mod personality;
+pub mod experience;
+pub mod evolution;
+pub mod status_condition;
+pub mod game_item;
+pub mod bag;
+pub mod capture;
+pub mod pokedex_progress;
+pub mod party;
+pub mod pc_storage;
+pub mod encounter;
+pub mod game_options;
+pub mod language;
+pub mod trainer;
 pub mod pokemon_database;
+pub mod battle;
 
 pub mod map;
 pub mod map_elements__galicia;
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+use super::pokemon::Pokemon;
+
+/// The most Pokémon that can travel with the player. The rest of them are sent to the `PcStorage`
+pub const MAX_PARTY_SIZE: usize = 6;
+
+/// The Pokémon that travel with the player, in the order that they're sent out to battle.
+/// It's saved alongside the rest of the `PlayerData`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Party {
+    members: Vec<Pokemon>,
+}
+
+impl Party {
+    pub fn new() -> Self {
+        Self { members: Vec::new() }
+    }
+
+    pub fn members(&self) -> &Vec<Pokemon> {
+        &self.members
+    }
+
+    pub fn members_mut(&mut self) -> &mut Vec<Pokemon> {
+        &mut self.members
+    }
+
+    /// Replaces the members with the Pokémon as they ended up after a battle
+    pub fn set_members(&mut self, members: Vec<Pokemon>) {
+        self.members = members;
+    }
+
+    pub fn get(&self, party_index: usize) -> Option<&Pokemon> {
+        self.members.get(party_index)
+    }
+
+    pub fn get_mut(&mut self, party_index: usize) -> Option<&mut Pokemon> {
+        self.members.get_mut(party_index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_SIZE
+    }
+
+    /// True while at least one of the members can fight
+    pub fn has_healthy_member(&self) -> bool {
+        self.members.iter().any(|pokemon| !pokemon.is_fainted())
+    }
+
+    /// How many members can still fight. Double battles need at least two of them
+    pub fn healthy_members(&self) -> usize {
+        self.members.iter().filter(|pokemon| !pokemon.is_fainted()).count()
+    }
+
+    /// Adds the Pokémon at the end of the party. Check `is_full()` first, a Pokémon that doesn't fit gets lost
+    pub fn add(&mut self, pokemon: Pokemon) -> Result<(), String> {
+        if self.is_full() {
+            return Err(format!("The party is full, {} doesn't fit", pokemon.name));
+        }
+        self.members.push(pokemon);
+        Ok(())
+    }
+
+    /// Takes a Pokémon out of the party. The last member that can fight can't leave it
+    pub fn remove(&mut self, party_index: usize) -> Result<Pokemon, String> {
+        let pokemon = match self.members.get(party_index) {
+            Some(pokemon) => pokemon,
+            None => return Err(format!("There's no Pokémon at the party slot {}", party_index))
+        };
+        let other_healthy_member = self.members.iter()
+            .enumerate()
+            .any(|(idx, member)| idx != party_index && !member.is_fainted());
+        if !pokemon.is_fainted() && !other_healthy_member {
+            return Err(format!("{} is the last Pokémon of the party that can fight", pokemon.name));
+        }
+        Ok(self.members.remove(party_index))
+    }
+
+    /// Moves the Pokémon at `from` to the `to` slot, shifting the members between them
+    pub fn reorder(&mut self, from: usize, to: usize) -> Result<(), String> {
+        if from >= self.members.len() || to >= self.members.len() {
+            return Err(format!("Can't move the Pokémon at the party slot {} to the slot {}", from, to));
+        }
+        let pokemon = self.members.remove(from);
+        self.members.insert(to, pokemon);
+        Ok(())
+    }
+
+    /// Exchanges the slots of two members of the party
+    pub fn swap(&mut self, first: usize, second: usize) -> Result<(), String> {
+        if first >= self.members.len() || second >= self.members.len() {
+            return Err(format!("Can't swap the Pokémon at the party slots {} and {}", first, second));
+        }
+        self.members.swap(first, second);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon(name: &str, current_hp: i32) -> Pokemon {
+        let mut pokemon = Pokemon::new_pokemon(1, name.to_string());
+        pokemon.current_hp = current_hp;
+        pokemon
+    }
+
+    fn names(party: &Party) -> Vec<&str> {
+        party.members().iter().map(|pokemon| pokemon.name.as_str()).collect()
+    }
+
+    #[test]
+    fn holds_up_to_six_pokemon() {
+        let mut party = Party::new();
+        for idx in 0..MAX_PARTY_SIZE {
+            party.add(pokemon(&format!("Pokémon {}", idx), 10)).unwrap();
+        }
+        assert!(party.is_full());
+        assert!(party.add(pokemon("Sobrante", 10)).is_err());
+        assert_eq!(party.len(), MAX_PARTY_SIZE);
+    }
+
+    #[test]
+    fn the_last_pokemon_that_can_fight_never_leaves() {
+        let mut party = Party::new();
+        party.add(pokemon("Sano", 10)).unwrap();
+        party.add(pokemon("Debilitado", 0)).unwrap();
+        assert_eq!(party.healthy_members(), 1);
+
+        assert!(party.remove(0).is_err());
+        assert!(party.remove(2).is_err());
+        assert_eq!(party.remove(1).unwrap().name, "Debilitado");
+        assert_eq!(names(&party), vec!["Sano"]);
+        assert!(party.has_healthy_member());
+    }
+
+    #[test]
+    fn reorders_and_swaps_the_slots() {
+        let mut party = Party::new();
+        for name in ["A", "B", "C", "D"] {
+            party.add(pokemon(name, 10)).unwrap();
+        }
+        party.reorder(0, 2).unwrap();
+        assert_eq!(names(&party), vec!["B", "C", "A", "D"]);
+        party.swap(0, 3).unwrap();
+        assert_eq!(names(&party), vec!["D", "C", "A", "B"]);
+        assert!(party.reorder(0, 4).is_err());
+        assert!(party.swap(4, 0).is_err());
+        assert_eq!(names(&party), vec!["D", "C", "A", "B"]);
+    }
+}
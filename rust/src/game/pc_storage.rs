@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use super::party::Party;
+use super::pokemon::Pokemon;
+
+/// How many boxes the PC has
+pub const PC_BOX_COUNT: usize = 8;
+/// The most Pokémon that fit on a single box
+pub const PC_BOX_CAPACITY: usize = 30;
+
+/// One of the boxes of the PC
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PcBox {
+    pub name: String,
+    pokemon: Vec<Pokemon>,
+}
+
+impl PcBox {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), pokemon: Vec::new() }
+    }
+
+    pub fn pokemon(&self) -> &Vec<Pokemon> {
+        &self.pokemon
+    }
+
+    pub fn len(&self) -> usize {
+        self.pokemon.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pokemon.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.pokemon.len() >= PC_BOX_CAPACITY
+    }
+}
+
+/// The boxes where the caught Pokémon that aren't on the `Party` are kept. It's saved alongside the rest of the `PlayerData`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PcStorage {
+    boxes: Vec<PcBox>,
+    // The box that the player was looking at the last time, where the new Pokémon are stored first
+    current_box: usize,
+}
+
+impl Default for PcStorage {
+    fn default() -> Self { PcStorage::new() }
+}
+
+impl PcStorage {
+    pub fn new() -> Self {
+        Self {
+            boxes: (1..=PC_BOX_COUNT).map(|number| PcBox::new(&format!("Caja {}", number))).collect(),
+            current_box: 0,
+        }
+    }
+
+    pub fn boxes(&self) -> &Vec<PcBox> {
+        &self.boxes
+    }
+
+    pub fn get_box(&self, box_index: usize) -> Option<&PcBox> {
+        self.boxes.get(box_index)
+    }
+
+    pub fn current_box(&self) -> usize {
+        self.current_box
+    }
+
+    pub fn set_current_box(&mut self, box_index: usize) -> Result<(), String> {
+        if box_index >= self.boxes.len() {
+            return Err(format!("There's no PC box {}", box_index));
+        }
+        self.current_box = box_index;
+        Ok(())
+    }
+
+    /// How many Pokémon are stored between all the boxes
+    pub fn total_pokemon(&self) -> usize {
+        self.boxes.iter().map(PcBox::len).sum()
+    }
+
+    /// True when there's no room left on any box
+    pub fn is_full(&self) -> bool {
+        self.boxes.iter().all(PcBox::is_full)
+    }
+
+    /// Stores the Pokémon on the current box, or on the next one with room left. Returns the box where it was stored.
+    /// Check `is_full()` first, a Pokémon that doesn't fit gets lost
+    pub fn store(&mut self, pokemon: Pokemon) -> Result<usize, String> {
+        let box_count = self.boxes.len();
+        let box_with_room = (0..box_count)
+            .map(|offset| (self.current_box + offset) % box_count)
+            .find(|box_index| !self.boxes[*box_index].is_full());
+        match box_with_room {
+            Some(box_index) => {
+                self.boxes[box_index].pokemon.push(pokemon);
+                Ok(box_index)
+            },
+            None => Err(format!("There's no room left on the PC for {}", pokemon.name))
+        }
+    }
+
+    /// Leaves the Pokémon of the party slot on the given box
+    pub fn deposit(&mut self, party: &mut Party, party_index: usize, box_index: usize) -> Result<(), String> {
+        match self.boxes.get(box_index) {
+            Some(pc_box) if pc_box.is_full() => return Err(format!("{} is full", pc_box.name)),
+            Some(_) => (),
+            None => return Err(format!("There's no PC box {}", box_index))
+        }
+        let pokemon = party.remove(party_index)?;
+        self.boxes[box_index].pokemon.push(pokemon);
+        Ok(())
+    }
+
+    /// Takes the Pokémon at the slot of the box back to the party
+    pub fn withdraw(&mut self, party: &mut Party, box_index: usize, slot: usize) -> Result<(), String> {
+        if party.is_full() {
+            return Err("The party is full".to_string());
+        }
+        let pokemon = self.take(box_index, slot)?;
+        party.add(pokemon)
+    }
+
+    /// Moves the Pokémon at the slot of a box to the end of another box
+    pub fn move_pokemon(&mut self, from_box: usize, slot: usize, to_box: usize) -> Result<(), String> {
+        match self.boxes.get(to_box) {
+            Some(pc_box) if pc_box.is_full() && from_box != to_box => return Err(format!("{} is full", pc_box.name)),
+            Some(_) => (),
+            None => return Err(format!("There's no PC box {}", to_box))
+        }
+        let pokemon = self.take(from_box, slot)?;
+        self.boxes[to_box].pokemon.push(pokemon);
+        Ok(())
+    }
+
+    /// Sets the Pokémon free. It's lost forever
+    pub fn release(&mut self, box_index: usize, slot: usize) -> Result<Pokemon, String> {
+        self.take(box_index, slot)
+    }
+
+    fn take(&mut self, box_index: usize, slot: usize) -> Result<Pokemon, String> {
+        let pc_box = match self.boxes.get_mut(box_index) {
+            Some(pc_box) => pc_box,
+            None => return Err(format!("There's no PC box {}", box_index))
+        };
+        if slot >= pc_box.pokemon.len() {
+            return Err(format!("There's no Pokémon at the slot {} of {}", slot, pc_box.name));
+        }
+        Ok(pc_box.pokemon.remove(slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon(name: &str, current_hp: i32) -> Pokemon {
+        let mut pokemon = Pokemon::new_pokemon(1, name.to_string());
+        pokemon.current_hp = current_hp;
+        pokemon
+    }
+
+    #[test]
+    fn stores_on_the_current_box_and_then_on_the_next_ones_with_room() {
+        let mut pc_storage = PcStorage::new();
+        pc_storage.set_current_box(PC_BOX_COUNT - 1).unwrap();
+        for _ in 0..PC_BOX_CAPACITY {
+            assert_eq!(pc_storage.store(pokemon("Caja llena", 10)), Ok(PC_BOX_COUNT - 1));
+        }
+        // Wraps around to the first box once the last one is full
+        assert_eq!(pc_storage.store(pokemon("Siguiente", 10)), Ok(0));
+        assert_eq!(pc_storage.total_pokemon(), PC_BOX_CAPACITY + 1);
+        assert!(pc_storage.set_current_box(PC_BOX_COUNT).is_err());
+
+        for _ in pc_storage.total_pokemon()..PC_BOX_COUNT * PC_BOX_CAPACITY {
+            pc_storage.store(pokemon("Relleno", 10)).unwrap();
+        }
+        assert!(pc_storage.is_full());
+        assert!(pc_storage.store(pokemon("Sin sitio", 10)).is_err());
+    }
+
+    #[test]
+    fn deposits_and_withdraws_without_losing_any_pokemon() {
+        let mut party = Party::new();
+        party.add(pokemon("Sano", 10)).unwrap();
+        party.add(pokemon("Otro", 10)).unwrap();
+        let mut pc_storage = PcStorage::new();
+
+        pc_storage.deposit(&mut party, 1, 2).unwrap();
+        assert_eq!(pc_storage.get_box(2).unwrap().pokemon()[0].name, "Otro");
+        // The last Pokémon that can fight stays on the party
+        assert!(pc_storage.deposit(&mut party, 0, 2).is_err());
+        assert_eq!(party.len(), 1);
+        assert!(pc_storage.deposit(&mut party, 0, PC_BOX_COUNT).is_err());
+
+        pc_storage.withdraw(&mut party, 2, 0).unwrap();
+        assert_eq!(party.get(1).unwrap().name, "Otro");
+        assert!(pc_storage.withdraw(&mut party, 2, 0).is_err());
+        assert_eq!(pc_storage.total_pokemon(), 0);
+
+        while !party.is_full() {
+            party.add(pokemon("Relleno", 10)).unwrap();
+        }
+        pc_storage.store(pokemon("En la caja", 10)).unwrap();
+        assert!(pc_storage.withdraw(&mut party, 0, 0).is_err());
+        assert_eq!(pc_storage.total_pokemon(), 1);
+    }
+
+    #[test]
+    fn moves_the_pokemon_between_boxes_only_when_they_fit() {
+        let mut pc_storage = PcStorage::new();
+        for _ in 0..PC_BOX_CAPACITY {
+            pc_storage.store(pokemon("Primera caja", 10)).unwrap();
+        }
+        pc_storage.set_current_box(1).unwrap();
+        pc_storage.store(pokemon("Segunda caja", 10)).unwrap();
+
+        assert!(pc_storage.move_pokemon(1, 0, 0).is_err());
+        // Inside the same full box, it just goes to the end
+        pc_storage.move_pokemon(0, 0, 0).unwrap();
+        pc_storage.move_pokemon(0, 0, 1).unwrap();
+        assert_eq!(pc_storage.get_box(0).unwrap().len(), PC_BOX_CAPACITY - 1);
+        assert_eq!(pc_storage.get_box(1).unwrap().len(), 2);
+        assert_eq!(pc_storage.release(1, 0).unwrap().name, "Segunda caja");
+        assert!(pc_storage.release(1, 1).is_err());
+    }
+}
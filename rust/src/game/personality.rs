@@ -0,0 +1,189 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+use super::pokemon_stats::Nature;
+
+/// A Pokémon is shiny when the xor of his personality value halves and the ids of his original trainer is below this.
+/// That's a chance of 1 / 8192
+pub const SHINY_THRESHOLD: u32 = 8;
+
+/// The gender of a Pokémon. Some species don't have one
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ToVariant, Serialize, Deserialize)]
+pub enum Gender {
+    Male,
+    Female,
+    #[default]
+    Genderless,
+}
+
+impl Gender {
+    // Returns the gender's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Gender::Male => "Macho",
+            Gender::Female => "Hembra",
+            Gender::Genderless => "Sin género"
+        }
+    }
+}
+
+/// How the individuals of a specie are split between males and females
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ToVariant, Serialize, Deserialize)]
+pub enum GenderRatio {
+    AlwaysMale,
+    /// Seven males for every female, like the starters
+    MostlyMale,
+    /// Three males for every female
+    OftenMale,
+    #[default]
+    Even,
+    /// Three females for every male
+    OftenFemale,
+    AlwaysFemale,
+    Genderless,
+}
+
+impl GenderRatio {
+    pub fn values() -> Vec<GenderRatio> {
+        vec![
+            Self::AlwaysMale, Self::MostlyMale, Self::OftenMale, Self::Even,
+            Self::OftenFemale, Self::AlwaysFemale, Self::Genderless
+        ]
+    }
+
+    /// The gender of the Pokémon whose personality value has the given lowest byte.
+    /// It's a female when that byte it's below the threshold of the ratio
+    pub fn gender_of(&self, gender_byte: u8) -> Gender {
+        let female_threshold = match self {
+            GenderRatio::Genderless => return Gender::Genderless,
+            GenderRatio::AlwaysMale => return Gender::Male,
+            GenderRatio::AlwaysFemale => return Gender::Female,
+            GenderRatio::MostlyMale => 31,
+            GenderRatio::OftenMale => 63,
+            GenderRatio::Even => 127,
+            GenderRatio::OftenFemale => 191
+        };
+        if gender_byte < female_threshold { Gender::Female } else { Gender::Male }
+    }
+}
+
+/// The public and the secret ids of a trainer. They're given to every Pokémon that the trainer gets,
+/// and never change after that
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct TrainerIds {
+    pub trainer_id: u16,
+    /// Never shown to the player
+    pub secret_id: u16,
+}
+
+impl TrainerIds {
+    pub fn new(trainer_id: u16, secret_id: u16) -> Self {
+        Self { trainer_id, secret_id }
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self::new(rng.gen(), rng.gen())
+    }
+
+    /// New random ids, for a new player and for the saved games that don't have them yet
+    pub fn generate() -> Self {
+        Self::random(&mut rand::thread_rng())
+    }
+}
+
+/// The hidden value rolled when a Pokémon it's created, together with the ids of his original trainer.
+///
+/// It decides the nature and the gender of the Pokémon, and if it's shiny
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Personality {
+    pub value: u32,
+    pub original_trainer: TrainerIds,
+}
+
+impl Personality {
+    pub fn new(value: u32, original_trainer: TrainerIds) -> Self {
+        Self { value, original_trainer }
+    }
+
+    pub fn random<R: Rng>(original_trainer: TrainerIds, rng: &mut R) -> Self {
+        Self::new(rng.gen(), original_trainer)
+    }
+
+    /// For the Pokémon saved before they had a personality: a random one that's never shiny, because they
+    /// weren't shiny when they were saved
+    pub fn unknown() -> Self {
+        let value: u32 = rand::random();
+        let trainer_id: u16 = rand::random();
+        // Makes the xor of the four halves 0xFFFF, way above the shiny threshold
+        let secret_id = trainer_id ^ (value >> 16) as u16 ^ value as u16 ^ 0xFFFF;
+        Self::new(value, TrainerIds::new(trainer_id, secret_id))
+    }
+
+    pub fn is_shiny(&self) -> bool {
+        let shiny_value = self.original_trainer.trainer_id as u32 ^ self.original_trainer.secret_id as u32
+            ^ (self.value >> 16) ^ (self.value & 0xFFFF);
+        shiny_value < SHINY_THRESHOLD
+    }
+
+    pub fn nature(&self) -> Nature {
+        Nature::from_index(self.value % 25)
+    }
+
+    pub fn gender(&self, gender_ratio: GenderRatio) -> Gender {
+        gender_ratio.gender_of((self.value & 0xFF) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shiny_only_below_the_threshold() {
+        let no_ids = TrainerIds::new(0, 0);
+        assert!(Personality::new(SHINY_THRESHOLD - 1, no_ids).is_shiny());
+        assert!(!Personality::new(SHINY_THRESHOLD, no_ids).is_shiny());
+        // The halves of the value and the ids cancel each other
+        assert!(Personality::new(0xBEEF_1234, TrainerIds::new(0xBEEF, 0x1234)).is_shiny());
+        assert!(!Personality::new(0xBEEF_1234, TrainerIds::new(0xBEEF, 0x1234 ^ SHINY_THRESHOLD as u16)).is_shiny());
+    }
+
+    #[test]
+    fn the_old_saved_pokemon_are_never_shiny() {
+        assert!((0..1000).all(|_| !Personality::unknown().is_shiny()));
+    }
+
+    #[test]
+    fn the_gender_changes_at_the_threshold_of_every_ratio() {
+        let thresholds = [
+            (GenderRatio::MostlyMale, 31), (GenderRatio::OftenMale, 63), (GenderRatio::Even, 127), (GenderRatio::OftenFemale, 191)
+        ];
+        for (gender_ratio, threshold) in thresholds {
+            assert_eq!(gender_ratio.gender_of(0), Gender::Female, "{:?}", gender_ratio);
+            assert_eq!(gender_ratio.gender_of(threshold - 1), Gender::Female, "{:?}", gender_ratio);
+            assert_eq!(gender_ratio.gender_of(threshold), Gender::Male, "{:?}", gender_ratio);
+            assert_eq!(gender_ratio.gender_of(u8::MAX), Gender::Male, "{:?}", gender_ratio);
+            let females = (0..=u8::MAX).filter(|gender_byte| gender_ratio.gender_of(*gender_byte) == Gender::Female).count();
+            assert_eq!(females, threshold as usize);
+        }
+        for gender_byte in [0, 127, u8::MAX] {
+            assert_eq!(GenderRatio::AlwaysMale.gender_of(gender_byte), Gender::Male);
+            assert_eq!(GenderRatio::AlwaysFemale.gender_of(gender_byte), Gender::Female);
+            assert_eq!(GenderRatio::Genderless.gender_of(gender_byte), Gender::Genderless);
+        }
+    }
+
+    #[test]
+    fn the_gender_comes_from_the_lowest_byte_and_the_nature_from_the_whole_value() {
+        let ids = TrainerIds::new(1, 2);
+        assert_eq!(Personality::new(0x1234_5600 + 126, ids).gender(GenderRatio::Even), Gender::Female);
+        assert_eq!(Personality::new(0x1234_5600 + 127, ids).gender(GenderRatio::Even), Gender::Male);
+
+        assert_eq!(Personality::new(0, ids).nature(), Nature::from_index(0));
+        assert_eq!(Personality::new(24, ids).nature(), Nature::from_index(24));
+        assert_eq!(Personality::new(25, ids).nature(), Nature::from_index(0));
+        assert_eq!(Personality::new(u32::MAX, ids).nature(), Nature::from_index(u32::MAX % 25));
+    }
+}
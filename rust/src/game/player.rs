@@ -14,9 +14,23 @@ use crate::game::code_abstractions::{
 
 use crate::utils::utils;
 use crate::utils::consts::in_game_constant;
+use crate::utils::all_game_items::game_item_by_name;
 
 use super::menu::menu::MenuStatus;
-
+use super::bag::Bag;
+use super::pokemon::Pokemon;
+use super::party::Party;
+use super::pc_storage::PcStorage;
+use super::pokedex_progress::PokedexProgress;
+use super::personality::TrainerIds;
+
+/// Where a just caught Pokémon ends up
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CaughtPokemonDestination {
+    Party,
+    // The index of the box of the PC where it was stored, because the party was full
+    PcBox(usize),
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 #[derive(Clone)]
@@ -27,6 +41,22 @@ pub struct PlayerData {
     name: String,
     player_direction: PlayerDirection,
     player_position: HashMap<String, f64>,
+    #[serde(default)]
+    bag: Bag,
+    // The Pokémon that travel with the player
+    #[serde(default, alias = "team")]
+    party: Party,
+    // The caught Pokémon that aren't on the party
+    #[serde(default)]
+    pc_storage: PcStorage,
+    #[serde(default)]
+    pokedex: PokedexProgress,
+    // The ids of the trainers that the player has already defeated. They don't challenge him again
+    #[serde(default)]
+    defeated_trainers: Vec<String>,
+    // Given to every Pokémon that the player catches. The saved games that don't have them get new ones
+    #[serde(default = "TrainerIds::generate")]
+    trainer_ids: TrainerIds,
 }
 
 impl PlayerData {
@@ -36,9 +66,137 @@ impl PlayerData {
             name: "".to_owned(),
             player_direction: PlayerDirection::default(),
             player_position: HashMap::new(),
+            bag: Bag::new(),
+            party: Party::new(),
+            pc_storage: PcStorage::new(),
+            pokedex: PokedexProgress::new(),
+            defeated_trainers: Vec::new(),
+            trainer_ids: TrainerIds::generate(),
         }
     }
 
+    /// Takes back from the saved data the progress that no node of the world sends again when the game starts, like the bag
+    pub fn restore_progress(&mut self, saved_player_data: &PlayerData) {
+        self.bag = saved_player_data.bag.clone();
+        self.party = saved_player_data.party.clone();
+        self.pc_storage = saved_player_data.pc_storage.clone();
+        self.pokedex = saved_player_data.pokedex.clone();
+        self.defeated_trainers = saved_player_data.defeated_trainers.clone();
+        self.trainer_ids = saved_player_data.trainer_ids;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn trainer_ids(&self) -> TrainerIds {
+        self.trainer_ids
+    }
+
+    /// Stores a just caught Pokémon on the party, or on the PC when the party it's full, and registers it on the Pokédex.
+    ///
+    /// Returns where the Pokémon was stored, or an error when it doesn't fit on the PC either
+    pub fn add_caught_pokemon(&mut self, pokemon: Pokemon) -> Result<CaughtPokemonDestination, String> {
+        self.pokedex.register_captured(pokemon.id);
+        if self.party.is_full() {
+            self.pc_storage.store(pokemon).map(CaughtPokemonDestination::PcBox)
+        } else {
+            self.party.add(pokemon).map(|_| CaughtPokemonDestination::Party)
+        }
+    }
+
+    pub fn party(&self) -> &Party {
+        &self.party
+    }
+
+    pub fn party_mut(&mut self) -> &mut Party {
+        &mut self.party
+    }
+
+    pub fn pc_storage(&self) -> &PcStorage {
+        &self.pc_storage
+    }
+
+    pub fn pc_storage_mut(&mut self) -> &mut PcStorage {
+        &mut self.pc_storage
+    }
+
+    /// Leaves the Pokémon of the party slot on a box of the PC
+    pub fn deposit(&mut self, party_index: usize, box_index: usize) -> Result<(), String> {
+        self.pc_storage.deposit(&mut self.party, party_index, box_index)
+    }
+
+    /// Takes a Pokémon of a box of the PC back to the party
+    pub fn withdraw(&mut self, box_index: usize, slot: usize) -> Result<(), String> {
+        self.pc_storage.withdraw(&mut self.party, box_index, slot)
+    }
+
+    /// Takes one unit of the item out of the bag and gives it to the member of the party.
+    /// The item that it was holding before goes back to the bag
+    pub fn give_item(&mut self, party_index: usize, item_name: &str) -> Result<(), String> {
+        match game_item_by_name(item_name) {
+            Some(game_item) if !game_item.can_be_held() => return Err(format!("{} can't be held by a Pokémon", item_name)),
+            Some(_) => (),
+            None => return Err(format!("{} isn't an item of the game", item_name))
+        }
+        let pokemon = match self.party.get_mut(party_index) {
+            Some(pokemon) => pokemon,
+            None => return Err(format!("There's no Pokémon at the party slot {}", party_index))
+        };
+
+        self.bag.remove(item_name, 1)?;
+        if let Some(previous_item) = pokemon.hold_item(item_name) {
+            if let Err(err) = self.bag.add(&previous_item, 1) {
+                // There's no room for the previous item, so the Pokémon keeps it
+                pokemon.hold_item(&previous_item);
+                self.bag.add(item_name, 1)?;
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes the item that the member of the party holds back to the bag. Returns the name of the item
+    pub fn take_item(&mut self, party_index: usize) -> Result<String, String> {
+        let pokemon = match self.party.get_mut(party_index) {
+            Some(pokemon) => pokemon,
+            None => return Err(format!("There's no Pokémon at the party slot {}", party_index))
+        };
+        let item_name = match pokemon.held_item.as_ref() {
+            Some(item_name) => item_name.to_owned(),
+            None => return Err(format!("{} isn't holding any item", pokemon.name))
+        };
+        self.bag.add(&item_name, 1)?;
+        pokemon.take_held_item();
+        Ok(item_name)
+    }
+
+    pub fn pokedex(&self) -> &PokedexProgress {
+        &self.pokedex
+    }
+
+    pub fn pokedex_mut(&mut self) -> &mut PokedexProgress {
+        &mut self.pokedex
+    }
+
+    pub fn register_defeated_trainer(&mut self, trainer_id: &str) {
+        if !self.has_defeated_trainer(trainer_id) {
+            self.defeated_trainers.push(trainer_id.to_string());
+        }
+    }
+
+    pub fn has_defeated_trainer(&self, trainer_id: &str) -> bool {
+        self.defeated_trainers.iter().any(|defeated_trainer| defeated_trainer == trainer_id)
+    }
+
+    pub fn bag(&self) -> &Bag {
+        &self.bag
+    }
+
+    pub fn bag_mut(&mut self) -> &mut Bag {
+        &mut self.bag
+    }
+
     pub fn set_player_direction(&mut self, player_current_direction: &PlayerDirection) {
         self.player_direction = player_current_direction.to_owned();
     }
@@ -111,6 +269,8 @@ impl RegisterSignal<Self> for PlayerCharacter {
         builder.signal( "player_position").done();
         builder.signal( "player_moving").done();
         builder.signal("player_stopped").done();
+        // Indicates that the Player just arrived to a new tile, walking or jumping over a ledge
+        builder.signal("player_step_completed").done();
     }
 }
 
@@ -170,6 +330,7 @@ impl CharacterTileMovement<KinematicBody2D, Input> for PlayerCharacter {
                 in_game_constant::TILE_SIZE * self.input_direction.y));
             self.percent_move_to_next_tile = 0.0; // Set to zero to be ready for the next tile movement
             self.is_moving = false; // The player completed a whole step (moved one entire tile)
+            owner.emit_signal("player_step_completed", &[]);
         // Else, sets the player position to a "somewhere-in-between" point
         } else {
             owner.set_global_position(self.initial_position + Vector2::new(in_game_constant::TILE_SIZE * self.input_direction.x * self.percent_move_to_next_tile as f32,
@@ -197,6 +358,7 @@ impl CharacterJump<KinematicBody2D, Input> for PlayerCharacter {
             self.player_shadow.unwrap().set_visible(false);
             // Manages the landing effect
             self.landing_dust_effect(owner);
+            owner.emit_signal("player_step_completed", &[]);
 
         } else {
             let jumping_input = in_game_constant::TILE_SIZE * self.input_direction.y * self.percent_move_to_next_tile as f32;
@@ -323,6 +485,11 @@ impl PlayerCharacter {
                 self.is_moving = false;
                 self.menu_status = MenuStatus::Open
             },
+            // A trainer has seen the player, and it's walking up to him to start a battle
+            "battle_active" | "trainer_spotted" => {
+                self.player_status = PlayerStatus::Interacting;
+                self.is_moving = false;
+            },
             _ => {
                 self.player_status = PlayerStatus::default();
                 self.dialogue_box_status = DialogueBoxStatus::Inactive;
@@ -374,6 +541,10 @@ impl PlayerCharacter {
         let receiver = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
         owner.connect("player_position", receiver,
          "_save_player_position", VariantArray::new_shared(), 0).unwrap();
+        // Deferred, because the step it's completed while this character it's still borrowed, and a wild battle
+        // started by the step freezes it
+        owner.connect("player_step_completed", receiver,
+         "_on_player_step_completed", VariantArray::new_shared(), 1).unwrap();
     }
 
     #[export]
@@ -512,6 +683,28 @@ impl Default for PlayerDirection {
     fn default() -> Self { PlayerDirection::Downwards }
 }
 
+impl PlayerDirection {
+    /// Converts the name of a direction, like the ones set on the Godot GUI, to his `PlayerDirection` counterpart
+    pub fn from_string(string: &str) -> PlayerDirection {
+        match string {
+            "Upwards" => PlayerDirection::Upwards,
+            "Left" => PlayerDirection::Left,
+            "Right" => PlayerDirection::Right,
+            _ => PlayerDirection::Downwards
+        }
+    }
+
+    /// The unit vector that points to this direction on the map
+    pub fn to_vector(&self) -> Vector2 {
+        match self {
+            PlayerDirection::Upwards => Vector2::new(0.0, -1.0),
+            PlayerDirection::Downwards => Vector2::new(0.0, 1.0),
+            PlayerDirection::Left => Vector2::new(-1.0, 0.0),
+            PlayerDirection::Right => Vector2::new(1.0, 0.0),
+        }
+    }
+}
+
 impl Serialize for PlayerDirection {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
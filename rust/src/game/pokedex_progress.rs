@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// The species that the player has seen and caught, by their Pokédex number. It's saved alongside the rest of the `PlayerData`
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PokedexProgress {
+    spotted: Vec<i32>,
+    captured: Vec<i32>,
+}
+
+impl PokedexProgress {
+    pub fn new() -> Self {
+        Self { spotted: Vec::new(), captured: Vec::new() }
+    }
+
+    /// Marks the specie as seen, the first time that the player finds one of them in a battle
+    pub fn register_spotted(&mut self, pokedex_number: i32) {
+        if !self.spotted.contains(&pokedex_number) {
+            self.spotted.push(pokedex_number);
+        }
+    }
+
+    /// Marks the specie as caught. A caught specie it's always a seen one too
+    pub fn register_captured(&mut self, pokedex_number: i32) {
+        self.register_spotted(pokedex_number);
+        if !self.captured.contains(&pokedex_number) {
+            self.captured.push(pokedex_number);
+        }
+    }
+
+    pub fn is_spotted(&self, pokedex_number: i32) -> bool {
+        self.spotted.contains(&pokedex_number)
+    }
+
+    pub fn is_captured(&self, pokedex_number: i32) -> bool {
+        self.captured.contains(&pokedex_number)
+    }
+
+    pub fn spotted_count(&self) -> usize {
+        self.spotted.len()
+    }
+
+    pub fn captured_count(&self) -> usize {
+        self.captured.len()
+    }
+}
@@ -1,47 +1,485 @@
 use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+use super::pokemon_type::PokemonType;
+use super::pokemon_specie::PokemonSpecie;
+use super::ability::Ability;
+use super::pokemon_stats::{self, Nature, StatSet};
+use super::personality::{Gender, Personality, TrainerIds};
+use super::experience::LevelUpEvent;
+use super::evolution;
+use super::pokemon_move::{MoveSet, MoveSlot};
+use super::status_condition::MajorStatus;
+
+use super::species_registry::pokemon_specie_by_id;
+use crate::utils::all_pokemon_moves::pokemon_move_by_name;
+
+/// The folder with the regular sprites of every specie, one subfolder for the front ones and another for the back ones
+pub const SPRITES_PATH: &str = "res://gfx/Pokemon/Normal";
+/// The folder with the alternate sprites of the shiny Pokémon, with the same layout as the regular ones
+pub const SHINY_SPRITES_PATH: &str = "res://gfx/Pokemon/Shiny";
+/// The subfolders of every sprite set, with the sprites of the front and of the back of every specie
+pub const SPRITE_FACINGS: [&str; 2] = ["Front", "Back"];
+/// Shown instead of the sprite of a specie while it isn't on the project yet
+pub const MISSING_SPRITE_PATH: &str = "res://gfx/Pokedex/pokeball.png";
+
+/// The path of the sprite of a specie, named after his Pokédex number, inside one of the sprite sets
+pub fn sprite_path(sprite_set_path: &str, facing: &str, id: i32) -> String {
+    format!("{}/{}/{:03}.png", sprite_set_path, facing, id)
+}
+
+/// The first of the candidate sprites that exists, or the placeholder when none of them does
+pub fn first_existing_sprite(candidates: &[String], exists: impl Fn(&str) -> bool) -> String {
+    candidates.iter()
+        .find(|sprite| exists(sprite))
+        .cloned()
+        .unwrap_or_else(|| MISSING_SPRITE_PATH.to_string())
+}
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
-#[derive(Debug)]
-/// The base class for a Pokemon data representation and data manipulation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The base class for a Pokemon data representation and data manipulation.
+///
+/// Only holds what it's unique to this individual. The data shared by all the Pokémon of his specie, like the types
+/// or the base stats, it's looked up on the `SpeciesRegistry` by his Pokédex number
 pub struct Pokemon {
+    // The Pokédex number of his specie
     #[property]
     pub id: i32,
+    // Taken from the specie when the Pokémon it's created or evolves
     #[property]
     pub name: String,
+
+    #[property(default = 1)]
+    pub level: i32,
+    // The total experience points, since level 1
+    #[property]
+    pub experience: i32,
+    pub ivs: StatSet,
+    pub evs: StatSet,
+    // Taken from the personality when the Pokémon it's created
+    pub nature: Nature,
+    // Rolled when the Pokémon it's created. The ones saved before it existed get a random one, never shiny
+    #[serde(default = "Personality::unknown")]
+    pub personality: Personality,
+    // Decided by the personality when the Pokémon it's created. Shiny Pokémon use the alternate sprites
+    #[property]
+    #[serde(default)]
+    pub is_shiny: bool,
+    // `None` for the Pokémon of the species without abilities, and for the ones saved before they existed
+    #[serde(default)]
+    pub ability: Option<Ability>,
+    // The name of the item that the Pokémon carries, from the items catalogue
+    #[serde(default)]
+    pub held_item: Option<String>,
+    #[property(default = 70)]
+    pub friendship: i32,
+    // The moves known by the Pokémon, with their remaining PP
+    pub move_set: MoveSet,
+    // The persistent status condition, that stays after the battles until it gets cured
+    #[serde(default)]
+    pub status: Option<MajorStatus>,
+
+    // The final stats, calculated from all the above. Always call `recalculate_stats()` after modifying any of them
+    #[property]
+    pub max_hp: i32,
+    #[property]
+    pub current_hp: i32,
     #[property]
-    pub type1: String,
+    pub attack: i32,
     #[property]
-    pub type2: String,
+    pub defense: i32,
     #[property]
-    pub height: f64,
-    #[property(default = 40.0)]
-    pub tall: f64,
-    // description: String,
+    pub special_attack: i32,
+    #[property]
+    pub special_defense: i32,
+    #[property]
+    pub speed: i32,
 }
 
 #[methods]
 impl Pokemon {
-    pub fn new(_owner: &Node2D) -> Self { 
-            Self { 
-                id: 0, 
+    pub fn new(_owner: &Node2D) -> Self {
+            Self {
+                id: 0,
                 name: "".to_string(),
-                type1: "".to_string(),
-                type2: "".to_string(),
-                height: 0.0, 
-                tall: 0.0, 
-                // description
-                } 
+                level: pokemon_stats::MIN_LEVEL,
+                experience: 0,
+                ivs: StatSet::default(),
+                evs: StatSet::default(),
+                nature: Nature::default(),
+                personality: Personality::unknown(),
+                is_shiny: false,
+                ability: None,
+                held_item: None,
+                friendship: evolution::BASE_FRIENDSHIP,
+                move_set: MoveSet::new(),
+                status: None,
+                max_hp: 0,
+                current_hp: 0,
+                attack: 0,
+                defense: 0,
+                special_attack: 0,
+                special_defense: 0,
+                speed: 0,
+                }
             }
 
-    pub fn new_pokemon(id: i32, name: String, type1: String, type2: String, height: f64, tall: f64) -> Self {
+    pub fn new_pokemon(id: i32, name: String) -> Self {
         Self {
             id: id,
             name: name,
-            type1: type1,
-            type2: type2,
-            height: height,
-            tall: tall
+            level: pokemon_stats::MIN_LEVEL,
+            experience: 0,
+            ivs: StatSet::default(),
+            evs: StatSet::default(),
+            nature: Nature::default(),
+            personality: Personality::unknown(),
+            is_shiny: false,
+            ability: None,
+            held_item: None,
+            friendship: evolution::BASE_FRIENDSHIP,
+            move_set: MoveSet::new(),
+            status: None,
+            max_hp: 0,
+            current_hp: 0,
+            attack: 0,
+            defense: 0,
+            special_attack: 0,
+            special_defense: 0,
+            speed: 0,
         }
     }
-}
\ No newline at end of file
+
+    /// Creates a new individual of the given specie, fully healed and with his stats already calculated.
+    /// It gets the most common ability of the specie, and the nature decided by his personality
+    pub fn from_specie(specie: &PokemonSpecie, level: i32, ivs: StatSet, personality: Personality) -> Self {
+        let mut pokemon = Self::new_pokemon(specie.id, specie.name.to_owned());
+        pokemon.level = level.clamp(pokemon_stats::MIN_LEVEL, pokemon_stats::MAX_LEVEL);
+        pokemon.experience = specie.growth_rate.experience_for_level(pokemon.level);
+        pokemon.ivs = ivs;
+        pokemon.ivs.clamp_ivs();
+        pokemon.personality = personality;
+        pokemon.nature = personality.nature();
+        pokemon.is_shiny = personality.is_shiny();
+        pokemon.ability = specie.abilities.first().copied();
+        for move_name in specie.moves_known_at(pokemon.level) {
+            pokemon.learn_move(&move_name);
+        }
+        pokemon.recalculate_stats();
+        pokemon.current_hp = pokemon.max_hp;
+        pokemon
+    }
+
+    /// Creates a new individual of the given specie with random IVs, personality and ability, like the wild ones.
+    /// The wild Pokémon already get the ids of the player, the trainer that they will have if they're caught
+    pub fn generate<R: Rng>(specie: &PokemonSpecie, level: i32, original_trainer: TrainerIds, rng: &mut R) -> Self {
+        let ivs = StatSet::random_ivs(rng);
+        let personality = Personality::random(original_trainer, rng);
+        let mut pokemon = Self::from_specie(specie, level, ivs, personality);
+        pokemon.ability = Ability::random_from(&specie.abilities, rng);
+        pokemon
+    }
+
+    /// The data of the specie of the Pokémon, from the `SpeciesRegistry`. `None` when his specie isn't on the game
+    pub fn specie(&self) -> Option<&'static PokemonSpecie> {
+        pokemon_specie_by_id(self.id)
+    }
+
+    pub fn type1(&self) -> PokemonType {
+        self.specie().map_or(PokemonType::default(), |specie| specie.type1)
+    }
+
+    /// `None` when the Pokémon only has one type
+    pub fn type2(&self) -> Option<PokemonType> {
+        self.specie().and_then(|specie| specie.type2)
+    }
+
+    /// The gender decided by the personality, with the gender ratio of the specie
+    pub fn gender(&self) -> Gender {
+        self.specie().map_or(Gender::Genderless, |specie| self.personality.gender(specie.gender_ratio))
+    }
+
+    /// Returns the six final stats of the Pokémon
+    pub fn stats(&self) -> StatSet {
+        StatSet::new(self.max_hp, self.attack, self.defense, self.special_attack, self.special_defense, self.speed)
+    }
+
+    /// Updates the final stats with the base stats of the specie and the current level, IVs, EVs and nature.
+    ///
+    /// The current HP changes by the same amount that the max HP does, so a damaged Pokémon keeps the damage received
+    pub fn recalculate_stats(&mut self) {
+        let base_stats = self.specie().map_or(StatSet::default(), |specie| specie.base_stats.clone());
+        let stats = pokemon_stats::calculate_stats(&base_stats, &self.ivs, &self.evs, self.level, self.nature);
+
+        self.current_hp = (self.current_hp + stats.hp - self.max_hp).clamp(0, stats.hp);
+        self.max_hp = stats.hp;
+        self.attack = stats.attack;
+        self.defense = stats.defense;
+        self.special_attack = stats.special_attack;
+        self.special_defense = stats.special_defense;
+        self.speed = stats.speed;
+    }
+
+    /// Teaches the move with the given name on the first free slot. Returns false if the move doesn't exist,
+    /// it's already known or there's no room left for it
+    pub fn learn_move(&mut self, move_name: &str) -> bool {
+        match pokemon_move_by_name(move_name) {
+            Some(pokemon_move) => self.move_set.learn(&pokemon_move).is_ok(),
+            None => false
+        }
+    }
+
+    /// Adds the experience points to the Pokémon, raising as many levels as the new total allows.
+    ///
+    /// Returns one `LevelUpEvent` per level gained, with the stats increased and the moves that can be learnt.
+    /// New moves are learnt straight away while there's a free slot for them.
+    /// The `specie` must be the one of this Pokémon, because the growth rate and the learnset are taken from it.
+    pub fn gain_experience(&mut self, specie: &PokemonSpecie, amount: i32) -> Vec<LevelUpEvent> {
+        let mut level_ups = Vec::new();
+        if self.level >= pokemon_stats::MAX_LEVEL {
+            return level_ups;
+        }
+
+        let max_experience = specie.growth_rate.experience_for_level(pokemon_stats::MAX_LEVEL);
+        self.experience = (self.experience + amount.max(0)).min(max_experience);
+
+        while self.level < pokemon_stats::MAX_LEVEL &&
+            self.experience >= specie.growth_rate.experience_for_level(self.level + 1)
+        {
+            let old_stats = self.stats();
+            self.level += 1;
+            self.recalculate_stats();
+            self.gain_friendship(evolution::level_up_friendship(self.friendship));
+            for move_name in specie.moves_learnt_at(self.level) {
+                self.learn_move(&move_name);
+            }
+
+            level_ups.push(LevelUpEvent {
+                new_level: self.level,
+                stat_deltas: self.stats().delta(&old_stats),
+                new_moves: specie.moves_learnt_at(self.level),
+            });
+        }
+        level_ups
+    }
+
+    /// Raises the friendship of the Pokémon, up to the highest value. Returns how much it went up
+    pub fn gain_friendship(&mut self, amount: i32) -> i32 {
+        let old_friendship = self.friendship;
+        self.friendship = (self.friendship + amount.max(0)).min(evolution::MAX_FRIENDSHIP);
+        self.friendship - old_friendship
+    }
+
+    /// Adds the effort values earned by defeating a Pokémon, and updates the stats with them
+    pub fn gain_effort_values(&mut self, ev_yield: &StatSet) -> StatSet {
+        let gained = self.evs.add_evs(ev_yield);
+        self.recalculate_stats();
+        gained
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.current_hp <= 0
+    }
+
+    /// Removes the persistent status condition. Returns false if the Pokémon didn't have any
+    pub fn cure_status(&mut self) -> bool {
+        self.status.take().is_some()
+    }
+
+    /// Restores all the HP and PP, and cures any status condition, like the Pokémon Centers do
+    pub fn fully_heal(&mut self) {
+        self.current_hp = self.max_hp;
+        self.move_set.restore_pp();
+        self.status = None;
+    }
+
+    /// Applies the poison damage that a poisoned Pokémon takes while the player walks, every `OVERWORLD_POISON_STEPS` steps.
+    ///
+    /// The Pokémon loses 1 HP, but never faints because of it: when it only has 1 HP left, the poison gets cured.
+    /// Returns true when that happens.
+    pub fn take_overworld_poison_damage(&mut self) -> bool {
+        match self.status {
+            Some(MajorStatus::Poison) | Some(MajorStatus::BadPoison) if !self.is_fainted() => {
+                if self.current_hp > 1 {
+                    self.current_hp -= 1;
+                }
+                if self.current_hp == 1 {
+                    self.status = None;
+                    return true;
+                }
+                false
+            },
+            _ => false
+        }
+    }
+
+    /// Transforms this Pokémon into the specie that it evolves into. Level, experience, IVs, EVs, nature
+    /// and the damage received are kept. The new types and base stats come with the new Pokédex number
+    pub fn evolve_into(&mut self, evolved_specie: &PokemonSpecie) {
+        self.id = evolved_specie.id;
+        self.name = evolved_specie.name.to_owned();
+        // The evolved Pokémon keeps his ability, unless the new specie can't have it
+        if !self.ability.map_or(false, |ability| evolved_specie.abilities.contains(&ability)) {
+            self.ability = evolved_specie.abilities.first().copied();
+        }
+        self.recalculate_stats();
+    }
+
+    #[method]
+    /// The experience points still needed to reach the next level. Zero at the max level
+    pub fn get_experience_to_next_level(&self) -> i32 {
+        if self.level >= pokemon_stats::MAX_LEVEL {
+            return 0;
+        }
+        match self.specie() {
+            Some(specie) => (specie.growth_rate.experience_for_level(self.level + 1) - self.experience).max(0),
+            None => 0
+        }
+    }
+
+    #[method]
+    /// The moves known by the Pokémon, each one as a Dictionary with his name and his current and max PP
+    pub fn get_moves(&self) -> Vec<MoveSlot> {
+        self.move_set.slots().clone()
+    }
+
+    #[method]
+    /// The short name of the status condition of the Pokémon, so the UI can show his icon. An empty String if it's healthy
+    pub fn get_status_name(&self) -> String {
+        self.status.map_or("", |status| status.abbreviation()).to_string()
+    }
+
+    #[method]
+    /// The spanish name of the first type of the Pokémon
+    pub fn get_type1_name(&self) -> String {
+        self.type1().to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The spanish name of the second type of the Pokémon, or an empty String if it only has one
+    pub fn get_type2_name(&self) -> String {
+        self.type2().map_or("", |pokemon_type| pokemon_type.to_spanish_str()).to_string()
+    }
+
+    #[method]
+    /// The spanish name of the nature of the Pokémon
+    pub fn get_nature_name(&self) -> String {
+        self.nature.to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The spanish name of the gender of the Pokémon
+    pub fn get_gender_name(&self) -> String {
+        self.gender().to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The path of the sprite that shows the front of the Pokémon, from the shiny sprite set when it's shiny
+    pub fn get_front_sprite_path(&self) -> String {
+        self.existing_sprite_path(SPRITE_FACINGS[0])
+    }
+
+    #[method]
+    /// The path of the sprite that shows the back of the Pokémon, from the shiny sprite set when it's shiny
+    pub fn get_back_sprite_path(&self) -> String {
+        self.existing_sprite_path(SPRITE_FACINGS[1])
+    }
+
+    /// The sprite of the Pokémon that's on the project. A shiny one without his shiny sprite uses the regular one,
+    /// and the placeholder it's used when the specie has no sprite at all
+    fn existing_sprite_path(&self, facing: &str) -> String {
+        let resource_loader = ResourceLoader::godot_singleton();
+        first_existing_sprite(&self.sprite_paths(facing), |sprite| resource_loader.exists(sprite, ""))
+    }
+
+    /// The paths where the sprite of the Pokémon could be, from the preferred one
+    fn sprite_paths(&self, facing: &str) -> Vec<String> {
+        let mut sprite_paths = Vec::new();
+        if self.is_shiny {
+            sprite_paths.push(sprite_path(SHINY_SPRITES_PATH, facing, self.id));
+        }
+        sprite_paths.push(sprite_path(SPRITES_PATH, facing, self.id));
+        sprite_paths
+    }
+
+    /// Gives an item to the Pokémon. Returns the item that it was holding before, if any
+    pub fn hold_item(&mut self, item_name: &str) -> Option<String> {
+        self.held_item.replace(item_name.to_string())
+    }
+
+    /// Takes away the item that the Pokémon holds, if any
+    pub fn take_held_item(&mut self) -> Option<String> {
+        self.held_item.take()
+    }
+
+    #[method]
+    /// The name of the item that the Pokémon holds, or an empty String if it doesn't hold any
+    pub fn get_held_item_name(&self) -> String {
+        self.held_item.as_deref().unwrap_or("").to_string()
+    }
+
+    #[method]
+    /// The spanish name of the ability of the Pokémon, or an empty String if it doesn't have one
+    pub fn get_ability_name(&self) -> String {
+        self.ability.map_or("", |ability| ability.to_spanish_str()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn charmander(level: i32) -> Pokemon {
+        let specie = pokemon_specie_by_id(4).expect("Charmander must be on the species data");
+        Pokemon::from_specie(specie, level, StatSet::uniform(15), Personality::new(31337, TrainerIds::new(1234, 5678)))
+    }
+
+    #[test]
+    fn every_level_up_reports_his_stat_deltas_and_new_moves() {
+        let specie = pokemon_specie_by_id(4).unwrap();
+        let mut pokemon = charmander(5);
+        // Just enough to reach the level 7, where Charmander learns Ascuas
+        let amount = specie.growth_rate.experience_for_level(7) - pokemon.experience;
+
+        let level_ups = pokemon.gain_experience(specie, amount);
+        assert_eq!(level_ups, vec![
+            LevelUpEvent { new_level: 6, stat_deltas: charmander(6).stats().delta(&charmander(5).stats()), new_moves: Vec::new() },
+            LevelUpEvent { new_level: 7, stat_deltas: charmander(7).stats().delta(&charmander(6).stats()), new_moves: vec!["Ascuas".to_string()] },
+        ]);
+        assert!(level_ups[0].stat_deltas.hp > 0);
+        assert_eq!(pokemon.level, 7);
+        assert_eq!(pokemon.stats(), charmander(7).stats());
+        assert!(pokemon.move_set.knows("Ascuas"));
+    }
+
+    #[test]
+    fn not_enough_experience_doesnt_level_up() {
+        let specie = pokemon_specie_by_id(4).unwrap();
+        let mut pokemon = charmander(5);
+        let amount = specie.growth_rate.experience_for_level(6) - pokemon.experience - 1;
+
+        assert!(pokemon.gain_experience(specie, amount).is_empty());
+        assert_eq!(pokemon.level, 5);
+    }
+
+    #[test]
+    fn a_missing_sprite_falls_back_to_the_regular_one_and_then_to_the_placeholder() {
+        let mut pokemon = charmander(5);
+        pokemon.is_shiny = true;
+        let shiny_sprite = sprite_path(SHINY_SPRITES_PATH, "Front", 4);
+        let regular_sprite = sprite_path(SPRITES_PATH, "Front", 4);
+        assert_eq!(pokemon.sprite_paths("Front"), vec![shiny_sprite.clone(), regular_sprite.clone()]);
+
+        let candidates = pokemon.sprite_paths("Front");
+        assert_eq!(first_existing_sprite(&candidates, |_| true), shiny_sprite);
+        assert_eq!(first_existing_sprite(&candidates, |sprite| sprite == regular_sprite), regular_sprite);
+        assert_eq!(first_existing_sprite(&candidates, |_| false), MISSING_SPRITE_PATH);
+
+        pokemon.is_shiny = false;
+        assert_eq!(pokemon.sprite_paths("Back"), vec![sprite_path(SPRITES_PATH, "Back", 4)]);
+    }
+}
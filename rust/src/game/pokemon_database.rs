@@ -1,7 +1,14 @@
 use gdnative::prelude::*;
 
 use super::pokemon_specie::PokemonSpecie;
-use crate::utils::all_pokemon_species::all_pokemon_species_data;
+use super::pokemon_stats::StatSet;
+use super::pokemon_type::PokemonType;
+use super::language::LocalizedText;
+use super::pokemon_move::PokemonMove;
+use super::game_item::GameItem;
+use super::species_registry::SpeciesRegistry;
+use crate::utils::all_pokemon_moves::all_pokemon_moves_data;
+use crate::utils::all_game_items::all_game_items_data;
 
 /// Acts as a wrapper over some type T, representing a DB Row of elements of that T type
 pub struct DBRow<'a, T> {
@@ -16,11 +23,56 @@ impl<'a, T> DBRow<'a, T> {
     }
 }
 
+/// The data of a specie as the GDScript side receives it, as a Dictionary, from the queries of the `PokemonDB`
+#[derive(Clone, Debug, ToVariant)]
+pub struct SpecieRecord {
+    pub id: i32,
+    pub name: String,
+    // The spanish names of the types. The second one it's an empty String when the specie only has one type
+    pub type1: String,
+    pub type2: String,
+    pub generation: i32,
+    pub height: f64,
+    pub weight: f64,
+    pub catch_rate: i32,
+    pub base_stats: StatSet,
+    // The Pokédex numbers of the species that this one can evolve into
+    pub evolves_into: Vec<i32>,
+    // Dictionaries with the text in every language, under the "es", "gl" and "en" keys
+    pub category: LocalizedText,
+    pub pokedex_description: LocalizedText,
+}
+
+impl SpecieRecord {
+    pub fn from_specie(specie: &PokemonSpecie) -> Self {
+        Self {
+            id: specie.id,
+            name: specie.name.to_owned(),
+            type1: specie.get_type1_name(),
+            type2: specie.get_type2_name(),
+            generation: specie.generation(),
+            height: specie.height,
+            weight: specie.weight,
+            catch_rate: specie.catch_rate,
+            base_stats: specie.base_stats.clone(),
+            evolves_into: specie.evolutions.iter().map(|evolution| evolution.evolves_into).collect(),
+            category: specie.category.clone(),
+            pokedex_description: specie.pokedex_description.clone(),
+        }
+    }
+}
+
 #[derive(NativeClass)]
 #[inherit(Node2D)]
 #[derive(Debug)]
-/// Using Nodes as tables, as more nodes as rows, creates a simulation of a real database by using nodes as the main objects.
+/// The access point of the GDScript side to the data of the game.
+///
+/// The species are queried through the `SpeciesRegistry`, that keeps them on indexed collections. The old simulation of a
+/// database, using nodes as tables and more nodes as rows, it's only built as a debug view, when `debug_tables` is enabled.
 pub struct PokemonDB {
+    // Builds the node tables on the scene tree, to inspect the data from the Godot editor. Set on the Godot GUI
+    #[property]
+    debug_tables: bool,
     // TRefs to the Nodes that simulates real DB tables
     pokemon_table: TRef<'static, Node2D>,
     moves_table: TRef<'static, Node2D>,
@@ -31,6 +83,7 @@ pub struct PokemonDB {
 impl PokemonDB {
     pub fn new(_owner: &Node2D) -> Self { 
         Self {
+            debug_tables: false,
             pokemon_table: unsafe { Node2D::new().assume_shared().assume_safe() },
             moves_table: unsafe { Node2D::new().assume_shared().assume_safe() },
             items_table: unsafe { Node2D::new().assume_shared().assume_safe() },
@@ -49,10 +102,86 @@ impl PokemonDB {
         pkm_resource
     }
 
+    /// An static way to access the PokemonMove struct as a Godot Node
+    fn get_move_class_as_resource() -> TRef<'static, Node> {
+        let move_resource = unsafe { ResourceLoader::godot_singleton()
+            .load("res://godot/Game/PokemonMoveNode.tscn", "", false)
+            .unwrap().assume_safe()
+            .cast::<PackedScene>()
+            .unwrap()
+            .instance(0)
+            .unwrap().assume_safe() };
+
+        move_resource
+    }
+
+    /// An static way to access the GameItem struct as a Godot Node
+    fn get_item_class_as_resource() -> TRef<'static, Node> {
+        let item_resource = unsafe { ResourceLoader::godot_singleton()
+            .load("res://godot/Game/GameItemNode.tscn", "", false)
+            .unwrap().assume_safe()
+            .cast::<PackedScene>()
+            .unwrap()
+            .instance(0)
+            .unwrap().assume_safe() };
+
+        item_resource
+    }
+
     #[export]
     fn _ready(&mut self, owner: &Node2D) {
-        //Load db, create it's structure and checks it's integrity
-        self.create_database_structure(owner);
+        // Checks the integrity of the species data, so the problems are reported as soon as the game starts
+        if let Err(err) = SpeciesRegistry::global() {
+            godot_print!("Error loading the Pokémon species:\n{}", err);
+        }
+        // Creates the debug view of the db
+        if self.debug_tables {
+            self.create_database_structure(owner);
+        }
+    }
+
+    #[method]
+    /// The specie with the given Pokédex number as a Dictionary, or null when it isn't on the game
+    fn get_specie(&self, id: i32) -> Option<SpecieRecord> {
+        SpeciesRegistry::global().ok()?
+            .get(id)
+            .map(SpecieRecord::from_specie)
+    }
+
+    #[method]
+    /// The specie with the given name as a Dictionary, ignoring the case, or null when it isn't on the game
+    fn get_specie_by_name(&self, name: String) -> Option<SpecieRecord> {
+        SpeciesRegistry::global().ok()?
+            .get_by_name(&name)
+            .map(SpecieRecord::from_specie)
+    }
+
+    #[method]
+    /// The species that have the given type, by his spanish, galician or english name. Empty if the type doesn't exist
+    fn get_species_by_type(&self, type_name: String) -> Vec<SpecieRecord> {
+        match PokemonType::from_name(type_name) {
+            Some(pokemon_type) => Self::records(|registry| registry.of_type(pokemon_type)),
+            None => Vec::new()
+        }
+    }
+
+    #[method]
+    /// The species introduced on the given generation
+    fn get_species_by_generation(&self, generation: i32) -> Vec<SpecieRecord> {
+        Self::records(|registry| registry.of_generation(generation))
+    }
+
+    #[method]
+    /// Every specie of the evolution family of the given one, itself included
+    fn get_evolution_family(&self, id: i32) -> Vec<SpecieRecord> {
+        Self::records(|registry| registry.evolution_family(id))
+    }
+
+    /// Runs a query on the `SpeciesRegistry`, converting the species found to records.
+    /// Empty when the species data file can't be read
+    fn records<F: FnOnce(&'static SpeciesRegistry) -> Vec<&'static PokemonSpecie>>(query: F) -> Vec<SpecieRecord> {
+        SpeciesRegistry::global()
+            .map_or(Vec::new(), |registry| query(registry).into_iter().map(SpecieRecord::from_specie).collect())
     }
 
     fn create_database_structure(&mut self, owner: &Node2D) {
@@ -61,8 +190,10 @@ impl PokemonDB {
         self.populate_pokemon_table(owner);
         // Moves
         self.moves_table = self.create_table(owner, "Moves");
+        self.populate_moves_table(owner);
         // Game Items
         self.items_table = self.create_table(owner, "Items");
+        self.populate_items_table(owner);
     }
 
     /// Creates a new node that simulates a real database table, by creating that node as a container for another nodes.
@@ -78,10 +209,14 @@ impl PokemonDB {
 
     /// Method that retrieves all the data of all the Pokémon Species availiable in the game.
     /// 
-    /// Gets the data from an external (to this crate) function, where all the values are handcoded (say tnx for the work... bit..)
-    /// which return a Vec<PokemonSpecie>. For each one, creates a new node with the instance data.
+    /// Gets the data from the `SpeciesRegistry`, read from `godot/Data/pokemon_species.json`. For each specie, creates
+    /// a new node with the instance data. When the file can't be read the table it's left empty.
     fn populate_pokemon_table(&mut self, owner: &Node2D) {
-        for pokemon_specie in all_pokemon_species_data().iter() {
+        let species_registry = match SpeciesRegistry::global() {
+            Ok(species_registry) => species_registry,
+            Err(_) => return
+        };
+        for pokemon_specie in species_registry.species() {
             let pokemon_as_row = DBRow::<PokemonSpecie>::new(
                 pokemon_specie
                 );
@@ -103,6 +238,64 @@ impl PokemonDB {
         // Sets the values of the node that holds the Pokémon Species attributes
         new_row.set("name", &row.object.name);
         new_row.set("id", &row.object.id);
+        new_row.set("catch_rate", &row.object.catch_rate);
         // godot_print!("Pokemon name: {:?}", new_row.get("name").to_string());
     }
-}
\ No newline at end of file
+
+    /// Retrieves all the moves availiable in the game, and creates a new node with the data of each one
+    fn populate_moves_table(&mut self, owner: &Node2D) {
+        for pokemon_move in all_pokemon_moves_data().iter() {
+            let move_as_row = DBRow::<PokemonMove>::new(
+                pokemon_move
+                );
+            self.create_move_row(owner, move_as_row)
+        }
+    }
+
+    /// Creates a new NODE2D representing a row inside the moves table
+    fn create_move_row(&self, _owner: &Node2D, row: DBRow<PokemonMove>) {
+        // Creates a new node to hold the PokemonMove instance data
+        let new_row = PokemonDB::get_move_class_as_resource();
+
+        // Add that node as a child of the moves table
+        self.moves_table.add_child(new_row, true);
+
+        // Sets the Node's name == Move's name
+        new_row.set_name(&row.object.name);
+
+        // Sets the values of the node that holds the move attributes
+        new_row.set("id", &row.object.id);
+        new_row.set("name", &row.object.name);
+        new_row.set("power", &row.object.power);
+        new_row.set("pp", &row.object.pp);
+        new_row.set("priority", &row.object.priority);
+    }
+
+    /// Retrieves all the items availiable in the game, and creates a new node with the data of each one
+    fn populate_items_table(&mut self, owner: &Node2D) {
+        for game_item in all_game_items_data().iter() {
+            let item_as_row = DBRow::<GameItem>::new(
+                game_item
+                );
+            self.create_item_row(owner, item_as_row)
+        }
+    }
+
+    /// Creates a new NODE2D representing a row inside the items table
+    fn create_item_row(&self, _owner: &Node2D, row: DBRow<GameItem>) {
+        // Creates a new node to hold the GameItem instance data
+        let new_row = PokemonDB::get_item_class_as_resource();
+
+        // Add that node as a child of the items table
+        self.items_table.add_child(new_row, true);
+
+        // Sets the Node's name == Item's name
+        new_row.set_name(&row.object.name);
+
+        // Sets the values of the node that holds the item attributes
+        new_row.set("id", &row.object.id);
+        new_row.set("name", &row.object.name);
+        new_row.set("price", &row.object.price);
+        new_row.set("description", &row.object.description);
+    }
+}
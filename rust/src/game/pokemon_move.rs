@@ -0,0 +1,327 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::pokemon_type::PokemonType;
+
+/// The most moves that a Pokémon can know at the same time
+pub const MAX_MOVES: usize = 4;
+/// The move used by a Pokémon that doesn't have PP left on any of his moves
+pub const STRUGGLE: &str = "Forcejeo";
+
+/// Decides which stats are used to calculate the damage of a move, if it deals any
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum MoveCategory {
+    /// Uses the attack of the user and the defense of the target
+    Physical,
+    /// Uses the special attack of the user and the special defense of the target
+    Special,
+    /// Doesn't deal damage directly
+    Status,
+}
+
+impl Default for MoveCategory {
+    fn default() -> Self { MoveCategory::Physical }
+}
+
+impl MoveCategory {
+    // Returns a Vec<MoveCategory> with all the Variants
+    pub fn values() -> Vec<MoveCategory> {
+        vec![Self::Physical, Self::Special, Self::Status]
+    }
+
+    // Returns the category's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Physical => "Físico",
+            Self::Special => "Especial",
+            Self::Status => "Estado",
+        }
+    }
+
+    // Returns the category's name translated into galician
+    pub fn to_galician_str(&self) -> &'static str {
+        match self {
+            Self::Physical => "Físico",
+            Self::Special => "Especial",
+            Self::Status => "Estado",
+        }
+    }
+}
+
+/// The Pokémon that a move hits. It only makes a difference on the double battles
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum MoveTarget {
+    /// One Pokémon chosen by the user. A foe, unless the player chooses his ally
+    Single,
+    /// Every foe on the battlefield
+    AdjacentFoes,
+    /// Every other Pokémon on the battlefield, the ally included
+    All,
+    /// Only the ally of the user. Fails on the single battles
+    Ally,
+}
+
+impl Default for MoveTarget {
+    fn default() -> Self { MoveTarget::Single }
+}
+
+impl MoveTarget {
+    /// True for the moves that can hit more than one Pokémon, whose damage gets reduced when they do
+    pub fn is_spread(&self) -> bool {
+        matches!(self, Self::AdjacentFoes | Self::All)
+    }
+}
+
+/// The stats that can be raised or lowered by stages while a battle is running
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, Serialize, Deserialize)]
+pub enum StageStat {
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+    Accuracy,
+    Evasion,
+}
+
+/// Something that a move does besides, or instead of, dealing damage.
+///
+/// Every `chance` it's a percentage from 0 to 100
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub enum MoveEffect {
+    /// Raises (positive `stages`) or lowers (negative `stages`) a stat of the target, or of the user when `affects_user`
+    StatChange { stat: StageStat, stages: i32, affects_user: bool, chance: i32 },
+    Burn { chance: i32 },
+    Poison { chance: i32 },
+    /// Poison that deals more damage every turn
+    BadPoison { chance: i32 },
+    Paralysis { chance: i32 },
+    Sleep { chance: i32 },
+    Freeze { chance: i32 },
+    Confusion { chance: i32 },
+    /// The target loses his action if it hasn't moved yet on this turn
+    Flinch { chance: i32 },
+    /// Drains HP from the target every turn, healing the user
+    LeechSeed,
+    /// The user recovers this percentage of the damage dealt
+    Drain { percent: i32 },
+    /// The user loses this percentage of the damage dealt
+    Recoil { percent: i32 },
+    /// Always deals this amount of damage, whatever the stats of the Pokémon are
+    FixedDamage { damage: i32 },
+    /// Lands critical hits more often
+    HighCriticalHitRatio,
+}
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The data of a move, as it's stored on the moves table of the `PokemonDB`
+pub struct PokemonMove {
+    #[property]
+    pub id: i32,
+    #[property]
+    pub name: String,
+    pub move_type: PokemonType,
+    pub category: MoveCategory,
+    // Zero for the moves that doesn't deal damage, or that deals a fixed amount of it
+    #[property]
+    pub power: i32,
+    // Percentage from 0 to 100. `None` for the moves that never miss
+    pub accuracy: Option<i32>,
+    // The Power Points, that sets how many times the move can be used before resting
+    #[property]
+    pub pp: i32,
+    // Moves with a higher priority are always used before the ones with a lower one, whatever the speed is
+    #[property]
+    pub priority: i32,
+    pub effect: Option<MoveEffect>,
+    // The Pokémon that the move hits on the double battles
+    #[serde(default)]
+    pub target: MoveTarget,
+}
+
+#[methods]
+impl PokemonMove {
+    // The mandatory constructor needed by `Godot - Rust `
+    pub fn new(_owner: &Node2D) -> Self {
+        Self {
+            id: 0,
+            name: "".to_string(),
+            move_type: PokemonType::default(),
+            category: MoveCategory::default(),
+            power: 0,
+            accuracy: None,
+            pp: 0,
+            priority: 0,
+            effect: None,
+            target: MoveTarget::default(),
+        }
+    }
+
+    // The constructor that given the needed data, creates a new move
+    pub fn new_move(id: i32, name: &str, move_type: PokemonType, category: MoveCategory, power: i32,
+        accuracy: Option<i32>, pp: i32, priority: i32, effect: Option<MoveEffect>
+        ) -> Self {
+        Self {
+            id: id,
+            name: name.to_string(),
+            move_type: move_type,
+            category: category,
+            power: power,
+            accuracy: accuracy,
+            pp: pp,
+            priority: priority,
+            effect: effect,
+            target: MoveTarget::default(),
+        }
+    }
+
+    /// Sets the Pokémon that the move hits, for the moves that don't hit a single one
+    pub fn with_target(mut self, target: MoveTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    #[method]
+    /// The spanish name of the type of the move
+    pub fn get_type_name(&self) -> String {
+        self.move_type.to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The spanish name of the category of the move
+    pub fn get_category_name(&self) -> String {
+        self.category.to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The accuracy of the move, or -1 if it never misses
+    pub fn get_accuracy(&self) -> i32 {
+        self.accuracy.unwrap_or(-1)
+    }
+}
+
+/// One of the moves known by a Pokémon, with the Power Points that it still has
+#[derive(PartialEq, Clone, Debug, Default, ToVariant, Serialize, Deserialize)]
+pub struct MoveSlot {
+    pub move_name: String,
+    pub current_pp: i32,
+    pub max_pp: i32,
+}
+
+impl MoveSlot {
+    /// A new slot for the given move, with all his PP
+    pub fn new(pokemon_move: &PokemonMove) -> Self {
+        Self {
+            move_name: pokemon_move.name.to_owned(),
+            current_pp: pokemon_move.pp,
+            max_pp: pokemon_move.pp,
+        }
+    }
+}
+
+/// The moves known by a Pokémon. Never more than `MAX_MOVES`
+#[derive(PartialEq, Clone, Debug, Default, ToVariant, Serialize, Deserialize)]
+pub struct MoveSet {
+    slots: Vec<MoveSlot>,
+}
+
+impl MoveSet {
+    pub fn new() -> Self {
+        Self { slots: Vec::new() }
+    }
+
+    pub fn slots(&self) -> &Vec<MoveSlot> {
+        &self.slots
+    }
+
+    pub fn get(&self, index: usize) -> Option<&MoveSlot> {
+        self.slots.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots.len() >= MAX_MOVES
+    }
+
+    pub fn knows(&self, move_name: &str) -> bool {
+        self.slots.iter().any(|slot| slot.move_name == move_name)
+    }
+
+    /// Adds the move on the first free slot
+    pub fn learn(&mut self, pokemon_move: &PokemonMove) -> Result<(), String> {
+        if self.knows(&pokemon_move.name) {
+            return Err(format!("{} it's already known", pokemon_move.name));
+        }
+        if self.is_full() {
+            return Err(format!("There's no room left to learn {}", pokemon_move.name));
+        }
+        self.slots.push(MoveSlot::new(pokemon_move));
+        Ok(())
+    }
+
+    /// Forgets the move at the given slot to learn a new one on his place. Returns the name of the forgotten move
+    pub fn replace(&mut self, index: usize, pokemon_move: &PokemonMove) -> Result<String, String> {
+        if self.knows(&pokemon_move.name) {
+            return Err(format!("{} it's already known", pokemon_move.name));
+        }
+        match self.slots.get_mut(index) {
+            Some(slot) => Ok(std::mem::replace(slot, MoveSlot::new(pokemon_move)).move_name),
+            None => Err(format!("There's no move at index {}", index))
+        }
+    }
+
+    /// Forgets the move at the given slot. A Pokémon must always know at least one move
+    pub fn forget(&mut self, index: usize) -> Result<String, String> {
+        if index >= self.slots.len() {
+            return Err(format!("There's no move at index {}", index));
+        }
+        if self.slots.len() == 1 {
+            return Err("The last move can't be forgotten".to_string());
+        }
+        Ok(self.slots.remove(index).move_name)
+    }
+
+    /// Spends one PP of the move at the given slot. Returns false if there weren't any left
+    pub fn use_pp(&mut self, index: usize) -> bool {
+        match self.slots.get_mut(index) {
+            Some(slot) if slot.current_pp > 0 => {
+                slot.current_pp -= 1;
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Refills the PP of every move
+    pub fn restore_pp(&mut self) {
+        for slot in self.slots.iter_mut() {
+            slot.current_pp = slot.max_pp;
+        }
+    }
+
+    /// Restores up to `amount` PP on every move. Returns false if all of them were already full
+    pub fn recover_pp(&mut self, amount: i32) -> bool {
+        let mut recovered = false;
+        for slot in self.slots.iter_mut() {
+            if slot.current_pp < slot.max_pp {
+                slot.current_pp = (slot.current_pp + amount.max(0)).min(slot.max_pp);
+                recovered = true;
+            }
+        }
+        recovered
+    }
+
+    /// True if at least one of the moves still can be used
+    pub fn has_pp_left(&self) -> bool {
+        self.slots.iter().any(|slot| slot.current_pp > 0)
+    }
+}
@@ -1,23 +1,75 @@
 use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::pokemon_type::PokemonType;
+use super::ability::Ability;
+use super::pokemon_stats::StatSet;
+use super::personality::GenderRatio;
+use super::experience::GrowthRate;
+use super::evolution::Evolution;
+use super::pokemon_move::MAX_MOVES;
+use super::language::{Language, LocalizedText};
+
+/// The last national Pokédex number of every generation, from the first one onwards
+const GENERATION_LAST_NUMBERS: [i32; 9] = [151, 251, 386, 493, 649, 721, 809, 905, 1025];
+
+/// A move that a specie learns when it reaches certain level
+#[derive(PartialEq, Clone, Debug, ToVariant, Serialize, Deserialize)]
+pub struct LearnableMove {
+    pub level: i32,
+    pub move_name: String,
+}
+
+impl LearnableMove {
+    pub fn new(level: i32, move_name: &str) -> Self {
+        Self { level, move_name: move_name.to_string() }
+    }
+}
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
-#[derive(Debug)]
-/// The base class for a Pokemon data representation and data manipulation, holded as a Godot Node using another node as parent and as a DB simulator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// The base class for a Pokemon data representation and data manipulation, holded as a Godot Node using another node as parent and as a DB simulator.
+///
+/// The species of the game are read from the `godot/Data/pokemon_species.json` data file
 pub struct PokemonSpecie {
     #[property]
     pub id: i32,
     #[property]
     pub name: String,
-    #[property]
-    pub type1: String,
-    #[property]
-    pub type2: String,
+    pub type1: PokemonType,
+    // `None` when the specie only has one type
+    #[serde(default)]
+    pub type2: Option<PokemonType>,
+    // In meters
     #[property]
     pub height: f64,
+    // The weight in kilograms, shown on the Pokédex
     #[property(default = 40.0)]
-    pub tall: f64,
-    pokedex_description: String,
+    pub weight: f64,
+    pub base_stats: StatSet,
+    pub growth_rate: GrowthRate,
+    // The base experience given when a Pokémon of this specie it's defeated
+    #[property]
+    pub base_experience_yield: i32,
+    // The effort values given when a Pokémon of this specie it's defeated
+    pub ev_yield: StatSet,
+    // How easy it's to catch a Pokémon of this specie, from 1 (the hardest) to 255 (the easiest)
+    #[property]
+    pub catch_rate: i32,
+    // The abilities that the individuals of this specie can have. The first one it's the most common
+    #[serde(default)]
+    pub abilities: Vec<Ability>,
+    pub learnset: Vec<LearnableMove>,
+    #[serde(default)]
+    pub evolutions: Vec<Evolution>,
+    // How the individuals of this specie are split between males and females
+    #[serde(default)]
+    pub gender_ratio: GenderRatio,
+    // The kind of Pokémon that the Pokédex says it is, like "Pokémon Semilla"
+    pub category: LocalizedText,
+    pub pokedex_description: LocalizedText,
 }
 
 #[methods]
@@ -27,26 +79,85 @@ impl PokemonSpecie {
             Self { 
                 id: 0, 
                 name: "".to_string(),
-                type1: "".to_string(),
-                type2: "".to_string(),
+                type1: PokemonType::default(),
+                type2: None,
                 height: 0.0, 
-                tall: 0.0, 
-                pokedex_description: "".to_string(),
+                weight: 0.0, 
+                base_stats: StatSet::default(),
+                growth_rate: GrowthRate::default(),
+                base_experience_yield: 0,
+                ev_yield: StatSet::default(),
+                catch_rate: 0,
+                abilities: Vec::new(),
+                learnset: Vec::new(),
+                evolutions: Vec::new(),
+                gender_ratio: GenderRatio::default(),
+                category: LocalizedText::default(),
+                pokedex_description: LocalizedText::default(),
                 } 
             }
     
-    // The constructor that given the needed data, creates a new Pokémon Specie
-    pub fn new_pokemon(id: i32, name: &str, type1: &str, type2: &str, height: f64, tall: f64,
-        pokedex_description: &str
-        ) -> Self {
-        Self {
-            id: id,
-            name: name.to_string(),
-            type1: type1.to_string(),
-            type2: type2.to_string(),
-            height: height,
-            tall: tall,
-            pokedex_description: pokedex_description.to_string(),
+    /// The generation where the specie was introduced, decided by his national Pokédex number.
+    /// 0 when the number is outside of the national Pokédex
+    pub fn generation(&self) -> i32 {
+        if self.id < 1 {
+            return 0;
+        }
+        GENERATION_LAST_NUMBERS.iter()
+            .position(|last_number| self.id <= *last_number)
+            .map_or(0, |idx| idx as i32 + 1)
+    }
+
+    /// True if the specie has the given type, as his first or as his second one
+    pub fn has_type(&self, pokemon_type: PokemonType) -> bool {
+        self.type1 == pokemon_type || self.type2 == Some(pokemon_type)
+    }
+
+    #[method]
+    /// The spanish name of the first type of the specie
+    pub fn get_type1_name(&self) -> String {
+        self.type1.to_spanish_str().to_string()
+    }
+
+    #[method]
+    /// The spanish name of the second type of the specie, or an empty String if it only has one
+    pub fn get_type2_name(&self) -> String {
+        self.type2.map_or("", |pokemon_type| pokemon_type.to_spanish_str()).to_string()
+    }
+
+    /// The kind of Pokémon that the specie is, in the given language
+    pub fn category(&self, language: Language) -> &str {
+        self.category.get(language)
+    }
+
+    /// The text that describes the specie on the Pokédex, in the given language
+    pub fn pokedex_description(&self, language: Language) -> &str {
+        self.pokedex_description.get(language)
+    }
+
+    #[method]
+    /// The base stats of the specie, as a Dictionary with one entry per stat
+    pub fn get_base_stats(&self) -> StatSet {
+        self.base_stats.clone()
+    }
+
+    /// The names of the moves that this specie learns exactly at the given level
+    pub fn moves_learnt_at(&self, level: i32) -> Vec<String> {
+        self.learnset.iter()
+            .filter(|learnable| learnable.level == level)
+            .map(|learnable| learnable.move_name.to_owned())
+            .collect()
+    }
+
+    /// The moves that a wild Pokémon of this specie knows at the given level: the last `MAX_MOVES` that it has learnt
+    pub fn moves_known_at(&self, level: i32) -> Vec<String> {
+        let mut known: Vec<String> = Vec::new();
+        for learnable in self.learnset.iter().filter(|learnable| learnable.level <= level) {
+            if !known.contains(&learnable.move_name) {
+                known.push(learnable.move_name.to_owned());
+            }
         }
+        let forgotten = known.len().saturating_sub(MAX_MOVES);
+        known.split_off(forgotten)
     }
 }
\ No newline at end of file
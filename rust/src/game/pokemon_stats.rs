@@ -0,0 +1,271 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+/// The highest value that an individual value (IV) can have
+pub const MAX_IV: i32 = 31;
+/// The highest amount of effort values (EVs) that a single stat can have
+pub const MAX_EVS_PER_STAT: i32 = 252;
+/// The highest amount of effort values (EVs) that a Pokémon can have, adding up all his stats
+pub const MAX_TOTAL_EVS: i32 = 510;
+
+pub const MIN_LEVEL: i32 = 1;
+pub const MAX_LEVEL: i32 = 100;
+
+/// Each one of the six stats of a Pokémon
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, ToVariant, Serialize, Deserialize)]
+pub enum Stat {
+    Hp,
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+}
+
+impl Stat {
+    // Returns a Vec<Stat> with all the Variants
+    pub fn values() -> Vec<Stat> {
+        vec![Self::Hp, Self::Attack, Self::Defense, Self::SpecialAttack, Self::SpecialDefense, Self::Speed]
+    }
+
+    // Returns the stat's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Hp => "PS",
+            Self::Attack => "Ataque",
+            Self::Defense => "Defensa",
+            Self::SpecialAttack => "At. Esp.",
+            Self::SpecialDefense => "Def. Esp.",
+            Self::Speed => "Velocidad",
+        }
+    }
+}
+
+/// A value for every one of the six stats. Used for base stats, IVs, EVs and for the final calculated stats
+#[derive(PartialEq, Clone, Debug, Default, ToVariant, FromVariant, Serialize, Deserialize)]
+pub struct StatSet {
+    pub hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub special_attack: i32,
+    pub special_defense: i32,
+    pub speed: i32,
+}
+
+impl StatSet {
+    pub fn new(hp: i32, attack: i32, defense: i32, special_attack: i32, special_defense: i32, speed: i32) -> Self {
+        Self { hp, attack, defense, special_attack, special_defense, speed }
+    }
+
+    /// Creates a StatSet with the same value on every stat
+    pub fn uniform(value: i32) -> Self {
+        Self::new(value, value, value, value, value, value)
+    }
+
+    /// Six random individual values, between 0 and `MAX_IV`
+    pub fn random_ivs<R: Rng>(rng: &mut R) -> Self {
+        Self::new(
+            rng.gen_range(0..=MAX_IV), rng.gen_range(0..=MAX_IV), rng.gen_range(0..=MAX_IV),
+            rng.gen_range(0..=MAX_IV), rng.gen_range(0..=MAX_IV), rng.gen_range(0..=MAX_IV)
+        )
+    }
+
+    pub fn get(&self, stat: Stat) -> i32 {
+        match stat {
+            Stat::Hp => self.hp,
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack => self.special_attack,
+            Stat::SpecialDefense => self.special_defense,
+            Stat::Speed => self.speed,
+        }
+    }
+
+    pub fn set(&mut self, stat: Stat, value: i32) {
+        match stat {
+            Stat::Hp => self.hp = value,
+            Stat::Attack => self.attack = value,
+            Stat::Defense => self.defense = value,
+            Stat::SpecialAttack => self.special_attack = value,
+            Stat::SpecialDefense => self.special_defense = value,
+            Stat::Speed => self.speed = value,
+        }
+    }
+
+    /// The sum of all the stats
+    pub fn total(&self) -> i32 {
+        Stat::values().iter().map(|stat| self.get(*stat)).sum()
+    }
+
+    /// Forces every stat to be a valid individual value
+    pub fn clamp_ivs(&mut self) {
+        for stat in Stat::values() {
+            self.set(stat, self.get(stat).clamp(0, MAX_IV));
+        }
+    }
+
+    /// Adds the given effort values, without exceeding neither the per stat limit nor the total one.
+    ///
+    /// Returns the effort values that were really gained
+    pub fn add_evs(&mut self, gained: &StatSet) -> StatSet {
+        let mut really_gained = StatSet::default();
+        for stat in Stat::values() {
+            let room_left = (MAX_EVS_PER_STAT - self.get(stat)).min(MAX_TOTAL_EVS - self.total()).max(0);
+            let amount = gained.get(stat).clamp(0, room_left);
+            self.set(stat, self.get(stat) + amount);
+            really_gained.set(stat, amount);
+        }
+        really_gained
+    }
+
+    /// The difference, stat by stat, between this StatSet and an older one
+    pub fn delta(&self, old: &StatSet) -> StatSet {
+        let mut delta = StatSet::default();
+        for stat in Stat::values() {
+            delta.set(stat, self.get(stat) - old.get(stat));
+        }
+        delta
+    }
+}
+
+/// The nature of a Pokémon increases one of his stats by a 10%, and decreases another one by a 10%.
+///
+/// The five natures that increases and decreases the same stat are neutral
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum Nature {
+    Hardy, Lonely, Brave, Adamant, Naughty,
+    Bold, Docile, Relaxed, Impish, Lax,
+    Timid, Hasty, Serious, Jolly, Naive,
+    Modest, Mild, Quiet, Bashful, Rash,
+    Calm, Gentle, Sassy, Careful, Quirky,
+}
+
+impl Default for Nature {
+    fn default() -> Self { Nature::Hardy }
+}
+
+impl Nature {
+    // Returns a Vec<Nature> with all the Variants, ordered by his index
+    pub fn values() -> Vec<Nature> {
+        vec![
+            Self::Hardy, Self::Lonely, Self::Brave, Self::Adamant, Self::Naughty,
+            Self::Bold, Self::Docile, Self::Relaxed, Self::Impish, Self::Lax,
+            Self::Timid, Self::Hasty, Self::Serious, Self::Jolly, Self::Naive,
+            Self::Modest, Self::Mild, Self::Quiet, Self::Bashful, Self::Rash,
+            Self::Calm, Self::Gentle, Self::Sassy, Self::Careful, Self::Quirky,
+        ]
+    }
+
+    /// Returns the nature with the given index, from 0 to 24. Values out of the range wraps around
+    pub fn from_index(index: u32) -> Nature {
+        Self::values()[(index % 25) as usize]
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Nature {
+        Self::from_index(rng.gen_range(0..25))
+    }
+
+    /// The stats modified by this nature, as (increased, decreased). Natures are ordered on a 5x5 grid,
+    /// where the row is the increased stat and the column the decreased one.
+    pub fn modified_stats(&self) -> (Stat, Stat) {
+        let order = [Stat::Attack, Stat::Defense, Stat::Speed, Stat::SpecialAttack, Stat::SpecialDefense];
+        let index = Self::values().iter().position(|nature| nature == self).unwrap();
+        (order[index / 5], order[index % 5])
+    }
+
+    /// True for the natures that doesn't modify any stat
+    pub fn is_neutral(&self) -> bool {
+        let (increased, decreased) = self.modified_stats();
+        increased == decreased
+    }
+
+    /// The multiplier that this nature applies to the given stat
+    pub fn modifier(&self, stat: Stat) -> f64 {
+        let (increased, decreased) = self.modified_stats();
+        if self.is_neutral() {
+            1.0
+        } else if stat == increased {
+            1.1
+        } else if stat == decreased {
+            0.9
+        } else {
+            1.0
+        }
+    }
+
+    // Returns the nature's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Hardy => "Fuerte", Self::Lonely => "Huraña", Self::Brave => "Audaz",
+            Self::Adamant => "Firme", Self::Naughty => "Pícara", Self::Bold => "Osada",
+            Self::Docile => "Dócil", Self::Relaxed => "Plácida", Self::Impish => "Agitada",
+            Self::Lax => "Floja", Self::Timid => "Miedosa", Self::Hasty => "Activa",
+            Self::Serious => "Seria", Self::Jolly => "Alegre", Self::Naive => "Ingenua",
+            Self::Modest => "Modesta", Self::Mild => "Afable", Self::Quiet => "Mansa",
+            Self::Bashful => "Tímida", Self::Rash => "Alocada", Self::Calm => "Serena",
+            Self::Gentle => "Amable", Self::Sassy => "Grosera", Self::Careful => "Cauta",
+            Self::Quirky => "Rara",
+        }
+    }
+}
+
+/// Calculates the final six stats of a Pokémon, using the standard formulas:
+///
+/// `HP = (2 * Base + IV + EV / 4) * Level / 100 + Level + 10`
+///
+/// `Stat = ((2 * Base + IV + EV / 4) * Level / 100 + 5) * Nature`
+pub fn calculate_stats(base_stats: &StatSet, ivs: &StatSet, evs: &StatSet, level: i32, nature: Nature) -> StatSet {
+    let mut stats = StatSet::default();
+    for stat in Stat::values() {
+        let core = (2 * base_stats.get(stat) + ivs.get(stat) + evs.get(stat) / 4) * level / 100;
+        let value = match stat {
+            Stat::Hp => core + level + 10,
+            _ => ((core + 5) as f64 * nature.modifier(stat)) as i32
+        };
+        stats.set(stat, value);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_the_stats_of_the_known_example() {
+        // The level 78 Adamant Garchomp that the games' formulas are usually explained with
+        let base_stats = StatSet::new(108, 130, 95, 80, 85, 102);
+        let ivs = StatSet::new(24, 12, 30, 16, 23, 5);
+        let evs = StatSet::new(74, 190, 91, 48, 84, 23);
+        assert_eq!(calculate_stats(&base_stats, &ivs, &evs, 78, Nature::Adamant), StatSet::new(289, 278, 193, 135, 171, 171));
+        assert_eq!(calculate_stats(&base_stats, &ivs, &evs, 78, Nature::Hardy).attack, 253);
+    }
+
+    #[test]
+    fn the_effort_values_stop_at_the_per_stat_and_the_total_limits() {
+        let mut evs = StatSet::new(250, 0, 0, 0, 0, 0);
+        assert_eq!(evs.add_evs(&StatSet::new(3, 2, 0, 0, 0, -4)), StatSet::new(2, 2, 0, 0, 0, 0));
+        assert_eq!(evs.hp, MAX_EVS_PER_STAT);
+
+        let mut evs = StatSet::new(MAX_EVS_PER_STAT, MAX_EVS_PER_STAT, 0, 0, 0, 0);
+        assert_eq!(evs.add_evs(&StatSet::uniform(4)), StatSet::new(0, 0, 4, 2, 0, 0));
+        assert_eq!(evs.total(), MAX_TOTAL_EVS);
+        assert_eq!(evs.add_evs(&StatSet::uniform(4)), StatSet::default());
+    }
+
+    #[test]
+    fn every_nature_raises_and_lowers_a_stat_except_the_neutral_ones() {
+        let neutral: Vec<Nature> = Nature::values().into_iter().filter(Nature::is_neutral).collect();
+        assert_eq!(neutral, vec![Nature::Hardy, Nature::Docile, Nature::Serious, Nature::Bashful, Nature::Quirky]);
+
+        assert_eq!(Nature::Adamant.modified_stats(), (Stat::Attack, Stat::SpecialAttack));
+        assert_eq!(Nature::Adamant.modifier(Stat::Attack), 1.1);
+        assert_eq!(Nature::Adamant.modifier(Stat::SpecialAttack), 0.9);
+        assert_eq!(Nature::Adamant.modifier(Stat::Speed), 1.0);
+        for nature in Nature::values() {
+            assert_eq!(nature.modifier(Stat::Hp), 1.0, "{:?}", nature);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Every elemental type that a Pokémon or a move can have
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum PokemonType {
+    Normal,
+    Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl Default for PokemonType {
+    fn default() -> Self { PokemonType::Normal }
+}
+
+impl PokemonType {
+    // Returns a Vec<PokemonType> with all the Variants
+    pub fn values() -> Vec<PokemonType> {
+        vec![
+            Self::Normal, Self::Fire, Self::Water, Self::Electric, Self::Grass, Self::Ice,
+            Self::Fighting, Self::Poison, Self::Ground, Self::Flying, Self::Psychic, Self::Bug,
+            Self::Rock, Self::Ghost, Self::Dragon, Self::Dark, Self::Steel, Self::Fairy
+        ]
+    }
+
+    /// Converts the spanish, galician or english name of a type to his `PokemonType` counterpart.
+    ///
+    /// Returns `None` when the name doesn't match any type.
+    pub fn from_name<S: AsRef<str>>(name: S) -> Option<PokemonType> {
+        let name = name.as_ref().trim();
+        Self::values().into_iter().find(|pokemon_type|
+            pokemon_type.to_spanish_str().eq_ignore_ascii_case(name) ||
+            pokemon_type.to_galician_str().eq_ignore_ascii_case(name) ||
+            pokemon_type.to_english_str().eq_ignore_ascii_case(name)
+        )
+    }
+
+    // Returns the type's name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Fuego",
+            Self::Water => "Agua",
+            Self::Electric => "Eléctrico",
+            Self::Grass => "Planta",
+            Self::Ice => "Hielo",
+            Self::Fighting => "Lucha",
+            Self::Poison => "Veneno",
+            Self::Ground => "Tierra",
+            Self::Flying => "Volador",
+            Self::Psychic => "Psíquico",
+            Self::Bug => "Bicho",
+            Self::Rock => "Roca",
+            Self::Ghost => "Fantasma",
+            Self::Dragon => "Dragón",
+            Self::Dark => "Siniestro",
+            Self::Steel => "Acero",
+            Self::Fairy => "Hada",
+        }
+    }
+
+    // Returns the type's name translated into galician
+    pub fn to_galician_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Lume",
+            Self::Water => "Auga",
+            Self::Electric => "Eléctrico",
+            Self::Grass => "Planta",
+            Self::Ice => "Xeo",
+            Self::Fighting => "Loita",
+            Self::Poison => "Veleno",
+            Self::Ground => "Terra",
+            Self::Flying => "Voador",
+            Self::Psychic => "Psíquico",
+            Self::Bug => "Bicho",
+            Self::Rock => "Rocha",
+            Self::Ghost => "Pantasma",
+            Self::Dragon => "Dragón",
+            Self::Dark => "Sinistro",
+            Self::Steel => "Aceiro",
+            Self::Fairy => "Fada",
+        }
+    }
+
+    // Returns the type's name in english
+    pub fn to_english_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Fire",
+            Self::Water => "Water",
+            Self::Electric => "Electric",
+            Self::Grass => "Grass",
+            Self::Ice => "Ice",
+            Self::Fighting => "Fighting",
+            Self::Poison => "Poison",
+            Self::Ground => "Ground",
+            Self::Flying => "Flying",
+            Self::Psychic => "Psychic",
+            Self::Bug => "Bug",
+            Self::Rock => "Rock",
+            Self::Ghost => "Ghost",
+            Self::Dragon => "Dragon",
+            Self::Dark => "Dark",
+            Self::Steel => "Steel",
+            Self::Fairy => "Fairy",
+        }
+    }
+
+    /// The damage multiplier of a move of this type hitting a Pokémon of the `defending` type.
+    ///
+    /// Returns 2.0 when it's super effective, 0.5 when it's not very effective, 0.0 when the defending type it's
+    /// immune and 1.0 otherwise
+    pub fn effectiveness(&self, defending: PokemonType) -> f64 {
+        use PokemonType::*;
+
+        // (super effective against, not very effective against, doesn't affect)
+        let (super_effective, not_very_effective, no_effect): (&[PokemonType], &[PokemonType], &[PokemonType]) = match self {
+            Normal => (&[], &[Rock, Steel], &[Ghost]),
+            Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+            Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+            Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+            Grass => (&[Water, Ground, Rock], &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel], &[]),
+            Ice => (&[Grass, Ground, Flying, Dragon], &[Fire, Water, Ice, Steel], &[]),
+            Fighting => (&[Normal, Ice, Rock, Dark, Steel], &[Poison, Flying, Psychic, Bug, Fairy], &[Ghost]),
+            Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+            Ground => (&[Fire, Electric, Poison, Rock, Steel], &[Grass, Bug], &[Flying]),
+            Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+            Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+            Bug => (&[Grass, Psychic, Dark], &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy], &[]),
+            Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+            Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+            Dragon => (&[Dragon], &[Steel], &[Fairy]),
+            Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+            Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+            Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+        };
+
+        if no_effect.contains(&defending) {
+            0.0
+        } else if super_effective.contains(&defending) {
+            2.0
+        } else if not_very_effective.contains(&defending) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// The combined multiplier of a move of this type against a Pokémon with one or two types
+    pub fn effectiveness_against(&self, type1: PokemonType, type2: Option<PokemonType>) -> f64 {
+        self.effectiveness(type1) * type2.map_or(1.0, |second_type| self.effectiveness(second_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PokemonType::*;
+
+    #[test]
+    fn the_chart_matches_the_known_matchups() {
+        assert_eq!(Water.effectiveness(Fire), 2.0);
+        assert_eq!(Fire.effectiveness(Water), 0.5);
+        assert_eq!(Grass.effectiveness(Water), 2.0);
+        assert_eq!(Normal.effectiveness(Normal), 1.0);
+        assert_eq!(Fairy.effectiveness(Dragon), 2.0);
+
+        let immunities: Vec<(PokemonType, PokemonType)> = PokemonType::values().into_iter()
+            .flat_map(|attacking| PokemonType::values().into_iter().map(move |defending| (attacking, defending)))
+            .filter(|(attacking, defending)| attacking.effectiveness(*defending) == 0.0)
+            .collect();
+        assert_eq!(immunities, vec![
+            (Normal, Ghost), (Electric, Ground), (Fighting, Ghost), (Poison, Steel),
+            (Ground, Flying), (Psychic, Dark), (Ghost, Normal), (Dragon, Fairy),
+        ]);
+    }
+
+    #[test]
+    fn the_two_types_of_a_pokemon_multiply_each_other() {
+        assert_eq!(Ice.effectiveness_against(Dragon, Some(Flying)), 4.0);
+        assert_eq!(Fire.effectiveness_against(Water, Some(Rock)), 0.25);
+        assert_eq!(Electric.effectiveness_against(Water, Some(Ground)), 0.0);
+        assert_eq!(Rock.effectiveness_against(Fire, Some(Fighting)), 1.0);
+        assert_eq!(Water.effectiveness_against(Fire, None), Water.effectiveness(Fire));
+    }
+
+    #[test]
+    fn every_type_is_found_by_any_of_his_names() {
+        for pokemon_type in PokemonType::values() {
+            for name in [pokemon_type.to_spanish_str(), pokemon_type.to_galician_str(), pokemon_type.to_english_str()] {
+                assert_eq!(PokemonType::from_name(name), Some(pokemon_type), "{}", name);
+            }
+        }
+        assert_eq!(PokemonType::from_name("  fuego "), Some(Fire));
+        assert_eq!(PokemonType::from_name("Sonido"), None);
+    }
+}
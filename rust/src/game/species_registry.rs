@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::pokemon_specie::PokemonSpecie;
+use super::pokemon_type::PokemonType;
+
+/// The version of the species data file that this build of the game knows how to read
+pub const SPECIES_DATA_VERSION: u32 = 2;
+
+/// Where the species data file lives on the Godot project
+pub const SPECIES_DATA_PATH: &str = "res://godot/Data/pokemon_species.json";
+/// A copy of the species data file bundled inside the library when it's compiled. Only used when the data file of the
+/// project can't be read
+const BUNDLED_SPECIES_DATA: &str = include_str!("../../../godot/Data/pokemon_species.json");
+
+/// Reads the whole text of a file of the project, by his `res://` path
+pub type DataReader = fn(&str) -> Result<String, String>;
+
+/// The registry of the game, built only once, the first time that it's needed
+static SPECIES_REGISTRY: OnceLock<Result<SpeciesRegistry, String>> = OnceLock::new();
+/// Reads a `res://` file through Godot. Set when Godot loads the library, so the game reads the data file of the project,
+/// also when it's packed on an exported game. Without it, the file it's read from the disk
+static SPECIES_DATA_READER: OnceLock<DataReader> = OnceLock::new();
+
+/// The layout of the species data file. Every entry is checked on his own, so all the malformed ones get reported
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpeciesDataFile {
+    version: u32,
+    species: Vec<Value>,
+}
+
+/// The only place where the data of every specie lives.
+///
+/// The database, the Pokédex and every individual `Pokemon` don't keep a copy of it: they hold the Pokédex number of
+/// the specie and look it up here. It's indexed by name, type, generation and evolution family, so every query
+/// only visits the species that it returns
+#[derive(Debug, Clone, Default)]
+pub struct SpeciesRegistry {
+    species: BTreeMap<i32, PokemonSpecie>,
+    // The Pokédex number of every specie, by his name in lowercase
+    by_name: HashMap<String, i32>,
+    // The Pokédex numbers of the species of every type, as their first or as their second one, in order
+    by_type: HashMap<PokemonType, Vec<i32>>,
+    by_generation: BTreeMap<i32, Vec<i32>>,
+    // The first specie of the evolution family of every specie, the one that doesn't evolve from any other
+    family_roots: HashMap<i32, i32>,
+    // The Pokédex numbers of every evolution family, in order, by the first specie of the family
+    families: HashMap<i32, Vec<i32>>,
+}
+
+impl SpeciesRegistry {
+    /// The registry of the species of the game, or the problems that the data file has.
+    ///
+    /// It's read from the data file of the project when it's first needed, so the edited species only need the game
+    /// to be launched again. The copy bundled on the library it's only used when that file can't be read
+    pub fn global() -> Result<&'static SpeciesRegistry, String> {
+        SPECIES_REGISTRY.get_or_init(|| match Self::read_species_data() {
+                Ok(json) => Self::from_json(&json),
+                Err(_) => Self::from_json(BUNDLED_SPECIES_DATA)
+            })
+            .as_ref()
+            .map_err(|err| err.to_owned())
+    }
+
+    /// Sets how the data file of the project gets read. Only the first reader set it's used, and only when it's set
+    /// before the registry it's first needed
+    pub fn set_data_reader(reader: DataReader) {
+        let _ = SPECIES_DATA_READER.set(reader);
+    }
+
+    /// The content of the species data file of the project, through the reader set by Godot or, outside of it, like on
+    /// the tools and the tests, from the project where this crate lives
+    fn read_species_data() -> Result<String, String> {
+        match SPECIES_DATA_READER.get() {
+            Some(reader) => reader(SPECIES_DATA_PATH),
+            None => {
+                let relative_path = SPECIES_DATA_PATH.trim_start_matches("res://");
+                let species_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(relative_path);
+                fs::read_to_string(&species_path).map_err(|err| format!("Can't open {}: {}", species_path.display(), err))
+            }
+        }
+    }
+
+    /// Builds a registry out of the content of a species data file.
+    ///
+    /// Returns an error with one line per problem found: a wrong version, an entry that can't be read as a `PokemonSpecie`,
+    /// or a Pokédex number used by two entries
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let data_file: SpeciesDataFile = serde_json::from_str(json)
+            .map_err(|err| format!("The species data file is malformed: {}", err))?;
+        if data_file.version != SPECIES_DATA_VERSION {
+            return Err(format!("The species data file has the version {}, but the game reads the version {}",
+                data_file.version, SPECIES_DATA_VERSION));
+        }
+
+        let mut registry = Self::default();
+        let mut errors = Vec::new();
+        for (idx, entry) in data_file.species.into_iter().enumerate() {
+            let entry_name = match entry.get("name").and_then(Value::as_str) {
+                Some(name) => format!("#{} ({})", idx, name),
+                None => format!("#{}", idx)
+            };
+            match serde_json::from_value::<PokemonSpecie>(entry) {
+                Ok(pokemon_specie) if registry.contains(pokemon_specie.id) =>
+                    errors.push(format!("Species entry {}: the Pokédex number {} is already used", entry_name, pokemon_specie.id)),
+                Ok(pokemon_specie) => { registry.species.insert(pokemon_specie.id, pokemon_specie); },
+                Err(err) => errors.push(format!("Species entry {}: {}", entry_name, err))
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        registry.build_indexes();
+        Ok(registry)
+    }
+
+    /// Fills the indexes used by the queries, once every specie is on the registry
+    fn build_indexes(&mut self) {
+        // The specie that every specie evolves from, if any
+        let mut pre_evolutions: HashMap<i32, i32> = HashMap::new();
+        for pokemon_specie in self.species.values() {
+            self.by_name.insert(pokemon_specie.name.to_lowercase(), pokemon_specie.id);
+            self.by_type.entry(pokemon_specie.type1).or_default().push(pokemon_specie.id);
+            if let Some(type2) = pokemon_specie.type2.filter(|type2| *type2 != pokemon_specie.type1) {
+                self.by_type.entry(type2).or_default().push(pokemon_specie.id);
+            }
+            self.by_generation.entry(pokemon_specie.generation()).or_default().push(pokemon_specie.id);
+            for evolution in pokemon_specie.evolutions.iter() {
+                pre_evolutions.entry(evolution.evolves_into).or_insert(pokemon_specie.id);
+            }
+        }
+
+        for id in self.species.keys() {
+            let mut root = *id;
+            // Bounded by the number of species, so a malformed chain of evolutions that loops can't hang the game
+            for _ in 0..self.species.len() {
+                match pre_evolutions.get(&root) {
+                    Some(pre_evolution) if *pre_evolution != *id => root = *pre_evolution,
+                    _ => break
+                }
+            }
+            self.family_roots.insert(*id, root);
+            self.families.entry(root).or_default().push(*id);
+        }
+    }
+
+    /// The specie with the given Pokédex number, if it's on the game
+    pub fn get(&self, id: i32) -> Option<&PokemonSpecie> {
+        self.species.get(&id)
+    }
+
+    /// The specie with the given name, ignoring the case and the surrounding whitespace
+    pub fn get_by_name(&self, name: &str) -> Option<&PokemonSpecie> {
+        self.by_name.get(&name.trim().to_lowercase())
+            .and_then(|id| self.get(*id))
+    }
+
+    /// The species that have the given type, as their first or as their second one, ordered by their Pokédex number
+    pub fn of_type(&self, pokemon_type: PokemonType) -> Vec<&PokemonSpecie> {
+        self.lookup(self.by_type.get(&pokemon_type))
+    }
+
+    /// The species introduced on the given generation, ordered by their Pokédex number
+    pub fn of_generation(&self, generation: i32) -> Vec<&PokemonSpecie> {
+        self.lookup(self.by_generation.get(&generation))
+    }
+
+    /// Every specie of the evolution family of the given one, itself included, ordered by their Pokédex number.
+    /// Empty when the specie isn't on the game
+    pub fn evolution_family(&self, id: i32) -> Vec<&PokemonSpecie> {
+        self.lookup(self.family_roots.get(&id).and_then(|root| self.families.get(root)))
+    }
+
+    fn lookup(&self, ids: Option<&Vec<i32>>) -> Vec<&PokemonSpecie> {
+        ids.map_or(Vec::new(), |ids| ids.iter().filter_map(|id| self.get(*id)).collect())
+    }
+
+    pub fn contains(&self, id: i32) -> bool {
+        self.species.contains_key(&id)
+    }
+
+    /// Every specie of the registry, ordered by his Pokédex number
+    pub fn species(&self) -> impl Iterator<Item = &PokemonSpecie> {
+        self.species.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+}
+
+/// Looks up the specie with the given Pokédex number on the registry of the game.
+/// `None` when it isn't on the game, or when the species data file can't be read
+pub fn pokemon_specie_by_id(id: i32) -> Option<&'static PokemonSpecie> {
+    SpeciesRegistry::global().ok()?.get(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(species: Vec<&PokemonSpecie>) -> Vec<i32> {
+        species.iter().map(|specie| specie.id).collect()
+    }
+
+    fn registry() -> &'static SpeciesRegistry {
+        SpeciesRegistry::global().expect("The shipped species data must load")
+    }
+
+    #[test]
+    fn finds_the_species_by_number_and_by_name() {
+        assert_eq!(registry().len(), 151);
+        assert_eq!(registry().get(25).map(|specie| specie.name.as_str()), Some("Pikachu"));
+        assert!(registry().get(0).is_none());
+        assert!(registry().get(152).is_none());
+        assert_eq!(registry().get_by_name("  pIKAchu ").map(|specie| specie.id), Some(25));
+        assert!(registry().get_by_name("Pikachu2").is_none());
+        assert_eq!(pokemon_specie_by_id(150).map(|specie| specie.name.as_str()), Some("Mewtwo"));
+    }
+
+    #[test]
+    fn the_queries_return_the_species_in_order() {
+        assert_eq!(ids(registry().of_type(PokemonType::Fire)), vec![4, 5, 6, 37, 38, 58, 59, 77, 78, 126, 136, 146]);
+        // The species with two types are on both of them
+        assert!(ids(registry().of_type(PokemonType::Poison)).contains(&1));
+        assert!(ids(registry().of_type(PokemonType::Grass)).contains(&1));
+        assert_eq!(registry().of_generation(1).len(), 151);
+        assert!(registry().of_generation(2).is_empty());
+
+        assert_eq!(ids(registry().evolution_family(5)), vec![4, 5, 6]);
+        assert_eq!(ids(registry().evolution_family(135)), vec![133, 134, 135, 136]);
+        assert_eq!(ids(registry().evolution_family(151)), vec![151]);
+        assert!(registry().evolution_family(999).is_empty());
+    }
+
+    #[test]
+    fn reports_every_problem_of_a_bad_data_file() {
+        assert!(SpeciesRegistry::from_json("{").unwrap_err().starts_with("The species data file is malformed"));
+        let old_version = SpeciesRegistry::from_json(r#"{ "version": 1, "species": [] }"#).unwrap_err();
+        assert!(old_version.contains("has the version 1"), "{}", old_version);
+
+        let bulbasaur = serde_json::to_value(registry().get(1).unwrap()).unwrap();
+        let json = serde_json::json!({
+            "version": SPECIES_DATA_VERSION,
+            "species": [bulbasaur.clone(), bulbasaur, { "name": "Sin datos" }]
+        });
+        let errors = SpeciesRegistry::from_json(&json.to_string()).unwrap_err();
+        let errors: Vec<&str> = errors.lines().collect();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(errors[0], "Species entry #1 (Bulbasaur): the Pokédex number 1 is already used");
+        assert!(errors[1].starts_with("Species entry #2 (Sin datos): "), "{}", errors[1]);
+    }
+}
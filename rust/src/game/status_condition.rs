@@ -0,0 +1,159 @@
+use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+use super::pokemon_type::PokemonType;
+
+/// Every how many steps a poisoned Pokémon loses HP while the player walks
+pub const OVERWORLD_POISON_STEPS: i32 = 4;
+/// The chance, as a percentage, that a frozen Pokémon thaws out when it tries to move
+pub const THAW_CHANCE: i32 = 20;
+/// The chance, as a percentage, that a paralyzed Pokémon can't move on his turn
+pub const FULL_PARALYSIS_CHANCE: i32 = 25;
+/// The chance, as a percentage, that a confused Pokémon hurts itself instead of moving
+pub const CONFUSION_SELF_HIT_CHANCE: i32 = 33;
+/// The power of the typeless physical attack that a confused Pokémon uses against itself
+pub const CONFUSION_SELF_HIT_POWER: i32 = 40;
+
+/// The persistent status conditions. A Pokémon can only have one of them, and it stays after the battle
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum MajorStatus {
+    Poison,
+    /// Poison whose damage grows every turn while the Pokémon stays on the battlefield
+    BadPoison,
+    Burn,
+    Paralysis,
+    /// The Pokémon wakes up when `turns_left` reaches zero
+    Sleep { turns_left: i32 },
+    Freeze,
+}
+
+impl MajorStatus {
+    /// A new sleep, that lasts from 1 to 3 turns
+    pub fn random_sleep<R: Rng>(rng: &mut R) -> MajorStatus {
+        MajorStatus::Sleep { turns_left: rng.gen_range(1..=3) }
+    }
+
+    /// True when both are the same condition, whatever their counters are. Both kinds of poison counts as the same one
+    pub fn same_kind(&self, other: &MajorStatus) -> bool {
+        match (self, other) {
+            (Self::Poison | Self::BadPoison, Self::Poison | Self::BadPoison) => true,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other)
+        }
+    }
+
+    /// Some types are immune to certain conditions, like the Fire types to the burns
+    pub fn is_immune(&self, type1: PokemonType, type2: Option<PokemonType>) -> bool {
+        let immune_types: Vec<PokemonType> = match self {
+            Self::Poison | Self::BadPoison => vec![PokemonType::Poison, PokemonType::Steel],
+            Self::Burn => vec![PokemonType::Fire],
+            Self::Paralysis => vec![PokemonType::Electric],
+            Self::Freeze => vec![PokemonType::Ice],
+            Self::Sleep { .. } => Vec::new()
+        };
+        immune_types.contains(&type1) || type2.map_or(false, |pokemon_type| immune_types.contains(&pokemon_type))
+    }
+
+    /// The short name that the UI shows next to the HP bar
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Poison => "ENV",
+            Self::BadPoison => "TOX",
+            Self::Burn => "QUE",
+            Self::Paralysis => "PAR",
+            Self::Sleep { .. } => "DOR",
+            Self::Freeze => "CON",
+        }
+    }
+
+    // Returns the status' name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Poison => "Envenenado",
+            Self::BadPoison => "Gravemente envenenado",
+            Self::Burn => "Quemado",
+            Self::Paralysis => "Paralizado",
+            Self::Sleep { .. } => "Dormido",
+            Self::Freeze => "Congelado",
+        }
+    }
+
+    // Returns the status' name translated into galician
+    pub fn to_galician_str(&self) -> &'static str {
+        match self {
+            Self::Poison => "Envelenado",
+            Self::BadPoison => "Gravemente envelenado",
+            Self::Burn => "Queimado",
+            Self::Paralysis => "Paralizado",
+            Self::Sleep { .. } => "Durmido",
+            Self::Freeze => "Conxelado",
+        }
+    }
+}
+
+/// The status conditions that only lives while the Pokémon stays on the battlefield
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum VolatileStatus {
+    /// The Pokémon snaps out of the confusion when `turns_left` reaches zero
+    Confusion { turns_left: i32 },
+    /// The Pokémon loses his action on this turn. Removed at the end of every turn
+    Flinch,
+    /// Loses HP at the end of every turn, that the opposite Pokémon recovers
+    LeechSeed,
+}
+
+impl VolatileStatus {
+    /// A new confusion, that lasts from 1 to 4 turns
+    pub fn random_confusion<R: Rng>(rng: &mut R) -> VolatileStatus {
+        VolatileStatus::Confusion { turns_left: rng.gen_range(1..=4) }
+    }
+
+    /// True when both are the same condition, whatever their counters are
+    pub fn same_kind(&self, other: &VolatileStatus) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    // Returns the status' name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Confusion { .. } => "Confuso",
+            Self::Flinch => "Retrocede",
+            Self::LeechSeed => "Drenadoras",
+        }
+    }
+}
+
+/// Any status condition, persistent or volatile. Used by the battle events, so the UI can show the right icon or text
+#[derive(PartialEq, Eq, Clone, Copy, Debug, ToVariant, FromVariant, Serialize, Deserialize)]
+pub enum StatusCondition {
+    Major(MajorStatus),
+    Volatile(VolatileStatus),
+}
+
+impl StatusCondition {
+    // Returns the status' name translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Major(status) => status.to_spanish_str(),
+            Self::Volatile(status) => status.to_spanish_str(),
+        }
+    }
+}
+
+/// The HP lost at the end of every turn by a Pokémon with the given status.
+///
+/// `bad_poison_turns` it's the number of turns that the Pokémon has been badly poisoned on the battlefield, starting on 1
+pub fn end_of_turn_damage(status: &MajorStatus, max_hp: i32, bad_poison_turns: i32) -> i32 {
+    match status {
+        MajorStatus::Poison => (max_hp / 8).max(1),
+        MajorStatus::BadPoison => (max_hp * bad_poison_turns.clamp(1, 15) / 16).max(1),
+        MajorStatus::Burn => (max_hp / 16).max(1),
+        _ => 0
+    }
+}
+
+/// The damage of the typeless physical attack that a confused Pokémon uses against itself
+pub fn confusion_self_hit_damage(level: i32, attack: i32, defense: i32) -> i32 {
+    ((2 * level / 5 + 2) * CONFUSION_SELF_HIT_POWER * attack / defense.max(1)) / 50 + 2
+}
@@ -2,6 +2,8 @@ use gdnative::prelude::*;
 use gdnative::api::AnimationPlayer;
 
 use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::city::GameCity;
+use crate::game::encounter;
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
@@ -10,6 +12,11 @@ pub struct TallGrass {
     animation_player: Option<TRef<'static, AnimationPlayer>>,
     grass_overlay: TRef<'static, Sprite>,
     grass_overlay_texture: Option<Ref<Texture>>,
+    // The encounter table of the city that this grass uses. Set on the Godot GUI for each TallGrass instance
+    #[property]
+    encounter_area: String,
+    // The city or town whose location scene contains this grass
+    city: Option<GameCity>,
 }
 
 impl RegisterSignal<Self> for TallGrass {
@@ -25,6 +32,8 @@ impl TallGrass {
             animation_player: None,
             grass_overlay: unsafe { Sprite::new().assume_shared().assume_safe() },
             grass_overlay_texture: None,
+            encounter_area: encounter::DEFAULT_ENCOUNTER_AREA.to_string(),
+            city: None,
         }
     }
 
@@ -40,6 +49,21 @@ impl TallGrass {
             .cast::<Texture>()
             .unwrap()
             .assume_shared() });
+
+        self.city = Self::find_city(base);
+    }
+
+    /// Goes up through the scene tree looking for the location scene (like "CampoDePruebas") where the grass lives
+    fn find_city(base: TRef<Node2D>) -> Option<GameCity> {
+        let mut node = base.get_parent();
+        while let Some(parent) = node {
+            let parent = unsafe { parent.assume_safe() };
+            if let Some(game_city) = GameCity::from_node_name(parent.name().to_string()) {
+                return Some(game_city);
+            }
+            node = parent.get_parent();
+        }
+        None
     }
 
     #[method]
@@ -47,11 +71,24 @@ impl TallGrass {
     fn _on_area2d_body_entered(&mut self, #[base] base: TRef<Node2D>, _body: Variant) {
         self.player_in_grass(base);
         self.animation_player.unwrap().play("Stepped", 0.0, 1.0, false);
+
+        // The Game checks for wild Pokémon on every step that the player ends inside the grass
+        if let Some(game_city) = &self.city {
+            let game = unsafe { base.get_node("/root/Game").unwrap().assume_safe() };
+            unsafe { game.call("_on_tall_grass_entered", &[
+                game_city.to_fmt_string().to_variant(), self.encounter_area.to_variant()
+            ]) };
+        }
     }
 
     #[method]
     // Receives a signal when a body leaves the TallGrass (connected on the Godot GUI)
     fn _on_area2d_body_exited(&mut self, #[base] base: &Node2D, _body: Variant) {
+        if self.city.is_some() {
+            let game = unsafe { base.get_node("/root/Game").unwrap().assume_safe() };
+            unsafe { game.call("_on_tall_grass_exited", &[self.encounter_area.to_variant()]) };
+        }
+
         if unsafe { self.grass_overlay.assume_shared().is_instance_sane() } {
             self.grass_overlay.queue_free();
             base.remove_child(self.grass_overlay);
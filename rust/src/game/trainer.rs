@@ -0,0 +1,275 @@
+use gdnative::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::game::code_abstractions::{dialogue_connections::DialogueBoxActions, signals::RegisterSignal};
+use crate::utils::consts::in_game_constant;
+use crate::utils::{all_trainers, utils};
+
+use super::battle::battle_ai::AiDifficulty;
+use super::battle::battle_event::BattleFormat;
+use super::game::Game;
+use super::player::PlayerDirection;
+use super::pokemon::Pokemon;
+use super::pokemon_stats::StatSet;
+use super::personality::{Personality, TrainerIds};
+use crate::game::species_registry::pokemon_specie_by_id;
+
+/// The tiles that a trainer watches in front of him when the Godot scene doesn't set another number
+pub const DEFAULT_SIGHT_TILES: i32 = 4;
+/// The IVs of every stat of the trainers' Pokémon, so the same trainer always fights with the same team
+pub const TRAINER_POKEMON_IVS: i32 = 15;
+
+/// One of the Pokémon of a trainer's team
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainerPokemon {
+    /// The Pokédex number of the specie
+    pub specie_id: i32,
+    pub level: i32,
+}
+
+impl TrainerPokemon {
+    pub fn new(specie_id: i32, level: i32) -> Self {
+        Self { specie_id, level }
+    }
+}
+
+/// The data of a trainer of the game, as it's stored on the `all_trainers` catalogue
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainerData {
+    /// Unique between all trainers. It's what gets saved when the player defeats him
+    pub id: String,
+    pub name: String,
+    pub team: Vec<TrainerPokemon>,
+    /// How the trainer chooses his actions on the battle
+    pub ai: AiDifficulty,
+    /// What the trainer says after walking up to the player, before the battle starts
+    pub pre_battle_dialogue: String,
+    /// Trainer pairs fight double battles, sending out two Pokémon at the same time
+    pub format: BattleFormat,
+}
+
+impl TrainerData {
+    pub fn new(id: &str, name: &str, team: Vec<TrainerPokemon>, ai: AiDifficulty, pre_battle_dialogue: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            team,
+            ai,
+            pre_battle_dialogue: pre_battle_dialogue.to_string(),
+            format: BattleFormat::Single,
+        }
+    }
+
+    /// Sets how many Pokémon the trainer sends out at the same time
+    pub fn with_format(mut self, format: BattleFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Creates the Pokémon of the trainer's team, fully healed. Species that aren't on the game are left out.
+    ///
+    /// Their personalities, and the trainer ids, are rolled from the id of the trainer, so they're the same on every battle
+    pub fn build_team(&self) -> Vec<Pokemon> {
+        let seed = self.id.bytes().fold(0u64, |seed, byte| seed.wrapping_mul(31).wrapping_add(byte as u64));
+        let mut rng = StdRng::seed_from_u64(seed);
+        let trainer_ids = TrainerIds::random(&mut rng);
+        self.team.iter()
+            .filter_map(|trainer_pokemon| pokemon_specie_by_id(trainer_pokemon.specie_id)
+                .map(|specie| Pokemon::from_specie(specie, trainer_pokemon.level,
+                    StatSet::uniform(TRAINER_POKEMON_IVS), Personality::random(trainer_ids, &mut rng))))
+            .collect()
+    }
+}
+
+/// How many tiles away the target is when it's standing on the line of tiles that the watcher is facing,
+/// no further than `sight_tiles`. Returns `None` when the watcher can't see the target
+pub fn tiles_in_sight(watcher_tile: (i32, i32), facing: &PlayerDirection, sight_tiles: i32, target_tile: (i32, i32)) -> Option<i32> {
+    let (dx, dy) = (target_tile.0 - watcher_tile.0, target_tile.1 - watcher_tile.1);
+    let distance = match facing {
+        PlayerDirection::Upwards if dx == 0 => -dy,
+        PlayerDirection::Downwards if dx == 0 => dy,
+        PlayerDirection::Left if dy == 0 => -dx,
+        PlayerDirection::Right if dy == 0 => dx,
+        _ => return None
+    };
+
+    if (1..=sight_tiles).contains(&distance) { Some(distance) } else { None }
+}
+
+/// The tile of the map where the given position is
+fn tile_of(position: Vector2) -> (i32, i32) {
+    (
+        (position.x / in_game_constant::TILE_SIZE).floor() as i32,
+        (position.y / in_game_constant::TILE_SIZE).floor() as i32
+    )
+}
+
+/// What a trainer of the overworld it's doing
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TrainerStatus {
+    /// Looking at the tiles in front of him, waiting for the player
+    Watching,
+    /// Spotted the player and it's walking up to him
+    Approaching,
+    /// Telling the pre battle dialogue
+    Talking,
+    /// Waiting for the battle against the player to end
+    Battling,
+    /// Already lost against the player. Doesn't challenge him anymore
+    Defeated,
+}
+
+impl Default for TrainerStatus {
+    fn default() -> Self { TrainerStatus::Watching }
+}
+
+#[derive(NativeClass)]
+#[inherit(Sprite)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+pub struct Trainer {
+    // The id of the trainer on the `all_trainers` catalogue. Set on the Godot GUI for each Trainer instance
+    #[property]
+    trainer_id: String,
+    // How many tiles in front of him the trainer can see
+    #[property]
+    sight_tiles: i32,
+    // Where the trainer it's looking at. One of "Upwards", "Downwards", "Left" or "Right"
+    #[property]
+    facing: String,
+    status: TrainerStatus,
+    // The position where the trainer stops walking, right in front of the player
+    approach_target: Vector2,
+}
+
+// The trainer prints his pre battle dialogue on the `DialogueBox`
+impl DialogueBoxActions for Trainer { }
+
+impl RegisterSignal<Self> for Trainer {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.signal("print_to_dialogue_box").done();
+    }
+}
+
+#[methods]
+impl Trainer {
+    fn new(_owner: &Sprite) -> Self {
+        Self {
+            trainer_id: "".to_string(),
+            sight_tiles: DEFAULT_SIGHT_TILES,
+            facing: "Downwards".to_string(),
+            status: TrainerStatus::default(),
+            approach_target: Vector2::new(0.0, 0.0),
+        }
+    }
+
+    #[method]
+    fn _ready(&mut self, #[base] base: TRef<Sprite>) {
+        base.set_process(true);
+        // Connects this trainer with the dialogue box
+        self.connect_signal_to_dialogue_box(&base);
+
+        // Defeated trainers are stored with the player data, and never challenge the player again
+        if self.is_defeated(base) {
+            self.status = TrainerStatus::Defeated;
+        }
+
+        // Deferred, like the `Game` one, that runs first because the `Game` was created before any trainer. So the `Game` has
+        // already checked for wild Pokémon on the same step
+        let player = unsafe { base.get_node("/root/Game/Player").unwrap().assume_safe() };
+        player.connect("player_step_completed", base, "_on_player_step_completed",
+            VariantArray::new_shared(), 1).unwrap();
+        let dialogue_box = unsafe { base.get_node("/root/Game/Player/Camera2D/CanvasLayer/DialogueBox").unwrap().assume_safe() };
+        dialogue_box.connect("dialogue_box_inactive", base, "_on_dialogue_box_inactive",
+            VariantArray::new_shared(), 0).unwrap();
+        let game = unsafe { base.get_node("/root/Game").unwrap().assume_safe() };
+        game.connect("trainer_battle_finished", base, "_on_trainer_battle_finished",
+            VariantArray::new_shared(), 0).unwrap();
+    }
+
+    /// Checks the progress of the running game, and the saved one for when the `Game` hasn't restored it yet
+    fn is_defeated(&self, base: TRef<Sprite>) -> bool {
+        let game = unsafe { base.get_node_as_instance::<Game>("/root/Game") };
+        game.and_then(|game| game.map(|game, _| game.player_data().has_defeated_trainer(&self.trainer_id)).ok())
+            .unwrap_or(false) ||
+            utils::retrieve_game_data().player_data().has_defeated_trainer(&self.trainer_id)
+    }
+
+    #[method]
+    /// Walks the trainer, one tile after another, until he stands in front of the player. Then starts talking
+    fn _process(&mut self, #[base] base: TRef<Sprite>, delta: f64) {
+        if self.status != TrainerStatus::Approaching {
+            return;
+        }
+
+        let position = base.global_position();
+        let step = in_game_constant::TILE_SIZE * (in_game_constant::WALK_SPEED * delta) as f32;
+        if position.distance_to(self.approach_target) <= step {
+            base.set_global_position(self.approach_target);
+            self.status = TrainerStatus::Talking;
+            self.emit_object_signal(base);
+        } else {
+            base.set_global_position(position + (self.approach_target - position).normalized() * step);
+        }
+    }
+
+    #[method]
+    /// Receives the `player_step_completed` signal of the `PlayerCharacter`. When the player stops on one of the tiles that
+    /// the trainer it's watching, the player gets frozen and the trainer walks up to him
+    fn _on_player_step_completed(&mut self, #[base] base: TRef<Sprite>) {
+        if self.status != TrainerStatus::Watching {
+            return;
+        }
+
+        // The step that started a wild battle doesn't start a trainer one too
+        let game = unsafe { base.get_node_as_instance::<Game>("/root/Game").unwrap() };
+        if game.map(|game, _| game.is_battle_running()).unwrap_or(true) {
+            return;
+        }
+
+        let player = unsafe { base.get_node_as::<Node2D>("/root/Game/Player").unwrap() };
+
+        let facing = PlayerDirection::from_string(&self.facing);
+        let distance = match tiles_in_sight(tile_of(base.global_position()), &facing, self.sight_tiles, tile_of(player.global_position())) {
+            Some(distance) => distance,
+            None => return
+        };
+
+        unsafe { player.call("handle_interaction", &[Variant::new("trainer_spotted")]) };
+        self.approach_target = base.global_position() + facing.to_vector() * in_game_constant::TILE_SIZE * (distance - 1) as f32;
+        self.status = TrainerStatus::Approaching;
+    }
+
+    #[method]
+    /// Sends the pre battle dialogue of the trainer to the `DialogueBox`
+    fn emit_object_signal(&self, #[base] base: TRef<Sprite>) {
+        let pre_battle_dialogue = all_trainers::trainer_by_id(&self.trainer_id)
+            .map_or("¡Te reto a un combate!".to_string(), |trainer| trainer.pre_battle_dialogue);
+        let dialogue_data = (0, Vec::<&str>::new(), vec![pre_battle_dialogue]);
+
+        base.emit_signal("print_to_dialogue_box", &[dialogue_data.to_variant()]);
+    }
+
+    #[method]
+    /// Receives the `dialogue_box_inactive` signal of the `DialogueBox`. When the pre battle dialogue ends, the battle starts
+    fn _on_dialogue_box_inactive(&mut self, #[base] base: TRef<Sprite>, _signal_info: Variant) {
+        if self.status != TrainerStatus::Talking {
+            return;
+        }
+
+        let game = unsafe { base.get_node_as_instance::<Game>("/root/Game").unwrap() };
+        let battle_started = game.map_mut(|game, game_base| game.start_trainer_battle(&game_base, &self.trainer_id))
+            .unwrap_or(false);
+        self.status = if battle_started { TrainerStatus::Battling } else { TrainerStatus::Watching };
+    }
+
+    #[method]
+    /// Receives the `trainer_battle_finished` signal of the `Game`, with the trainer that fought and if the player won
+    fn _on_trainer_battle_finished(&mut self, trainer_id: String, player_won: bool) {
+        if trainer_id != self.trainer_id || self.status != TrainerStatus::Battling {
+            return;
+        }
+        self.status = if player_won { TrainerStatus::Defeated } else { TrainerStatus::Watching };
+    }
+}
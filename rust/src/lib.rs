@@ -9,9 +9,12 @@ use game_client::login_screen::LoginScreen;
 use game::game::Game;
 use game::pokemon::Pokemon;
 use game::pokemon_specie::PokemonSpecie;
+use game::pokemon_move::PokemonMove;
+use game::game_item::GameItem;
 use game::pokemon_database::PokemonDB;
 use game::area_scene_switcher::AreaSceneSwitcher;
 use game::tall_grass::TallGrass;
+use game::trainer::Trainer;
 use game::grass_step_effect::GrassStepEffect;
 use game::landing_dust_effect::LandingDustEffect;
 use game::map::Map;
@@ -19,6 +22,12 @@ use game::player::{PlayerAnimation, PlayerCharacter};
 use game::dialogue_box::DialogueBox;
 use game::menu::menu::Menu;
 use game::menu::pokedex::pokedex::Pokedex;
+use game::menu::bag::bag_screen::BagScreen;
+use game::menu::party::party_screen::PartyScreen;
+use game::menu::options::options_screen::OptionsScreen;
+use game::battle::battle_scene::BattleScene;
+use game::battle::battle_ui::BattleUi;
+use game::species_registry::SpeciesRegistry;
 
 use game::map_elements__galicia::{
     area1_pueblo_de_teo,
@@ -27,6 +36,9 @@ use game::map_elements__galicia::{
 
 
 fn init(handle: InitHandle) {
+    // The species are read from the data file of the project when they're first needed, through Godot
+    SpeciesRegistry::set_data_reader(utils::utils::read_project_file);
+
     // Here we register the Rust Structs that will register as classes on GDScript
 
     //First just will register a class that only prints a greet on the Godot Engine console
@@ -38,16 +50,24 @@ fn init(handle: InitHandle) {
     handle.add_class::<Game>();
     handle.add_class::<Pokemon>();
     handle.add_class::<PokemonSpecie>();
+    handle.add_class::<PokemonMove>();
+    handle.add_class::<GameItem>();
     handle.add_class::<PokemonDB>();
     handle.add_class::<Map>();
     handle.add_class::<AreaSceneSwitcher>();
     handle.add_class::<TallGrass>();
+    handle.add_class::<Trainer>();
     handle.add_class::<GrassStepEffect>();
     handle.add_class::<LandingDustEffect>();
     
     handle.add_class::<Menu>();
     handle.add_class::<DialogueBox>();
     handle.add_class::<Pokedex>();
+    handle.add_class::<BagScreen>();
+    handle.add_class::<PartyScreen>();
+    handle.add_class::<OptionsScreen>();
+    handle.add_class::<BattleScene>();
+    handle.add_class::<BattleUi>();
     
     handle.add_class::<area1_pueblo_de_teo::truck::Truck>();
 }
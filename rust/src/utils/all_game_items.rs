@@ -0,0 +1,130 @@
+use crate::game::game_item::{BagPocket, GameItem, HeldEffect, ItemEffect};
+use crate::game::pokemon_type::PokemonType;
+use crate::game::status_condition::MajorStatus;
+
+/// Returns the data of the item with the given name, if it's on the game
+pub fn game_item_by_name(name: &str) -> Option<GameItem> {
+    all_game_items_data().into_iter().find(|game_item| game_item.name == name)
+}
+
+pub fn all_game_items_data() -> Vec<GameItem> {
+    let mut all_game_items: Vec<GameItem> = Vec::new();
+
+    // Items
+    all_game_items.push(GameItem::new_item(1, "Piedra Fuego", BagPocket::Items, 3000,
+        "Piedra peculiar que hace evolucionar a ciertas especies de Pokémon. Es de color rojo anaranjado.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(2, "Piedra Hoja", BagPocket::Items, 3000,
+        "Piedra peculiar que hace evolucionar a ciertas especies de Pokémon. Tiene un dibujo de una hoja.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(3, "Piedra Agua", BagPocket::Items, 3000,
+        "Piedra peculiar que hace evolucionar a ciertas especies de Pokémon. Es de color azul claro.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(8, "Piedra Trueno", BagPocket::Items, 3000,
+        "Piedra peculiar que hace evolucionar a ciertas especies de Pokémon. Tiene un dibujo de un rayo.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(9, "Piedra Lunar", BagPocket::Items, 3000,
+        "Piedra peculiar que hace evolucionar a ciertas especies de Pokémon. Es tan negra como el cielo nocturno.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(25, "Cordón Unión", BagPocket::Items, 3000,
+        "Cordón peculiar que hace evolucionar a las especies de Pokémon que en otras regiones solo evolucionan al intercambiarlas.",
+        Some(ItemEffect::Evolution)));
+    all_game_items.push(GameItem::new_item(4, "Restos", BagPocket::Items, 0,
+        "Objeto para llevar. El Pokémon que lo lleva recupera PS poco a poco durante el combate.",
+        None)
+        .with_held_effect(HeldEffect::HealEachTurn { divisor: 16 }));
+    all_game_items.push(GameItem::new_item(5, "Carbón", BagPocket::Items, 9800,
+        "Objeto para llevar. Potencia los movimientos de tipo Fuego.",
+        None)
+        .with_held_effect(HeldEffect::BoostType { move_type: PokemonType::Fire, multiplier: 1.2 }));
+    all_game_items.push(GameItem::new_item(6, "Semilla Milagro", BagPocket::Items, 9800,
+        "Objeto para llevar. Potencia los movimientos de tipo Planta.",
+        None)
+        .with_held_effect(HeldEffect::BoostType { move_type: PokemonType::Grass, multiplier: 1.2 }));
+    all_game_items.push(GameItem::new_item(7, "Agua Mística", BagPocket::Items, 9800,
+        "Objeto para llevar. Potencia los movimientos de tipo Agua.",
+        None)
+        .with_held_effect(HeldEffect::BoostType { move_type: PokemonType::Water, multiplier: 1.2 }));
+
+    // Medicine
+    all_game_items.push(GameItem::new_item(10, "Poción", BagPocket::Medicine, 200,
+        "Spray que cura las heridas. Restaura 20 PS de un Pokémon.",
+        Some(ItemEffect::HealHp { amount: 20 })));
+    all_game_items.push(GameItem::new_item(11, "Superpoción", BagPocket::Medicine, 700,
+        "Spray que cura las heridas. Restaura 60 PS de un Pokémon.",
+        Some(ItemEffect::HealHp { amount: 60 })));
+    all_game_items.push(GameItem::new_item(12, "Hiperpoción", BagPocket::Medicine, 1500,
+        "Spray que cura las heridas. Restaura 120 PS de un Pokémon.",
+        Some(ItemEffect::HealHp { amount: 120 })));
+    all_game_items.push(GameItem::new_item(13, "Revivir", BagPocket::Medicine, 2000,
+        "Reanima a un Pokémon debilitado y le restaura la mitad de sus PS.",
+        Some(ItemEffect::Revive { hp_percent: 50 })));
+    all_game_items.push(GameItem::new_item(15, "Antídoto", BagPocket::Medicine, 100,
+        "Spray medicinal que cura el envenenamiento de un Pokémon.",
+        Some(ItemEffect::CureStatus { status: Some(MajorStatus::Poison) })));
+    all_game_items.push(GameItem::new_item(16, "Antiquemar", BagPocket::Medicine, 250,
+        "Spray medicinal que cura las quemaduras de un Pokémon.",
+        Some(ItemEffect::CureStatus { status: Some(MajorStatus::Burn) })));
+    all_game_items.push(GameItem::new_item(17, "Antiparalizador", BagPocket::Medicine, 200,
+        "Spray medicinal que cura la parálisis de un Pokémon.",
+        Some(ItemEffect::CureStatus { status: Some(MajorStatus::Paralysis) })));
+    all_game_items.push(GameItem::new_item(18, "Despertar", BagPocket::Medicine, 250,
+        "Spray medicinal que despierta a un Pokémon dormido.",
+        Some(ItemEffect::CureStatus { status: Some(MajorStatus::Sleep { turns_left: 0 }) })));
+    all_game_items.push(GameItem::new_item(19, "Antihielo", BagPocket::Medicine, 250,
+        "Spray medicinal que descongela a un Pokémon congelado.",
+        Some(ItemEffect::CureStatus { status: Some(MajorStatus::Freeze) })));
+    all_game_items.push(GameItem::new_item(24, "Cura Total", BagPocket::Medicine, 600,
+        "Spray medicinal que cura cualquier problema de estado de un Pokémon.",
+        Some(ItemEffect::CureStatus { status: None })));
+    all_game_items.push(GameItem::new_item(14, "Elixir", BagPocket::Medicine, 0,
+        "Restaura 10 PP de cada uno de los movimientos de un Pokémon.",
+        Some(ItemEffect::RestorePp { amount: 10 })));
+
+    // Poké Balls
+    all_game_items.push(GameItem::new_item(20, "Poké Ball", BagPocket::PokeBalls, 200,
+        "Dispositivo para atrapar Pokémon salvajes. Se lanza como una pelota y sirve de cápsula.",
+        Some(ItemEffect::CatchRate { multiplier: 1.0 })));
+    all_game_items.push(GameItem::new_item(21, "Super Ball", BagPocket::PokeBalls, 600,
+        "Poké Ball de alto rendimiento que ofrece más probabilidades de atrapar Pokémon que una normal.",
+        Some(ItemEffect::CatchRate { multiplier: 1.5 })));
+    all_game_items.push(GameItem::new_item(22, "Ultra Ball", BagPocket::PokeBalls, 800,
+        "Poké Ball de muy alto rendimiento que ofrece más probabilidades de atrapar Pokémon que una Super Ball.",
+        Some(ItemEffect::CatchRate { multiplier: 2.0 })));
+    all_game_items.push(GameItem::new_item(23, "Master Ball", BagPocket::PokeBalls, 0,
+        "La mejor Poké Ball, con la que se atrapa a cualquier Pokémon salvaje sin fallar.",
+        Some(ItemEffect::CatchRate { multiplier: 255.0 })));
+
+    // TMs
+    all_game_items.push(GameItem::new_item(30, "MT Hoja Afilada", BagPocket::TMs, 3000,
+        "Enseña a un Pokémon el movimiento Hoja Afilada.",
+        Some(ItemEffect::TeachMove { move_name: "Hoja Afilada".to_string() })));
+    all_game_items.push(GameItem::new_item(31, "MT Ascuas", BagPocket::TMs, 3000,
+        "Enseña a un Pokémon el movimiento Ascuas.",
+        Some(ItemEffect::TeachMove { move_name: "Ascuas".to_string() })));
+
+    // Berries
+    all_game_items.push(GameItem::new_item(40, "Baya Aranja", BagPocket::Berries, 20,
+        "Restaura 10 PS de un Pokémon. Si la lleva, se la come al quedarse con la mitad de sus PS.",
+        Some(ItemEffect::HealHp { amount: 10 }))
+        .with_held_effect(HeldEffect::HealAtHalfHp { amount: 10 }));
+    all_game_items.push(GameItem::new_item(41, "Baya Zidra", BagPocket::Berries, 20,
+        "Restaura 30 PS de un Pokémon. Si la lleva, se la come al quedarse con la mitad de sus PS.",
+        Some(ItemEffect::HealHp { amount: 30 }))
+        .with_held_effect(HeldEffect::HealAtHalfHp { amount: 30 }));
+    all_game_items.push(GameItem::new_item(42, "Baya Ziuela", BagPocket::Berries, 20,
+        "Cura cualquier problema de estado de un Pokémon. Si la lleva, se la come en cuanto sufre uno.",
+        Some(ItemEffect::CureStatus { status: None }))
+        .with_held_effect(HeldEffect::CureStatus { status: None }));
+
+    // Key items
+    all_game_items.push(GameItem::new_item(50, "Bicicleta", BagPocket::KeyItems, 0,
+        "Bicicleta plegable que permite moverse mucho más rápido que corriendo.",
+        None));
+    all_game_items.push(GameItem::new_item(51, "Mapa", BagPocket::KeyItems, 0,
+        "Mapa de Galicia, con los pueblos y ciudades que ya has visitado.",
+        None));
+
+    // RETURN
+    all_game_items
+}
@@ -0,0 +1,60 @@
+use crate::game::pokemon_move::{MoveCategory, MoveEffect, MoveTarget, PokemonMove, StageStat, STRUGGLE};
+use crate::game::pokemon_type::PokemonType;
+
+/// Returns the data of the move with the given name, if it's on the game
+pub fn pokemon_move_by_name(name: &str) -> Option<PokemonMove> {
+    all_pokemon_moves_data().into_iter().find(|pokemon_move| pokemon_move.name == name)
+}
+
+pub fn all_pokemon_moves_data() -> Vec<PokemonMove> {
+    let mut all_pokemon_moves: Vec<PokemonMove> = Vec::new();
+
+    // Normal
+    all_pokemon_moves.push(PokemonMove::new_move(10, "Arañazo", PokemonType::Normal, MoveCategory::Physical,
+        40, Some(100), 35, 0, None));
+    all_pokemon_moves.push(PokemonMove::new_move(33, "Placaje", PokemonType::Normal, MoveCategory::Physical,
+        40, Some(100), 35, 0, None));
+    all_pokemon_moves.push(PokemonMove::new_move(45, "Gruñido", PokemonType::Normal, MoveCategory::Status,
+        0, Some(100), 40, 0,
+        Some(MoveEffect::StatChange { stat: StageStat::Attack, stages: -1, affects_user: false, chance: 100 }))
+        .with_target(MoveTarget::AdjacentFoes));
+    all_pokemon_moves.push(PokemonMove::new_move(98, "Ataque Rápido", PokemonType::Normal, MoveCategory::Physical,
+        40, Some(100), 30, 1, None));
+    all_pokemon_moves.push(PokemonMove::new_move(108, "Pantalla de Humo", PokemonType::Normal, MoveCategory::Status,
+        0, Some(100), 20, 0,
+        Some(MoveEffect::StatChange { stat: StageStat::Accuracy, stages: -1, affects_user: false, chance: 100 })));
+    all_pokemon_moves.push(PokemonMove::new_move(165, STRUGGLE, PokemonType::Normal, MoveCategory::Physical,
+        50, None, 1, 0, Some(MoveEffect::Recoil { percent: 25 })));
+    all_pokemon_moves.push(PokemonMove::new_move(184, "Cara Susto", PokemonType::Normal, MoveCategory::Status,
+        0, Some(100), 10, 0,
+        Some(MoveEffect::StatChange { stat: StageStat::Speed, stages: -2, affects_user: false, chance: 100 })));
+
+    // Grass
+    all_pokemon_moves.push(PokemonMove::new_move(22, "Látigo Cepa", PokemonType::Grass, MoveCategory::Physical,
+        45, Some(100), 25, 0, None));
+    all_pokemon_moves.push(PokemonMove::new_move(73, "Drenadoras", PokemonType::Grass, MoveCategory::Status,
+        0, Some(90), 10, 0, Some(MoveEffect::LeechSeed)));
+    all_pokemon_moves.push(PokemonMove::new_move(75, "Hoja Afilada", PokemonType::Grass, MoveCategory::Physical,
+        55, Some(95), 25, 0, Some(MoveEffect::HighCriticalHitRatio)));
+    all_pokemon_moves.push(PokemonMove::new_move(79, "Somnífero", PokemonType::Grass, MoveCategory::Status,
+        0, Some(75), 15, 0, Some(MoveEffect::Sleep { chance: 100 })));
+    all_pokemon_moves.push(PokemonMove::new_move(72, "Megaagotar", PokemonType::Grass, MoveCategory::Special,
+        40, Some(100), 15, 0, Some(MoveEffect::Drain { percent: 50 })));
+
+    // Poison
+    all_pokemon_moves.push(PokemonMove::new_move(77, "Polvo Veneno", PokemonType::Poison, MoveCategory::Status,
+        0, Some(75), 35, 0, Some(MoveEffect::Poison { chance: 100 })));
+
+    // Fire
+    all_pokemon_moves.push(PokemonMove::new_move(52, "Ascuas", PokemonType::Fire, MoveCategory::Special,
+        40, Some(100), 25, 0, Some(MoveEffect::Burn { chance: 10 })));
+    all_pokemon_moves.push(PokemonMove::new_move(424, "Colmillo Ígneo", PokemonType::Fire, MoveCategory::Physical,
+        65, Some(95), 15, 0, Some(MoveEffect::Burn { chance: 10 })));
+
+    // Dragon
+    all_pokemon_moves.push(PokemonMove::new_move(82, "Furia Dragón", PokemonType::Dragon, MoveCategory::Special,
+        0, Some(100), 10, 0, Some(MoveEffect::FixedDamage { damage: 40 })));
+
+    // RETURN
+    all_pokemon_moves
+}
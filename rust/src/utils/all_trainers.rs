@@ -0,0 +1,26 @@
+use crate::game::battle::battle_ai::AiDifficulty;
+use crate::game::battle::battle_event::BattleFormat;
+use crate::game::trainer::{TrainerData, TrainerPokemon};
+
+/// Returns the data of the trainer with the given id, if it's on the game
+pub fn trainer_by_id(id: &str) -> Option<TrainerData> {
+    all_trainers_data().into_iter().find(|trainer| trainer.id == id)
+}
+
+pub fn all_trainers_data() -> Vec<TrainerData> {
+    let mut all_trainers: Vec<TrainerData> = Vec::new();
+
+    // Campo de Pruebas
+    all_trainers.push(TrainerData::new("campo_de_pruebas_joven_brais", "Joven Brais",
+        vec![TrainerPokemon::new(4, 5)], AiDifficulty::Greedy,
+        "¡Eh, tú! ¡Nuestras miradas se han cruzado! ¡Eso significa que tenemos que combatir!"));
+    all_trainers.push(TrainerData::new("campo_de_pruebas_chica_uxia", "Chica Uxía",
+        vec![TrainerPokemon::new(1, 5), TrainerPokemon::new(4, 6)], AiDifficulty::Smart,
+        "¿Vienes a entrenar al Campo de Pruebas? ¡Pues a ver qué tal lo haces contra mí!"));
+    all_trainers.push(TrainerData::new("campo_de_pruebas_gemelas_iria_y_noa", "Gemelas Iria y Noa",
+        vec![TrainerPokemon::new(1, 6), TrainerPokemon::new(4, 6), TrainerPokemon::new(1, 7)], AiDifficulty::Greedy,
+        "¡Somos las gemelas Iria y Noa! ¡Combatimos siempre juntas, así que prepara a dos de tus Pokémon!")
+        .with_format(BattleFormat::Double));
+
+    all_trainers
+}
@@ -0,0 +1,636 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::game::area_scene_switcher;
+use crate::game::battle::battle_event::BattleFormat;
+use crate::game::city::{City, GameCity};
+use crate::game::encounter::{self, EncounterTable};
+use crate::game::evolution::{EvolutionCondition, EvolutionTrigger};
+use crate::game::game_item::{GameItem, ItemEffect};
+use crate::game::language::LocalizedText;
+use crate::game::party::MAX_PARTY_SIZE;
+use crate::game::pokemon::{self, SHINY_SPRITES_PATH, SPRITES_PATH, SPRITE_FACINGS};
+use crate::game::pokemon_move::PokemonMove;
+use crate::game::pokemon_stats::MAX_LEVEL;
+use crate::game::species_registry::SpeciesRegistry;
+use crate::game::trainer::TrainerData;
+use crate::utils::all_game_items;
+use crate::utils::all_pokemon_moves;
+use crate::utils::all_trainers;
+
+/// The scenes that the code of the game loads by their path, instead of through another scene
+const SCENES_LOADED_BY_CODE: [&str; 12] = [
+    "res://godot/Game/Game.tscn",
+    "res://godot/Game/Map.tscn",
+    "res://godot/Game/Battle.tscn",
+    "res://godot/Game/Pokedex.tscn",
+    "res://godot/Game/PokedexItem.tscn",
+    "res://godot/Game/Party.tscn",
+    "res://godot/Game/Bag.tscn",
+    "res://godot/Game/Options.tscn",
+    "res://godot/Game/PokeDB.tscn",
+    "res://godot/Game/PokemonSpecieNode.tscn",
+    "res://godot/Game/GrassStepEffect.tscn",
+    "res://godot/Game/LandingDustEffect.tscn",
+];
+const TALL_GRASS_SCENE: &str = "res://godot/Game/TallGrass.tscn";
+const TRAINER_SCENE: &str = "res://godot/Game/Trainer.tscn";
+const SCENE_SWITCHER_SCENE: &str = "res://godot/Game/SceneSwitcher.tscn";
+/// The directions that a `Trainer` can be facing to
+const TRAINER_FACINGS: [&str; 4] = ["Upwards", "Downwards", "Left", "Right"];
+
+/// What the validation found on one kind of data of the game
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationSection {
+    pub name: &'static str,
+    // How many entries of this kind of data were checked
+    pub checked: usize,
+    // One line per problem found, saying where it is
+    pub problems: Vec<String>,
+    // One line per missing content that the game can work without, like a sprite that has a placeholder
+    pub warnings: Vec<String>,
+}
+
+impl ValidationSection {
+    fn new(name: &'static str) -> Self {
+        Self { name, checked: 0, problems: Vec::new(), warnings: Vec::new() }
+    }
+}
+
+/// A node of a Godot scene, as it's written on his `.tscn` file
+#[derive(Debug, Clone, PartialEq)]
+struct SceneNode {
+    name: String,
+    // `None` for the root node of the scene
+    parent: Option<String>,
+    // The path of the scene that the node instances, if it's an instance of another one
+    instance: Option<String>,
+    // The properties set on the Godot GUI, with the quotes of the strings already removed
+    properties: HashMap<String, String>,
+}
+
+/// The parts of a Godot scene that points to other files or to the data of the game
+#[derive(Debug, Clone, PartialEq)]
+struct SceneFile {
+    // The `res://` paths of the external resources, by their id, with their type
+    ext_resources: BTreeMap<String, (String, String)>,
+    nodes: Vec<SceneNode>,
+}
+
+impl SceneFile {
+    /// Reads the external resources and the nodes of the content of a `.tscn` file
+    fn parse(content: &str) -> Self {
+        let mut scene_file = Self { ext_resources: BTreeMap::new(), nodes: Vec::new() };
+        let mut inside_node = false;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') && line.ends_with(']') {
+                inside_node = false;
+                if line.starts_with("[ext_resource ") {
+                    if let (Some(id), Some(path)) = (header_attribute(line, "id"), header_attribute(line, "path")) {
+                        scene_file.ext_resources.insert(id, (path, header_attribute(line, "type").unwrap_or_default()));
+                    }
+                } else if line.starts_with("[node ") {
+                    let instance = header_attribute(line, "instance")
+                        .and_then(|id| scene_file.ext_resources.get(&id))
+                        .map(|(path, _)| path.to_owned());
+                    scene_file.nodes.push(SceneNode {
+                        name: header_attribute(line, "name").unwrap_or_default(),
+                        parent: header_attribute(line, "parent"),
+                        instance,
+                        properties: HashMap::new(),
+                    });
+                    inside_node = true;
+                }
+            } else if let (true, Some((key, value))) = (inside_node, line.split_once(" = ")) {
+                if let Some(node) = scene_file.nodes.last_mut() {
+                    node.properties.insert(key.to_string(), value.trim_matches('"').to_string());
+                }
+            }
+        }
+        scene_file
+    }
+
+    /// The name of the root node of the scene
+    fn root_name(&self) -> &str {
+        self.nodes.iter()
+            .find(|node| node.parent.is_none())
+            .map_or("", |node| node.name.as_str())
+    }
+
+    /// The nodes that are instances of the scene on the given path
+    fn instances_of<'a>(&'a self, scene_path: &'a str) -> impl Iterator<Item = &'a SceneNode> + 'a {
+        self.nodes.iter().filter(move |node| node.instance.as_deref() == Some(scene_path))
+    }
+}
+
+/// The value of an attribute of the header of a section of a `.tscn` file, like the `path` of `[ext_resource path="res://..." id=1]`.
+/// The `ExtResource( 4 )` values are returned as the id of the resource
+fn header_attribute(header: &str, key: &str) -> Option<String> {
+    let start = header.find(&format!(" {}=", key))? + key.len() + 2;
+    let value = &header[start..];
+    if let Some(quoted) = value.strip_prefix('"') {
+        quoted.split('"').next().map(str::to_string)
+    } else if let Some(resource) = value.strip_prefix("ExtResource(") {
+        resource.split(')').next().map(|id| id.trim().to_string())
+    } else {
+        value.split([' ', ']']).next().map(str::to_string)
+    }
+}
+
+/// Loads every data file of the game, from the Godot project on the given folder, and cross-checks them:
+/// the species, the moves, the items, the wild encounter tables, the trainers with their dialogues, the maps with their scenes
+/// and the sprites of every specie.
+///
+/// The species are read from the data file on the disk, not from the one bundled on the library, so they can be checked before
+/// building the game again
+pub fn validate_game_data(project_root: &Path) -> Vec<ValidationSection> {
+    let moves = all_pokemon_moves::all_pokemon_moves_data();
+    let items = all_game_items::all_game_items_data();
+    let trainers = all_trainers::all_trainers_data();
+    let (species_section, registry) = validate_species(project_root, &moves, &items);
+    let (scenes_section, scenes) = validate_scenes(project_root);
+
+    vec![
+        species_section,
+        validate_moves(&moves),
+        validate_items(&items, &moves),
+        validate_encounters(project_root, registry.as_ref(), &scenes),
+        validate_trainers(&trainers, registry.as_ref(), &scenes),
+        scenes_section,
+        validate_sprites(project_root, registry.as_ref()),
+    ]
+}
+
+/// Reads the species data file, and checks that every specie only points to moves, items and species that exist
+fn validate_species(project_root: &Path, moves: &[PokemonMove], items: &[GameItem]) -> (ValidationSection, Option<SpeciesRegistry>) {
+    let species_path = project_root.join("godot/Data/pokemon_species.json");
+    let registry = match fs::read_to_string(&species_path) {
+        Ok(json) => SpeciesRegistry::from_json(&json),
+        Err(err) => Err(format!("Can't open {}: {}", species_path.display(), err))
+    };
+    match registry {
+        Ok(registry) => (check_species(&registry, moves, items), Some(registry)),
+        Err(err) => {
+            let mut section = ValidationSection::new("Species");
+            section.problems.extend(err.lines().map(str::to_string));
+            (section, None)
+        }
+    }
+}
+
+/// Checks the values of every specie of the registry, and the moves, items and species that they point to
+fn check_species(registry: &SpeciesRegistry, moves: &[PokemonMove], items: &[GameItem]) -> ValidationSection {
+    let mut section = ValidationSection::new("Species");
+    for specie in registry.species() {
+        section.checked += 1;
+        let mut problem = |problem: String| section.problems.push(format!("{} (#{}): {}", specie.name, specie.id, problem));
+
+        if specie.id < 1 || specie.name.is_empty() {
+            problem("the species need a Pokédex number from 1 onwards and a name".to_string());
+        }
+        if !(1..=255).contains(&specie.catch_rate) {
+            problem(format!("the catch rate {} isn't between 1 and 255", specie.catch_rate));
+        }
+        if specie.height <= 0.0 || specie.weight <= 0.0 {
+            problem("the height and the weight must be greater than zero".to_string());
+        }
+        for (field, text) in [("category", &specie.category), ("pokedex_description", &specie.pokedex_description)] {
+            if text.en.is_empty() {
+                problem(format!("the {} doesn't have the english text, that the rest of languages fall back to", field));
+            }
+            if text == &LocalizedText::default() {
+                problem(format!("the {} is empty", field));
+            }
+        }
+        for learnable in specie.learnset.iter() {
+            if !moves.iter().any(|pokemon_move| pokemon_move.name == learnable.move_name) {
+                problem(format!("learns the unknown move \"{}\"", learnable.move_name));
+            }
+            if !(1..=MAX_LEVEL).contains(&learnable.level) {
+                problem(format!("learns {} at the level {}", learnable.move_name, learnable.level));
+            }
+        }
+        for evolution in specie.evolutions.iter() {
+            if evolution.evolves_into == specie.id || !registry.contains(evolution.evolves_into) {
+                problem(format!("evolves into #{}, that isn't another specie of the data file", evolution.evolves_into));
+            }
+            if let EvolutionTrigger::UseItem { item_name } = &evolution.trigger {
+                match items.iter().find(|game_item| &game_item.name == item_name) {
+                    Some(game_item) if game_item.effect == Some(ItemEffect::Evolution) => (),
+                    Some(_) => problem(format!("evolves with \"{}\", that isn't an evolution item", item_name)),
+                    None => problem(format!("evolves with the unknown item \"{}\"", item_name))
+                }
+            }
+            for condition in evolution.conditions.iter() {
+                if let EvolutionCondition::MinLevel { level } = condition {
+                    if !(1..=MAX_LEVEL).contains(level) {
+                        problem(format!("evolves at the level {}", level));
+                    }
+                }
+            }
+        }
+    }
+    section
+}
+
+/// Checks that the names and the ids of the moves are unique, and that their values are on range
+fn validate_moves(moves: &[PokemonMove]) -> ValidationSection {
+    let mut section = ValidationSection::new("Moves");
+    let mut ids = HashSet::new();
+    let mut names = HashSet::new();
+    for pokemon_move in moves.iter() {
+        section.checked += 1;
+        let mut problem = |problem: String| section.problems.push(format!("{} (#{}): {}", pokemon_move.name, pokemon_move.id, problem));
+
+        if !ids.insert(pokemon_move.id) {
+            problem("the id is already used by another move".to_string());
+        }
+        if !names.insert(pokemon_move.name.to_owned()) {
+            problem("the name is already used by another move".to_string());
+        }
+        if pokemon_move.pp < 1 {
+            problem(format!("has {} PP", pokemon_move.pp));
+        }
+        if let Some(accuracy) = pokemon_move.accuracy.filter(|accuracy| !(1..=100).contains(accuracy)) {
+            problem(format!("the accuracy {} isn't between 1 and 100", accuracy));
+        }
+    }
+    section
+}
+
+/// Checks that the names and the ids of the items are unique, and that the TMs teach moves that exist
+fn validate_items(items: &[GameItem], moves: &[PokemonMove]) -> ValidationSection {
+    let mut section = ValidationSection::new("Items");
+    let mut ids = HashSet::new();
+    let mut names = HashSet::new();
+    for game_item in items.iter() {
+        section.checked += 1;
+        let mut problem = |problem: String| section.problems.push(format!("{} (#{}): {}", game_item.name, game_item.id, problem));
+
+        if !ids.insert(game_item.id) {
+            problem("the id is already used by another item".to_string());
+        }
+        if !names.insert(game_item.name.to_owned()) {
+            problem("the name is already used by another item".to_string());
+        }
+        if game_item.price < 0 {
+            problem(format!("costs {}", game_item.price));
+        }
+        if let Some(ItemEffect::TeachMove { move_name }) = &game_item.effect {
+            if !moves.iter().any(|pokemon_move| &pokemon_move.name == move_name) {
+                problem(format!("teaches the unknown move \"{}\"", move_name));
+            }
+        }
+    }
+    section
+}
+
+/// Checks the wild encounter tables of `godot/Encounters`: every file belongs to a city, every slot is a specie that knows
+/// some move at his levels, and every tall grass of the cities uses an area that has a table
+fn validate_encounters(project_root: &Path, registry: Option<&SpeciesRegistry>, scenes: &BTreeMap<String, SceneFile>) -> ValidationSection {
+    let mut section = ValidationSection::new("Encounter tables");
+    let mut tables_by_city: HashMap<String, Vec<EncounterTable>> = HashMap::new();
+
+    for path in files_with_extension(&project_root.join("godot/Encounters"), "json") {
+        let file_name = path.file_name().map_or(String::new(), |file_name| file_name.to_string_lossy().to_string());
+        let city_node_name = path.file_stem().map_or(String::new(), |file_stem| file_stem.to_string_lossy().to_string());
+        if GameCity::from_node_name(&city_node_name).is_none() {
+            section.problems.push(format!("{}: there isn't any city or town called {}", file_name, city_node_name));
+        }
+        let encounter_tables = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_str::<Vec<EncounterTable>>(&json).map_err(|err| err.to_string()));
+        let encounter_tables = match encounter_tables {
+            Ok(encounter_tables) => encounter_tables,
+            Err(err) => {
+                section.problems.push(format!("{}: {}", file_name, err));
+                continue;
+            }
+        };
+
+        check_encounter_tables(&mut section, &file_name, &encounter_tables, registry);
+        tables_by_city.insert(city_node_name, encounter_tables);
+    }
+
+    for game_city in GameCity::values() {
+        let city_node_name = City::new(game_city.to_fmt_string(), None).get_as_node_path();
+        let scene_file = match scenes.get(&location_scene_path(&game_city)) {
+            Some(scene_file) => scene_file,
+            None => continue
+        };
+        for tall_grass in scene_file.instances_of(TALL_GRASS_SCENE) {
+            let area = tall_grass.properties.get("encounter_area").map_or(encounter::DEFAULT_ENCOUNTER_AREA, String::as_str);
+            let city_tables = tables_by_city.get(&city_node_name).map_or(&[][..], Vec::as_slice);
+            if encounter::table_for_area(city_tables, area).is_none() {
+                section.problems.push(format!("{}.json: the tall grass {} of {} uses the area \"{}\", that doesn't have a table",
+                    city_node_name, tall_grass.name, game_city.to_fmt_string(), area));
+            }
+        }
+    }
+
+    section
+}
+
+/// Checks the encounter tables of one file: one table per area, with the rates on range and slots of existing species
+/// that know some move at their levels
+fn check_encounter_tables(section: &mut ValidationSection, file_name: &str, encounter_tables: &[EncounterTable], registry: Option<&SpeciesRegistry>) {
+    let mut areas = HashSet::new();
+    for encounter_table in encounter_tables.iter() {
+        section.checked += 1;
+        let mut problem = |problem: String| section.problems.push(format!("{} ({}): {}", file_name, encounter_table.area, problem));
+
+        if !areas.insert(encounter_table.area.to_owned()) {
+            problem("the area has another table on the same file".to_string());
+        }
+        if !(0..=100).contains(&encounter_table.encounter_rate) || !(0..=100).contains(&encounter_table.double_battle_rate) {
+            problem("the encounter rates are percentages, between 0 and 100".to_string());
+        }
+        for (idx, slot) in encounter_table.slots.iter().enumerate() {
+            if slot.min_level < 1 || slot.min_level > slot.max_level || slot.max_level > MAX_LEVEL {
+                problem(format!("the slot #{} has the levels {}-{}", idx, slot.min_level, slot.max_level));
+            }
+            if slot.rarity < 1 {
+                problem(format!("the slot #{} never appears, with a rarity of {}", idx, slot.rarity));
+            }
+            match registry.map(|registry| registry.get(slot.specie_id)) {
+                Some(Some(specie)) if specie.moves_known_at(slot.min_level).is_empty() =>
+                    problem(format!("the slot #{} is a {} that doesn't know any move at the level {}", idx, specie.name, slot.min_level)),
+                Some(None) => problem(format!("the slot #{} names the unknown specie #{}", idx, slot.specie_id)),
+                _ => ()
+            }
+        }
+    }
+}
+
+/// Checks the trainers of the game and their pre battle dialogues, and that every trainer placed on a scene is on the catalogue
+fn validate_trainers(trainers: &[TrainerData], registry: Option<&SpeciesRegistry>, scenes: &BTreeMap<String, SceneFile>) -> ValidationSection {
+    let mut section = ValidationSection::new("Trainers and dialogues");
+    let mut ids = HashSet::new();
+    for trainer in trainers.iter() {
+        section.checked += 1;
+        let mut problem = |problem: String| section.problems.push(format!("{} ({}): {}", trainer.name, trainer.id, problem));
+
+        if !ids.insert(trainer.id.to_owned()) {
+            problem("the id is already used by another trainer".to_string());
+        }
+        if trainer.pre_battle_dialogue.trim().is_empty() {
+            problem("doesn't have a pre battle dialogue".to_string());
+        }
+        let required_pokemon = if trainer.format == BattleFormat::Double { 2 } else { 1 };
+        if trainer.team.len() < required_pokemon || trainer.team.len() > MAX_PARTY_SIZE {
+            problem(format!("has {} Pokémon on a {:?} battle", trainer.team.len(), trainer.format));
+        }
+        for trainer_pokemon in trainer.team.iter() {
+            if !(1..=MAX_LEVEL).contains(&trainer_pokemon.level) {
+                problem(format!("has a Pokémon at the level {}", trainer_pokemon.level));
+            }
+            match registry.map(|registry| registry.get(trainer_pokemon.specie_id)) {
+                Some(Some(specie)) if specie.moves_known_at(trainer_pokemon.level).is_empty() =>
+                    problem(format!("has a {} that doesn't know any move at the level {}", specie.name, trainer_pokemon.level)),
+                Some(None) => problem(format!("has a Pokémon of the unknown specie #{}", trainer_pokemon.specie_id)),
+                _ => ()
+            }
+        }
+    }
+
+    for (scene_path, scene_file) in scenes.iter() {
+        for trainer_node in scene_file.instances_of(TRAINER_SCENE) {
+            let mut problem = |problem: String| section.problems.push(format!("{} ({}): {}", scene_path, trainer_node.name, problem));
+
+            match trainer_node.properties.get("trainer_id") {
+                Some(trainer_id) if !ids.contains(trainer_id) => problem(format!("places the unknown trainer \"{}\"", trainer_id)),
+                None => problem("places a trainer without trainer_id".to_string()),
+                _ => ()
+            }
+            if let Some(facing) = trainer_node.properties.get("facing").filter(|facing| !TRAINER_FACINGS.contains(&facing.as_str())) {
+                problem(format!("faces to \"{}\", that isn't one of {:?}", facing, TRAINER_FACINGS));
+            }
+        }
+    }
+
+    section
+}
+
+/// Reads every scene of the project, and checks that the scenes and scripts that they point to exist, that every city and
+/// town has his location scene, and that every scene switcher leads to an existing scene
+fn validate_scenes(project_root: &Path) -> (ValidationSection, BTreeMap<String, SceneFile>) {
+    let mut section = ValidationSection::new("Maps and scenes");
+    let mut scenes = BTreeMap::new();
+    for path in files_with_extension(&project_root.join("godot"), "tscn") {
+        let scene_path = to_res_path(project_root, &path);
+        match fs::read_to_string(&path) {
+            Ok(content) => { scenes.insert(scene_path, SceneFile::parse(&content)); },
+            Err(err) => section.problems.push(format!("{}: {}", scene_path, err))
+        }
+    }
+    let exists = |res_path: &str| project_path(project_root, res_path).map_or(false, |path| path.is_file());
+
+    for (scene_path, scene_file) in scenes.iter() {
+        section.checked += 1;
+        for (path, resource_type) in scene_file.ext_resources.values() {
+            if (resource_type == "PackedScene" || resource_type == "Script") && !exists(path) {
+                section.problems.push(format!("{}: points to the missing {} {}", scene_path, resource_type, path));
+            }
+        }
+        for switcher in scene_file.instances_of(SCENE_SWITCHER_SCENE) {
+            let parent_name = match switcher.parent.as_deref() {
+                Some(".") | None => scene_file.root_name(),
+                Some(parent) => parent.rsplit('/').next().unwrap_or(parent)
+            };
+            let scene_to_switch = area_scene_switcher::scene_to_switch_path(scene_file.root_name(), parent_name);
+            if !exists(&scene_to_switch) {
+                section.problems.push(format!("{}: the scene switcher of {} leads to the missing scene {}", scene_path, parent_name, scene_to_switch));
+            }
+        }
+    }
+
+    for game_city in GameCity::values() {
+        let location_scene = location_scene_path(&game_city);
+        if !scenes.contains_key(&location_scene) {
+            section.problems.push(format!("{}: doesn't have his location scene {}", game_city.to_fmt_string(), location_scene));
+        }
+    }
+    for scene_path in SCENES_LOADED_BY_CODE {
+        if !exists(scene_path) {
+            section.problems.push(format!("The game loads the missing scene {}", scene_path));
+        }
+    }
+
+    (section, scenes)
+}
+
+/// Checks that every specie has his front and back sprites, both on the regular and on the shiny sprite sets.
+/// They're only warnings, because the game shows a placeholder instead of the missing sprites.
+/// A whole missing folder it's reported once, instead of once per specie
+fn validate_sprites(project_root: &Path, registry: Option<&SpeciesRegistry>) -> ValidationSection {
+    let mut section = ValidationSection::new("Sprites");
+    let registry = match registry {
+        Some(registry) => registry,
+        None => return section
+    };
+
+    for sprite_set_path in [SPRITES_PATH, SHINY_SPRITES_PATH] {
+        for facing in SPRITE_FACINGS {
+            let folder = format!("{}/{}", sprite_set_path, facing);
+            if !project_path(project_root, &folder).map_or(false, |path| path.is_dir()) {
+                section.warnings.push(format!("The sprite folder {} doesn't exist", folder));
+                continue;
+            }
+            for specie in registry.species() {
+                section.checked += 1;
+                let sprite = pokemon::sprite_path(sprite_set_path, facing, specie.id);
+                if !project_path(project_root, &sprite).map_or(false, |path| path.is_file()) {
+                    section.warnings.push(format!("{} (#{}): doesn't have the sprite {}", specie.name, specie.id, sprite));
+                }
+            }
+        }
+    }
+
+    section
+}
+
+/// The scene of the given city or town, that the `Map` instances
+fn location_scene_path(game_city: &GameCity) -> String {
+    format!("res://godot/Game/Locations/Towns - Cities/{}.tscn", City::new(game_city.to_fmt_string(), None).get_as_node_path())
+}
+
+/// The `res://` path that Godot uses for a file of the project
+fn to_res_path(project_root: &Path, path: &Path) -> String {
+    let relative_path = path.strip_prefix(project_root).unwrap_or(path);
+    "res://".to_string() + &relative_path.to_string_lossy().replace('\\', "/")
+}
+
+/// The file or folder of the project that a `res://` path points to. `None` when it isn't a `res://` path
+fn project_path(project_root: &Path, res_path: &str) -> Option<PathBuf> {
+    res_path.strip_prefix("res://").map(|relative_path| project_root.join(relative_path))
+}
+
+/// Every file with the given extension inside the folder and his subfolders, in order
+fn files_with_extension(folder: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending_folders = vec![folder.to_path_buf()];
+    while let Some(folder) = pending_folders.pop() {
+        let entries = match fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(_) => continue
+        };
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.is_dir() {
+                pending_folders.push(path);
+            } else if path.extension().map_or(false, |path_extension| path_extension == extension) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    use crate::game::battle::battle_ai::AiDifficulty;
+    use crate::game::trainer::TrainerPokemon;
+
+    fn shipped_species() -> &'static SpeciesRegistry {
+        SpeciesRegistry::global().expect("The shipped species data must load")
+    }
+
+    #[test]
+    fn the_shipped_game_data_has_no_problems() {
+        let project_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for section in validate_game_data(&project_root) {
+            assert!(section.checked > 0, "{} didn't check anything", section.name);
+            assert_eq!(section.problems, Vec::<String>::new(), "{}", section.name);
+        }
+    }
+
+    #[test]
+    fn finds_the_problems_of_a_bad_specie() {
+        let mut data: Value = serde_json::from_str(include_str!("../../../godot/Data/pokemon_species.json")).unwrap();
+        let species = data["species"].as_array_mut().unwrap();
+        species.truncate(1);
+        species[0]["catch_rate"] = 0.into();
+        species[0]["learnset"] = serde_json::json!([{ "level": 101, "move_name": "Movimiento Inventado" }]);
+        species[0]["evolutions"] = serde_json::json!([
+            { "evolves_into": 1, "trigger": "LevelUp", "conditions": [{ "MinLevel": { "level": 0 } }] }
+        ]);
+        let registry = SpeciesRegistry::from_json(&data.to_string()).unwrap();
+
+        let section = check_species(&registry, &all_pokemon_moves::all_pokemon_moves_data(), &all_game_items::all_game_items_data());
+        assert_eq!(section.checked, 1);
+        assert_eq!(section.problems, vec![
+            "Bulbasaur (#1): the catch rate 0 isn't between 1 and 255",
+            "Bulbasaur (#1): learns the unknown move \"Movimiento Inventado\"",
+            "Bulbasaur (#1): learns Movimiento Inventado at the level 101",
+            "Bulbasaur (#1): evolves into #1, that isn't another specie of the data file",
+            "Bulbasaur (#1): evolves at the level 0",
+        ]);
+    }
+
+    #[test]
+    fn finds_the_problems_of_bad_encounter_tables() {
+        let encounter_tables: Vec<EncounterTable> = serde_json::from_str(r#"[
+            { "area": "Hierba", "encounter_rate": 150, "slots": [
+                { "specie_id": 1, "min_level": 10, "max_level": 5, "rarity": 0 },
+                { "specie_id": 9999, "min_level": 2, "max_level": 3, "rarity": 10 }
+            ] },
+            { "area": "Hierba", "encounter_rate": 10, "slots": [] }
+        ]"#).unwrap();
+
+        let mut section = ValidationSection::new("Encounter tables");
+        check_encounter_tables(&mut section, "Prueba.json", &encounter_tables, Some(shipped_species()));
+        assert_eq!(section.checked, 2);
+        assert_eq!(section.problems, vec![
+            "Prueba.json (Hierba): the encounter rates are percentages, between 0 and 100",
+            "Prueba.json (Hierba): the slot #0 has the levels 10-5",
+            "Prueba.json (Hierba): the slot #0 never appears, with a rarity of 0",
+            "Prueba.json (Hierba): the slot #1 names the unknown specie #9999",
+            "Prueba.json (Hierba): the area has another table on the same file",
+        ]);
+    }
+
+    #[test]
+    fn finds_the_problems_of_bad_trainers_and_of_the_trainers_placed_on_the_scenes() {
+        let trainers = vec![
+            TrainerData::new("rival", "Rival", vec![TrainerPokemon::new(1, 0), TrainerPokemon::new(9999, 5)], AiDifficulty::Random, " "),
+            TrainerData::new("rival", "Gemelos", vec![TrainerPokemon::new(4, 10)], AiDifficulty::Smart, "¡A luchar!")
+                .with_format(BattleFormat::Double),
+        ];
+        let town = SceneFile::parse(r#"[gd_scene load_steps=2 format=2]
+
+[ext_resource path="res://godot/Game/Trainer.tscn" type="PackedScene" id=1]
+
+[node name="Town" type="Node2D"]
+
+[node name="Trainer" parent="." instance=ExtResource( 1 )]
+trainer_id = "desconocido"
+facing = "Upside"
+"#);
+        let scenes = BTreeMap::from([("res://Town.tscn".to_string(), town)]);
+
+        let section = validate_trainers(&trainers, Some(shipped_species()), &scenes);
+        assert_eq!(section.checked, 2);
+        assert_eq!(section.problems, vec![
+            "Rival (rival): doesn't have a pre battle dialogue".to_string(),
+            "Rival (rival): has a Pokémon at the level 0".to_string(),
+            "Rival (rival): has a Bulbasaur that doesn't know any move at the level 0".to_string(),
+            "Rival (rival): has a Pokémon of the unknown specie #9999".to_string(),
+            "Gemelos (rival): the id is already used by another trainer".to_string(),
+            "Gemelos (rival): has 1 Pokémon on a Double battle".to_string(),
+            "res://Town.tscn (Trainer): places the unknown trainer \"desconocido\"".to_string(),
+            format!("res://Town.tscn (Trainer): faces to \"Upside\", that isn't one of {:?}", TRAINER_FACINGS),
+        ]);
+    }
+
+    #[test]
+    fn the_missing_sprites_are_only_warnings() {
+        let section = validate_sprites(Path::new("/a/project/without/sprites"), Some(shipped_species()));
+        assert!(section.problems.is_empty());
+        assert_eq!(section.warnings.len(), SPRITE_FACINGS.len() * 2);
+        assert!(section.warnings.iter().all(|warning| warning.starts_with("The sprite folder ")));
+    }
+}
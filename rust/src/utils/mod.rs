@@ -3,4 +3,7 @@ pub mod utils;
 pub mod networking;
 // pub mod secret;
 
-pub mod all_pokemon_species;
\ No newline at end of file
+pub mod all_pokemon_moves;
+pub mod all_game_items;
+pub mod all_trainers;
+pub mod data_validation;
\ No newline at end of file
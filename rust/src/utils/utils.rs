@@ -5,6 +5,9 @@ use crate::game::game::Game;
 
 use crate::game_client::gamer::Gamer;
 use crate::game::player::PlayerDirection;
+use crate::game::city::{City, GameCity};
+use crate::game::encounter::EncounterTable;
+use crate::game::battle::battle_log::BattleLog;
 
 use chrono::{Datelike, Duration as Dur, NaiveDate, NaiveTime, Utc, Weekday};
 use chrono::prelude::{DateTime, Local};
@@ -196,6 +199,55 @@ pub fn save_game_data(player_data: &Game) {
     file.close();
 }
 
+/// Overwrites `godot/last_battle_log.json` with the log of the battle that just finished.
+///
+/// The `battle_replay` binary can play it again, headless, with exactly the same outcome
+pub fn save_battle_log(battle_log: &BattleLog) {
+    let json_battle_log = match battle_log.to_json() {
+        Ok(json_battle_log) => json_battle_log,
+        Err(err) => {
+            godot_print!("{}", err);
+            return;
+        }
+    };
+    let (file, _) = open_json_file(GodotString::from_str("last_battle_log"), File::WRITE);
+
+    file.store_string(&json_battle_log);
+    file.close();
+}
+
+/// Reads the wild encounter tables of the given city, stored on `godot/Encounters/<CityNodeName>.json`.
+///
+/// A city without encounters file, or with a wrong one, just doesn't have wild Pokémon
+pub fn load_encounter_tables(game_city: &GameCity) -> Vec<EncounterTable> {
+    let city_file_name = City::new(game_city.to_fmt_string(), None).get_as_node_path();
+    let (file, _) = open_json_file(GodotString::from_str("Encounters/".to_string() + &city_file_name), File::READ);
+
+    let json_encounters = file.get_as_text(false).to_string();
+    file.close();
+
+    match serde_json::from_str::<Vec<EncounterTable>>(&json_encounters) {
+        Ok(encounter_tables) => encounter_tables,
+        Err(err) => {
+            godot_print!("Error reading the encounters of {}: {:?}", game_city.to_fmt_string(), err);
+            Vec::new()
+        }
+    }
+}
+
+/// Reads the whole text of a `res://` file through Godot, so it's also found when it's packed on an exported game.
+/// Used by the `SpeciesRegistry` to read the species data file, falling back to his bundled copy when this fails
+pub fn read_project_file(res_path: &str) -> Result<String, String> {
+    let file = File::new();
+    if let Err(err) = file.open(res_path, File::READ) {
+        godot_print!("Can't open {}, using the data bundled with the game: {:?}", res_path, err);
+        return Err(format!("Can't open {}: {:?}", res_path, err));
+    }
+    let text = file.get_as_text(false).to_string();
+    file.close();
+    Ok(text)
+}
+
 pub fn open_json_file(file_name: GodotString, mode: i64) -> (Ref<File, Unique>, &'static JSON ){
     let file = File::new();
     let json = JSON::godot_singleton();